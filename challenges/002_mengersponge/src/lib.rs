@@ -1,12 +1,15 @@
 use graphics::{
-    rectangle, Drawable, DrawingContext, EventHandler, Graphics, InputContext, Runnable, Updatable,
-    WindowConfig,
+    rectangle, shortcuts::Shortcuts, Drawable, DrawingContext, EventHandler, Graphics,
+    InputContext, Runnable, Updatable, WindowConfig,
 };
-use piston::{Button, ButtonState, Key};
+use piston::{ButtonState, Key};
 
 const WINDOW_WIDTH: f64 = 1000.0;
 const WINDOW_HEIGHT: f64 = 1000.0;
 
+const ITERATE: &str = "iterate";
+const QUIT: &str = "quit";
+
 pub struct Menger {
     iteration: u32,
 }
@@ -67,14 +70,9 @@ impl EventHandler for Menger {
         if ctx.args.state != ButtonState::Release {
             return;
         }
-        let key = if let Button::Keyboard(key) = ctx.args.button {
-            key
-        } else {
-            return;
-        };
-        match key {
-            Key::Space => self.iteration += 1,
-            Key::Escape | Key::Q => std::process::exit(0),
+        match ctx.action {
+            Some(ITERATE) => self.iteration += 1,
+            Some(QUIT) => std::process::exit(0),
             _ => (),
         }
     }
@@ -90,4 +88,11 @@ impl Runnable for Menger {
             title: "Menger Sponge".to_owned(),
         }
     }
+
+    fn shortcuts(&self) -> Shortcuts {
+        Shortcuts::new()
+            .with_action(ITERATE, "next iteration", Key::Space)
+            .with_action(QUIT, "quit", Key::Escape)
+            .with_binding(QUIT, Key::Q)
+    }
 }
@@ -1,7 +1,8 @@
 use graphics::{
-    clear, rectangle, Drawable, DrawingContext, EventHandler, Graphics, InputContext, Runnable,
-    Updatable, UpdateContext, WindowConfig,
+    clear, rectangle, shortcuts::Shortcuts, Drawable, DrawingContext, EventHandler, Graphics,
+    InputContext, Runnable, Updatable, UpdateContext, WindowConfig,
 };
+use piston::Key;
 
 mod food;
 mod snake;
@@ -15,6 +16,11 @@ const GRID_SQUARE: f64 = 30.0;
 const X_RES: f64 = WIDTH / (GRID_SQUARE);
 const Y_RES: f64 = HEIGHT / (GRID_SQUARE);
 
+const UP: &str = "up";
+const LEFT: &str = "left";
+const DOWN: &str = "down";
+const RIGHT: &str = "right";
+
 pub struct SnakeGame {
     food: Food,
     snake: Snake,
@@ -87,4 +93,16 @@ impl Runnable for SnakeGame {
             title: "Snake".to_owned(),
         }
     }
+
+    fn shortcuts(&self) -> Shortcuts {
+        Shortcuts::new()
+            .with_action(UP, "turn up", Key::W)
+            .with_binding(UP, Key::Up)
+            .with_action(LEFT, "turn left", Key::A)
+            .with_binding(LEFT, Key::Left)
+            .with_action(DOWN, "turn down", Key::S)
+            .with_binding(DOWN, Key::Down)
+            .with_action(RIGHT, "turn right", Key::D)
+            .with_binding(RIGHT, Key::Right)
+    }
 }
@@ -1,9 +1,8 @@
-use super::GRID_SQUARE;
+use super::{DOWN, GRID_SQUARE, LEFT, RIGHT, UP};
 use graphics::{
     rectangle, Color, Drawable, DrawingContext, EventHandler, Graphics, InputContext, Updatable,
     UpdateContext,
 };
-use piston::{Button, Key};
 
 #[derive(Clone, Copy, PartialEq, Eq)]
 enum Dir {
@@ -128,17 +127,11 @@ impl Updatable for Snake {
 
 impl EventHandler for Snake {
     fn handle_input(&mut self, ctx: &InputContext) {
-        let key = if let Button::Keyboard(key) = ctx.args.button {
-            key
-        } else {
-            return;
-        };
-
-        match key {
-            Key::W | Key::Up if self.dir != Dir::Down => self.dir = Dir::Up,
-            Key::A | Key::Left if self.dir != Dir::Right => self.dir = Dir::Left,
-            Key::S | Key::Down if self.dir != Dir::Up => self.dir = Dir::Down,
-            Key::D | Key::Right if self.dir != Dir::Left => self.dir = Dir::Right,
+        match ctx.action {
+            Some(UP) if self.dir != Dir::Down => self.dir = Dir::Up,
+            Some(LEFT) if self.dir != Dir::Right => self.dir = Dir::Left,
+            Some(DOWN) if self.dir != Dir::Up => self.dir = Dir::Down,
+            Some(RIGHT) if self.dir != Dir::Left => self.dir = Dir::Right,
             _ => (),
         }
     }
@@ -1,5 +1,10 @@
 use super::{ALIEN_HEIGHT, ALIEN_WIDTH, ALIEN_XSPEED};
-use graphics::{polygon, Drawable, DrawingContext, Graphics, Updatable, UpdateContext};
+use graphics::{
+    colors::rgba,
+    polygon,
+    sprite::{Sprite, SpriteSheet},
+    Drawable, DrawingContext, Graphics, Updatable, UpdateContext,
+};
 use math::vec2d::Vec2D;
 
 pub struct Alien {
@@ -23,6 +28,24 @@ impl Alien {
             && self.pos.y <= y
             && self.pos.y + ALIEN_HEIGHT >= y
     }
+
+    /// The frame is scaled to the width of the alien and centered in its bounds
+    pub fn draw_sprite(
+        &self,
+        sheet: &SpriteSheet,
+        frame: usize,
+        ctx: &mut DrawingContext,
+        gl: &mut Graphics,
+    ) {
+        if self.dead {
+            return;
+        }
+        let center = self.pos + Vec2D::new(ALIEN_WIDTH / 2.0, ALIEN_HEIGHT / 2.0);
+        let sprite = Sprite::new(center)
+            .with_scale(ALIEN_WIDTH / sheet.frame(frame).w as f64)
+            .with_tint(rgba::GREEN);
+        sheet.draw(frame, &sprite, ctx.id_trans(), ctx, gl);
+    }
 }
 
 impl Drawable for Alien {
@@ -32,7 +55,7 @@ impl Drawable for Alien {
         }
         let transform = ctx.id_trans();
         polygon(
-            [0.0, 1.0, 0.0, 1.0],
+            rgba::GREEN.into(),
             &[
                 [self.pos.x, self.pos.y],
                 [self.pos.x + ALIEN_WIDTH, self.pos.y],
@@ -2,7 +2,7 @@ use super::{
     alien::Alien, ALIEN_HEIGHT, ALIEN_WIDTH, ALIEN_YSPEED, COL_DIST, ROW_DIST, ROW_OFFSET,
 };
 use core::array;
-use graphics::{Drawable, DrawingContext, Graphics, Updatable, UpdateContext};
+use graphics::{sprite::SpriteSheet, Drawable, DrawingContext, Graphics, Updatable, UpdateContext};
 
 pub struct AlienShip<const N: usize, const M: usize> {
     pub alien_rows: [[Alien; M]; N],
@@ -33,6 +33,20 @@ impl<const N: usize, const M: usize> AlienShip<N, M> {
             }
         }
     }
+
+    pub fn draw_sprites(
+        &self,
+        sheet: &SpriteSheet,
+        frame: usize,
+        ctx: &mut DrawingContext,
+        gl: &mut Graphics,
+    ) {
+        for row in self.alien_rows.iter() {
+            for alien in row.iter() {
+                alien.draw_sprite(sheet, frame, ctx, gl);
+            }
+        }
+    }
 }
 
 impl<const N: usize, const M: usize> Drawable for AlienShip<N, M> {
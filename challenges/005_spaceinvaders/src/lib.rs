@@ -1,9 +1,13 @@
 use core::array;
 use graphics::{
-    ellipse, Drawable, DrawingContext, EventHandler, Graphics, InputContext, Runnable, Updatable,
-    UpdateContext, WindowConfig,
+    ellipse,
+    shortcuts::Shortcuts,
+    sprite::{Animation, SpriteSheet},
+    Drawable, DrawingContext, EventHandler, Graphics, InputContext, Runnable, SetupContext,
+    Updatable, UpdateContext, WindowConfig,
 };
 use math::vec2d::Vec2D;
+use piston::Key;
 
 const NUM_ROWS: usize = 5;
 const NUM_COLS: usize = 12;
@@ -21,6 +25,18 @@ const SHIP_WIDTH: f64 = 20.0;
 const SHIP_HEIGHT: f64 = 25.0;
 const SHIP_ACCEL: f64 = 800.0;
 
+/// two frames of the marching aliens and the ship, without it everything is drawn as shapes
+const SPRITE_SHEET: &str = "challenges/005_spaceinvaders/sprites.png";
+const SPRITE_SIZE: [u32; 2] = [13, 8];
+const SPRITE_SPACING: u32 = 1;
+const SHIP_FRAME: usize = 2;
+/// steps of the aliens per second
+const ALIEN_FPS: f64 = 2.0;
+
+const LEFT: &str = "left";
+const RIGHT: &str = "right";
+const SHOOT: &str = "shoot";
+
 mod alien;
 mod alienship;
 mod projectile;
@@ -35,6 +51,8 @@ pub struct SpaceInvaders {
     ship: SpaceShip,
     background_stars: [Vec2D; 3000],
     aliens: AlienShip<NUM_ROWS, NUM_COLS>,
+    sprites: Option<SpriteSheet>,
+    alien_animation: Animation,
 }
 
 impl SpaceInvaders {
@@ -46,6 +64,8 @@ impl SpaceInvaders {
                 y: rand::random::<f64>() * 2.0 * HEIGHT,
             }),
             aliens: AlienShip::new(),
+            sprites: None,
+            alien_animation: Animation::new(vec![0, 1], ALIEN_FPS),
         }
     }
 }
@@ -54,6 +74,7 @@ impl Updatable for SpaceInvaders {
     fn update(&mut self, ctx: &mut UpdateContext) {
         self.ship.update(ctx);
         self.aliens.update(ctx);
+        self.alien_animation.update(ctx);
         let projectiles = &self.ship.projectiles;
 
         if self
@@ -96,8 +117,17 @@ impl Drawable for SpaceInvaders {
             );
         }
 
-        self.ship.draw(ctx, gl);
-        self.aliens.draw(ctx, gl);
+        match &self.sprites {
+            Some(sheet) => {
+                self.ship.draw_sprite(sheet, SHIP_FRAME, ctx, gl);
+                self.aliens
+                    .draw_sprites(sheet, self.alien_animation.frame(), ctx, gl);
+            }
+            None => {
+                self.ship.draw(ctx, gl);
+                self.aliens.draw(ctx, gl);
+            }
+        }
     }
 }
 
@@ -108,6 +138,18 @@ impl EventHandler for SpaceInvaders {
 }
 
 impl Runnable for SpaceInvaders {
+    fn setup(&mut self, ctx: &mut SetupContext) {
+        match SpriteSheet::load_grid(
+            SPRITE_SHEET,
+            SPRITE_SIZE,
+            SPRITE_SPACING,
+            ctx.texture_context,
+        ) {
+            Ok(sheet) => self.sprites = Some(sheet),
+            Err(err) => println!("Could not load sprites, drawing shapes instead: {err}"),
+        }
+    }
+
     fn config(&self) -> WindowConfig {
         WindowConfig {
             width: WIDTH,
@@ -115,4 +157,13 @@ impl Runnable for SpaceInvaders {
             title: "Space Invaders".to_owned(),
         }
     }
+
+    fn shortcuts(&self) -> Shortcuts {
+        Shortcuts::new()
+            .with_action(LEFT, "move left", Key::A)
+            .with_binding(LEFT, Key::Left)
+            .with_action(RIGHT, "move right", Key::D)
+            .with_binding(RIGHT, Key::Right)
+            .with_action(SHOOT, "shoot", Key::Space)
+    }
 }
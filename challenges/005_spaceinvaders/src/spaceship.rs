@@ -1,10 +1,16 @@
 use graphics::{
-    polygon, Drawable, DrawingContext, EventHandler, Graphics, InputContext, Transformed,
-    Updatable, UpdateContext,
+    polygon,
+    sprite::{Sprite, SpriteSheet},
+    Drawable, DrawingContext, EventHandler, Graphics, InputContext, Transformed, Updatable,
+    UpdateContext,
 };
-use piston::{Button, ButtonState, Key};
+use piston::ButtonState;
 
-use super::{projectile::Projectile, SHIP_ACCEL, SHIP_HEIGHT, SHIP_WIDTH};
+use math::vec2d::Vec2D;
+
+use super::{projectile::Projectile, LEFT, RIGHT, SHIP_ACCEL, SHIP_HEIGHT, SHIP_WIDTH, SHOOT};
+
+const SHIP_COLOR: [f32; 4] = [1.0, 0.1, 0.7, 1.0];
 
 pub struct SpaceShip {
     center_dist: f64,
@@ -25,6 +31,32 @@ impl SpaceShip {
         let projectile = Projectile::new(window_width / 2.0 + self.center_dist, window_height);
         self.projectiles.push(projectile)
     }
+
+    fn transform(&self, ctx: &DrawingContext) -> [[f64; 3]; 2] {
+        ctx.context.transform.trans(
+            ctx.args.window_size[0] / 2.0,
+            ctx.args.window_size[1] - SHIP_HEIGHT,
+        )
+    }
+
+    /// The frame is scaled to the width of the ship and stands on the bottom of the window
+    pub fn draw_sprite(
+        &self,
+        sheet: &SpriteSheet,
+        frame: usize,
+        ctx: &mut DrawingContext,
+        gl: &mut Graphics,
+    ) {
+        for projectile in self.projectiles.iter() {
+            projectile.draw(ctx, gl);
+        }
+
+        let sprite = Sprite::new(Vec2D::new(self.center_dist, SHIP_HEIGHT))
+            .with_anchor(Vec2D::new(0.5, 1.0))
+            .with_scale(SHIP_WIDTH / sheet.frame(frame).w as f64)
+            .with_tint(SHIP_COLOR.into());
+        sheet.draw(frame, &sprite, self.transform(ctx), ctx, gl);
+    }
 }
 
 impl Drawable for SpaceShip {
@@ -33,13 +65,9 @@ impl Drawable for SpaceShip {
             projectile.draw(ctx, gl);
         }
 
-        let transform = ctx.context.transform.trans(
-            ctx.args.window_size[0] / 2.0,
-            ctx.args.window_size[1] - SHIP_HEIGHT,
-        );
-
+        let transform = self.transform(ctx);
         polygon(
-            [1.0, 0.1, 0.7, 1.0],
+            SHIP_COLOR,
             &[
                 [self.center_dist - SHIP_WIDTH / 2.0, SHIP_HEIGHT],
                 [self.center_dist, 0.0],
@@ -76,22 +104,14 @@ impl EventHandler for SpaceShip {
     fn handle_input(&mut self, ctx: &InputContext) {
         let state = ctx.args.state;
 
-        let key = if let Button::Keyboard(key) = ctx.args.button {
-            key
-        } else {
-            return;
-        };
-
-        match key {
-            Key::Right | Key::D | Key::Left | Key::A if state == ButtonState::Release => {
-                self.velocity = 0.0
-            }
-            Key::Right | Key::D => self.velocity += SHIP_ACCEL,
-            Key::Left | Key::A => self.velocity -= SHIP_ACCEL,
-            Key::Space if state == ButtonState::Press => {
+        match ctx.action {
+            Some(LEFT | RIGHT) if state == ButtonState::Release => self.velocity = 0.0,
+            Some(RIGHT) => self.velocity += SHIP_ACCEL,
+            Some(LEFT) => self.velocity -= SHIP_ACCEL,
+            Some(SHOOT) if state == ButtonState::Press => {
                 self.shoot(ctx.window_width, ctx.window_height)
             }
-            _ => return,
+            _ => (),
         }
     }
 }
@@ -1,12 +1,9 @@
-use graphics::{
-    ellipse, Color, Drawable, DrawingContext, Graphics, Transformed, Updatable, UpdateContext,
-};
+use graphics::{ellipse, Color, Drawable, DrawingContext, Graphics, Transformed};
 use math::vec2d::Vec2D;
 
 pub struct Body {
     pub center: Vec2D,
-    velocity: Vec2D,
-    pub accel: Vec2D,
+    pub velocity: Vec2D,
     color: Color,
     pub mass: f64,
 }
@@ -18,7 +15,6 @@ impl Body {
             color,
             mass,
             velocity,
-            accel: Vec2D::default(),
         }
     }
 }
@@ -43,19 +39,6 @@ impl Drawable for Body {
             [0.0, 0.0, self.velocity.x, self.velocity.y],
             transform,
             gl,
-        );
-        line(
-            [0.0, 1.0, 0.0, 1.0],
-            2.0,
-            [0.0, 0.0, self.accel.x, self.accel.y],
-            transform,
-            gl,
         );*/
     }
 }
-impl Updatable for Body {
-    fn update(&mut self, ctx: &mut UpdateContext) {
-        self.center += self.velocity * ctx.args.dt;
-        self.velocity += self.accel * ctx.args.dt;
-    }
-}
@@ -2,7 +2,7 @@ use graphics::{
     ellipse, Drawable, DrawingContext, EventHandler, Graphics, InputContext, Runnable,
     SetupContext, Updatable, UpdateContext, WindowConfig,
 };
-use math::{rand_between, vec2d::Vec2D};
+use math::{integrate::Integrator, rand_between, vec2d::Vec2D};
 use piston::{Button, ButtonState, MouseButton, ResizeArgs};
 use std::iter;
 
@@ -15,6 +15,7 @@ const HEIGHT: f64 = 900.0;
 const MAX_R: f64 = 10.0;
 const MIN_R: f64 = 1.0;
 const GRAVITY: f64 = 100.0;
+const INTEGRATOR: Integrator = Integrator::VelocityVerlet;
 
 pub struct SolarSystem {
     sun: Body,
@@ -36,31 +37,38 @@ impl SolarSystem {
         }
     }
 
-    pub fn calculate_gravity(&self) -> Vec<Vec2D> {
+    /// Acceleration of every body when the bodies are at `positions`
+    pub fn calculate_gravity(&self, positions: &[Vec2D]) -> Vec<Vec2D> {
         let mut forces: Vec<Vec2D> = iter::repeat(Vec2D::default())
             .take(self.bodies.len())
             .collect();
 
         for ind1 in 0..self.bodies.len() {
             let fst = &self.bodies[ind1];
-            let abs = fst.center.abs();
+            let fst_pos = positions[ind1];
+            let abs = fst_pos.abs();
             let sun_gravity = GRAVITY * (fst.mass * self.sun.mass) / (abs * abs);
-            forces[ind1] -= fst.center * sun_gravity;
+            forces[ind1] -= fst_pos * sun_gravity;
             for ind2 in (ind1 + 1)..self.bodies.len() {
                 let snd = &self.bodies[ind2];
+                let snd_pos = positions[ind2];
 
-                let dist = fst.center.dist(&snd.center);
+                let dist = fst_pos.dist(&snd_pos);
                 if dist < fst.mass + snd.mass {
                     continue;
                 }
                 let force = (GRAVITY * (fst.mass * snd.mass)) / (dist * dist);
-                let mut direction = fst.center - snd.center;
+                let mut direction = fst_pos - snd_pos;
                 direction.set_abs(force);
                 forces[ind1] -= direction;
                 forces[ind2] += direction;
             }
         }
         forces
+            .into_iter()
+            .zip(self.bodies.iter())
+            .map(|(force, body)| force / body.mass)
+            .collect()
     }
 
     fn generate_background(&mut self, window_width: f64, window_height: f64) {
@@ -95,20 +103,19 @@ impl Drawable for SolarSystem {
 
 impl Updatable for SolarSystem {
     fn update(&mut self, ctx: &mut UpdateContext) {
-        for body in self.bodies.iter_mut() {
-            body.update(ctx);
-        }
-
-        let forces = self.calculate_gravity();
-
-        for (ind, body) in self.bodies.iter_mut().enumerate() {
-            body.accel = forces[ind] / body.mass;
-
-            if body.center.x <= body.mass
-                || body.center.x >= ctx.window_width - body.mass
-                || body.center.y <= body.mass
-                || body.center.y >= ctx.window_height - body.mass
-            {}
+        let mut positions: Vec<Vec2D> = self.bodies.iter().map(|body| body.center).collect();
+        let mut velocities: Vec<Vec2D> = self.bodies.iter().map(|body| body.velocity).collect();
+        INTEGRATOR.step(&mut positions, &mut velocities, ctx.args.dt, |pos| {
+            self.calculate_gravity(pos)
+        });
+
+        for (body, (pos, vel)) in self
+            .bodies
+            .iter_mut()
+            .zip(positions.into_iter().zip(velocities))
+        {
+            body.center = pos;
+            body.velocity = vel;
         }
     }
 }
@@ -1,8 +1,8 @@
 use graphics::{
-    Drawable, DrawingContext, EventHandler, Graphics, InputContext, Runnable, Updatable,
-    UpdateContext, WindowConfig,
+    shortcuts::Shortcuts, Drawable, DrawingContext, EventHandler, Graphics, InputContext, Runnable,
+    Updatable, UpdateContext, WindowConfig,
 };
-use piston::{Button, ButtonState, Key};
+use piston::{ButtonState, Key};
 
 mod hexagon_maze;
 mod maze;
@@ -15,6 +15,9 @@ const HEIGHT: f64 = 900.0;
 const NUM_ROWS: usize = 50;
 const NUM_COLS: usize = 50;
 
+const SWITCH: &str = "switch";
+const SCREENSHOT: &str = "screenshot";
+
 pub struct MazeGenerator {
     hexagon_maze: HexagonMaze<NUM_ROWS, NUM_COLS>,
     square_maze: SquareMaze<NUM_ROWS, NUM_COLS>,
@@ -54,12 +57,10 @@ impl EventHandler for MazeGenerator {
             return;
         }
 
-        if ctx.args.button == Button::Keyboard(Key::Space) {
-            self.show_hexagon = !self.show_hexagon;
-        }
-
-        if ctx.args.button == Button::Keyboard(Key::P) {
-            self.screenshot();
+        match ctx.action {
+            Some(SWITCH) => self.show_hexagon = !self.show_hexagon,
+            Some(SCREENSHOT) => self.screenshot(),
+            _ => (),
         }
     }
 }
@@ -72,4 +73,10 @@ impl Runnable for MazeGenerator {
             title: "Maze Generator".to_owned(),
         }
     }
+
+    fn shortcuts(&self) -> Shortcuts {
+        Shortcuts::new()
+            .with_action(SWITCH, "square or hexagon maze", Key::Space)
+            .with_action(SCREENSHOT, "take a screenshot", Key::P)
+    }
 }
@@ -1,7 +1,5 @@
-use super::{
-    interpolate_color, DIFFUSION_A, DIFFUSION_B, FEED_RATE, KILL_RATE, NUM_COLS, NUM_ROWS,
-};
-use graphics::{rectangle, Drawable, DrawingContext, Graphics, Updatable, UpdateContext};
+use super::{DIFFUSION_A, DIFFUSION_B, FEED_RATE, KILL_RATE, NUM_COLS, NUM_ROWS};
+use graphics::{rectangle, Color, DrawingContext, Graphics, Updatable, UpdateContext};
 
 #[derive(Clone, Copy)]
 pub struct Cell {
@@ -28,14 +26,11 @@ impl Cell {
             laplace_b: 0.0,
         }
     }
-}
 
-impl Drawable for Cell {
-    fn draw(&self, ctx: &mut DrawingContext, gl: &mut Graphics) {
+    pub fn draw(&self, color: Color, ctx: &mut DrawingContext, gl: &mut Graphics) {
         let transform = ctx.id_trans();
         let row_y = ctx.args.window_size[1] / NUM_ROWS as f64;
         let col_x = ctx.args.window_size[0] / NUM_COLS as f64;
-        let color = interpolate_color(self.concentration_a as f32, self.concentration_b as f32);
         rectangle(
             color,
             [
@@ -1,8 +1,14 @@
 use graphics::{
-    Color, Drawable, DrawingContext, EventHandler, Graphics, InputContext, Runnable, SetupContext,
+    colors::{Colormap, Gradient},
+    grid_image::{load_gray, save_colored},
+    shortcuts::Shortcuts,
+    Drawable, DrawingContext, EventHandler, Graphics, InputContext, Runnable, SetupContext,
     Updatable, UpdateContext, WindowConfig,
 };
-use math::grid::Grid;
+use math::{
+    convolution::Kernel,
+    grid::{Boundary, Grid},
+};
 use piston::{Button, ButtonState, Key, MouseButton, ResizeArgs};
 //use std::time::Instant;
 
@@ -16,36 +22,33 @@ const FEED_RATE: f64 = 0.055;
 const KILL_RATE: f64 = 0.062;
 const DIFFUSION_A: f64 = 1.0;
 const DIFFUSION_B: f64 = 0.5;
-const COLOR_B: Color = [1.0, 1.0, 1.0, 1.0];
-const COLOR_A: Color = [0.0, 0.0, 0.0, 1.0];
 
 const LAPLACE_WEIGHTS: [[f64; 3]; 3] = [[0.05, 0.2, 0.05], [0.2, -1.0, 0.2], [0.05, 0.2, 0.05]];
 
 const MOUSE_BRUSH_SIZE: usize = 10;
 const NUM_UPDATES: usize = 10;
 
-const HELP_TEXT: &str = "Controls:
-h: show help text (stdout)
-spacebar: pause/unpause,
-r ; reset
-";
+/// white areas of the mask are seeded with chemical b
+const MASK_FILE: &str = "challenges/013_reactiondiffusion/mask.png";
+const STATE_FILE: &str = "challenges/013_reactiondiffusion/state.png";
+
+const PAUSE: &str = "pause";
+const RESET: &str = "reset";
+const SCREENSHOT: &str = "screenshot";
+const MASK: &str = "mask";
+const SAVE: &str = "save";
+const COLORMAP: &str = "colormap";
 
 mod cell;
 use cell::Cell;
 
-pub fn interpolate_color(concentration_a: f32, concentration_b: f32) -> Color {
-    [
-        COLOR_A[0] * concentration_a + COLOR_B[0] * concentration_b,
-        COLOR_A[1] * concentration_a + COLOR_B[1] * concentration_b,
-        COLOR_A[2] * concentration_a + COLOR_B[2] * concentration_b,
-        1.0,
-    ]
-}
-
 pub struct ReactionDiffusion {
     running: bool,
     drawing: bool,
     cells: Grid<Cell>,
+    laplace: Kernel,
+    colormap: Colormap,
+    gradient: Gradient,
 }
 
 impl ReactionDiffusion {
@@ -54,6 +57,9 @@ impl ReactionDiffusion {
             drawing: false,
             running: false,
             cells: Grid::from_fn(|x, y| Cell::new(x as u64, y as u64), NUM_COLS, NUM_ROWS),
+            laplace: Kernel::from_rows(LAPLACE_WEIGHTS),
+            colormap: Colormap::default(),
+            gradient: Colormap::default().gradient(),
         }
     }
 
@@ -83,12 +89,37 @@ impl ReactionDiffusion {
             }
         }
     }
+
+    fn load_mask(&mut self) {
+        let mask = match load_gray(MASK_FILE) {
+            Ok(mask) => mask.resample_bilinear(NUM_COLS, NUM_ROWS),
+            Err(err) => {
+                println!("Could not load {MASK_FILE}: {err}");
+                return;
+            }
+        };
+        for (cell, val) in self.cells.iter_mut().zip(mask.iter()) {
+            if *val > 0.5 {
+                cell.concentration_a = 0.0;
+                cell.concentration_b = 1.0;
+            }
+        }
+    }
+
+    fn save_state(&self) {
+        let concentration_b = self.cells.map_ref(|cell| cell.concentration_b);
+        match save_colored(&concentration_b, STATE_FILE, &self.gradient) {
+            Ok(()) => println!("Saved {STATE_FILE}"),
+            Err(err) => println!("Could not save {STATE_FILE}: {err}"),
+        }
+    }
 }
 
 impl Drawable for ReactionDiffusion {
     fn draw(&self, ctx: &mut DrawingContext, gl: &mut Graphics) {
         for cell in self.cells.iter() {
-            cell.draw(ctx, gl);
+            let color = self.gradient.map(cell.concentration_b);
+            cell.draw(color.into(), ctx, gl);
         }
     }
 }
@@ -105,14 +136,12 @@ impl Updatable for ReactionDiffusion {
         for _ in 0..NUM_UPDATES {
             let laplace_a = self
                 .cells
-                .clone()
-                .map(|cell| cell.concentration_a)
-                .convolute(LAPLACE_WEIGHTS);
+                .map_ref(|cell| cell.concentration_a)
+                .convolve(&self.laplace, Boundary::Wrap);
             let laplace_b = self
                 .cells
-                .clone()
-                .map(|cell| cell.concentration_b)
-                .convolute(LAPLACE_WEIGHTS);
+                .map_ref(|cell| cell.concentration_b)
+                .convolve(&self.laplace, Boundary::Wrap);
 
             for y in 0..NUM_ROWS {
                 for x in 0..NUM_COLS {
@@ -136,21 +165,25 @@ impl EventHandler for ReactionDiffusion {
             return;
         }
 
-        if let Button::Keyboard(key) = ctx.args.button {
-            match key {
-                Key::Space => self.running = !self.running,
-                Key::H => println!("{}", HELP_TEXT),
-                Key::R => {
-                    self.drawing = false;
-                    self.running = false;
-                    for cell in self.cells.iter_mut() {
-                        cell.concentration_a = 1.0;
-                        cell.concentration_b = 0.0;
-                    }
+        match ctx.action {
+            Some(PAUSE) => self.running = !self.running,
+            Some(RESET) => {
+                self.drawing = false;
+                self.running = false;
+                for cell in self.cells.iter_mut() {
+                    cell.concentration_a = 1.0;
+                    cell.concentration_b = 0.0;
                 }
-                Key::P => self.screenshot(),
-                _ => return,
             }
+            Some(SCREENSHOT) => self.screenshot(),
+            Some(MASK) => self.load_mask(),
+            Some(SAVE) => self.save_state(),
+            Some(COLORMAP) => {
+                self.colormap = self.colormap.next();
+                self.gradient = self.colormap.gradient();
+                println!("Colormap: {:?}", self.colormap);
+            }
+            _ => (),
         }
     }
     fn handle_resize(&mut self, args: &ResizeArgs) {
@@ -170,4 +203,14 @@ impl Runnable for ReactionDiffusion {
     fn setup(&mut self, ctx: &mut SetupContext) {
         self.resize_cells(ctx.window_width, ctx.window_height);
     }
+
+    fn shortcuts(&self) -> Shortcuts {
+        Shortcuts::new()
+            .with_action(PAUSE, "pause or unpause", Key::Space)
+            .with_action(RESET, "clear the chemicals", Key::R)
+            .with_action(SCREENSHOT, "take a screenshot", Key::P)
+            .with_action(MASK, "seed chemical b from mask.png", Key::M)
+            .with_action(SAVE, "save the concentration of b to state.png", Key::S)
+            .with_action(COLORMAP, "next colormap", Key::C)
+    }
 }
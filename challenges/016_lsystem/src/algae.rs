@@ -2,7 +2,7 @@ use super::{
     l_system::{LSystem, Symbol},
     turtle::{TurtleCommand, TurtleInstructor, TurtleState},
 };
-use graphics::{DrawingContext, Transformed};
+use graphics::DrawingContext;
 use std::{collections::HashMap, f64::consts::PI, hash::Hash};
 
 #[derive(PartialEq, Eq, Hash, Clone)]
@@ -28,7 +28,7 @@ impl Symbol for Algae {}
 impl TurtleInstructor for Algae {
     fn start(&self, ctx: &mut DrawingContext, _iter: u64) -> TurtleState {
         TurtleState::new(
-            ctx.id_trans().trans(
+            ctx.id_affine().trans(
                 2.0 * ctx.args.window_size[0] / 3.0,
                 ctx.args.window_size[1] / 2.0,
             ),
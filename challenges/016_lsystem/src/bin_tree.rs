@@ -2,7 +2,7 @@ use super::{
     l_system::{LSystem, Symbol},
     turtle::{TurtleCommand, TurtleInstructor, TurtleState},
 };
-use graphics::{DrawingContext, Transformed};
+use graphics::DrawingContext;
 use std::collections::HashMap;
 use std::f64::consts::PI;
 
@@ -40,7 +40,7 @@ impl BinTree {
 impl TurtleInstructor for BinTree {
     fn start(&self, ctx: &mut DrawingContext, iter: u64) -> TurtleState {
         let mut st = TurtleState::new(
-            ctx.id_trans()
+            ctx.id_affine()
                 .trans(ctx.args.window_size[0] / 2.0, ctx.args.window_size[1])
                 .rot_rad(-PI),
             [1.0, 1.0, 1.0, 1.0],
@@ -2,7 +2,7 @@ use super::{
     l_system::{LSystem, Symbol},
     turtle::{TurtleCommand, TurtleInstructor, TurtleState},
 };
-use graphics::{DrawingContext, Transformed};
+use graphics::DrawingContext;
 use std::{collections::HashMap, hash::Hash};
 
 #[derive(PartialEq, Eq, Hash, Clone)]
@@ -28,7 +28,7 @@ impl Cantor {
 impl TurtleInstructor for Cantor {
     fn start(&self, ctx: &mut DrawingContext, iter: u64) -> TurtleState {
         let mut st = TurtleState::new(
-            ctx.id_trans().trans(ctx.args.window_size[0] / 2.0, 0.0),
+            ctx.id_affine().trans(ctx.args.window_size[0] / 2.0, 0.0),
             [1.0, 1.0, 1.0, 1.0],
             2.0,
         );
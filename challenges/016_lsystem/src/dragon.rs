@@ -29,7 +29,7 @@ impl Dragon {
 
 impl TurtleInstructor for Dragon {
     fn start(&self, ctx: &mut DrawingContext, iter: u64) -> TurtleState {
-        let mut st = TurtleState::new(ctx.center_trans(), [1.0, 1.0, 1.0, 1.0], 2.0);
+        let mut st = TurtleState::new(ctx.center_affine(), [1.0, 1.0, 1.0, 1.0], 2.0);
         st.len = ctx.args.window_size[0].min(ctx.args.window_size[1]) / (1.5_f64).powi(iter as i32);
         st
     }
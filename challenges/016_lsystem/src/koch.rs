@@ -2,7 +2,7 @@ use super::{
     l_system::{LSystem, Symbol},
     turtle::{TurtleCommand, TurtleInstructor, TurtleState},
 };
-use graphics::{DrawingContext, Transformed};
+use graphics::DrawingContext;
 use std::{collections::HashMap, f64::consts::PI, hash::Hash};
 
 #[derive(PartialEq, Eq, Hash, Clone)]
@@ -39,7 +39,7 @@ impl Koch {
 impl TurtleInstructor for Koch {
     fn start(&self, ctx: &mut DrawingContext, iter: u64) -> TurtleState {
         let mut st = TurtleState::new(
-            ctx.id_trans().trans(ctx.args.window_size[0] / 2.0, 0.0),
+            ctx.id_affine().trans(ctx.args.window_size[0] / 2.0, 0.0),
             [1.0, 1.0, 1.0, 1.0],
             2.0,
         );
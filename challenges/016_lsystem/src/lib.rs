@@ -1,8 +1,8 @@
 use graphics::{
-    Drawable, DrawingContext, EventHandler, Graphics, InputContext, Runnable, Updatable,
-    UpdateContext, WindowConfig,
+    shortcuts::Shortcuts, Drawable, DrawingContext, EventHandler, Graphics, InputContext, Runnable,
+    Updatable, UpdateContext, WindowConfig,
 };
-use piston::{Button, ButtonState, Key};
+use piston::{ButtonState, Key};
 
 mod algae;
 mod bin_tree;
@@ -19,6 +19,11 @@ mod turtle;
 use systems::System;
 use turtle::Turtle;
 
+const STEP: &str = "step";
+const GROW: &str = "grow";
+const SHRINK: &str = "shrink";
+const NEXT: &str = "next";
+
 const WIDTH: f64 = 800.0;
 const HEIGHT: f64 = 900.0;
 
@@ -64,27 +69,19 @@ impl EventHandler for SystemRunner {
         if ctx.args.state != ButtonState::Release {
             return;
         }
-        if ctx.args.button == Button::Keyboard(Key::Space) {
-            self.paused = false
-        }
-
-        if ctx.args.button == Button::Keyboard(Key::Plus)
-            || ctx.args.button == Button::Keyboard(Key::Equals)
-        {
-            self.turtle.global_scale += 0.1;
-        }
-
-        if ctx.args.button == Button::Keyboard(Key::Minus) {
-            self.turtle.global_scale -= 0.1;
-        }
-
-        if ctx.args.button == Button::Keyboard(Key::N) {
-            self.current_system += 1;
-            self.current_system = self.current_system % self.systems.len();
-            self.systems[self.current_system].reset();
-            self.turtle.iteration = 0;
-            self.turtle.commands = self.systems[self.current_system].commands();
-            println!("current system {}", self.systems[self.current_system]);
+        match ctx.action {
+            Some(STEP) => self.paused = false,
+            Some(GROW) => self.turtle.global_scale += 0.1,
+            Some(SHRINK) => self.turtle.global_scale -= 0.1,
+            Some(NEXT) => {
+                self.current_system += 1;
+                self.current_system = self.current_system % self.systems.len();
+                self.systems[self.current_system].reset();
+                self.turtle.iteration = 0;
+                self.turtle.commands = self.systems[self.current_system].commands();
+                println!("current system {}", self.systems[self.current_system]);
+            }
+            _ => (),
         }
     }
 }
@@ -97,4 +94,13 @@ impl Runnable for SystemRunner {
             title: "L-System".to_owned(),
         }
     }
+
+    fn shortcuts(&self) -> Shortcuts {
+        Shortcuts::new()
+            .with_action(STEP, "next iteration", Key::Space)
+            .with_action(GROW, "scale up", Key::Plus)
+            .with_binding(GROW, Key::Equals)
+            .with_action(SHRINK, "scale down", Key::Minus)
+            .with_action(NEXT, "next system", Key::N)
+    }
 }
@@ -2,7 +2,7 @@ use super::{
     l_system::{LSystem, Symbol},
     turtle::{TurtleCommand, TurtleInstructor, TurtleState},
 };
-use graphics::{DrawingContext, Transformed};
+use graphics::DrawingContext;
 use std::{collections::HashMap, f64::consts::PI, hash::Hash};
 
 #[derive(PartialEq, Eq, Hash, Clone)]
@@ -54,7 +54,7 @@ impl Plant {
 impl TurtleInstructor for Plant {
     fn start(&self, ctx: &mut DrawingContext, iter: u64) -> TurtleState {
         let mut st = TurtleState::new(
-            ctx.id_trans()
+            ctx.id_affine()
                 .trans(ctx.args.window_size[0] / 2.0, ctx.args.window_size[1])
                 .rot_rad(PI),
             [1.0, 1.0, 1.0, 1.0],
@@ -1,4 +1,6 @@
-use graphics::{line, Color, Drawable, DrawingContext, Graphics, Transformed};
+use graphics::{line, Color, Drawable, DrawingContext, Graphics};
+use math::affine2::Affine2;
+
 pub struct Turtle {
     pub commands: Vec<Box<dyn TurtleInstructor>>,
     pub global_scale: f64,
@@ -17,7 +19,7 @@ impl Turtle {
 
 #[derive(Clone)]
 pub struct TurtleState {
-    pub transform: [[f64; 3]; 2],
+    pub transform: Affine2,
     pub color: Color,
     pub radius: f64,
     pub state_stack: Vec<TurtleState>,
@@ -25,7 +27,7 @@ pub struct TurtleState {
 }
 
 impl TurtleState {
-    pub fn new(transform: [[f64; 3]; 2], color: Color, radius: f64) -> TurtleState {
+    pub fn new(transform: Affine2, color: Color, radius: f64) -> TurtleState {
         TurtleState {
             transform,
             color,
@@ -57,7 +59,7 @@ impl TurtleCommand {
                     st.color,
                     st.radius,
                     [0.0, 0.0, 0.0, st.len],
-                    st.transform,
+                    st.transform.into(),
                     gl,
                 );
                 st.transform = st.transform.trans(0.0, st.len);
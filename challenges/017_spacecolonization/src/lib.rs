@@ -1,9 +1,13 @@
 use graphics::{
-    ellipse, line, Color, Drawable, DrawingContext, EventHandler, Graphics, InputContext, Runnable,
-    SetupContext, Updatable, UpdateContext, WindowConfig,
+    ellipse, line, shortcuts::Shortcuts, Color, Drawable, DrawingContext, EventHandler, Graphics,
+    InputContext, Runnable, SetupContext, Updatable, UpdateContext, WindowConfig,
 };
-use math::{rand_between, vec2d::Vec2D};
-use piston::{Button, ButtonState, Key, ResizeArgs};
+use math::{
+    rand_between,
+    spatial::{KdTree, SpatialIndex},
+    vec2d::Vec2D,
+};
+use piston::{ButtonState, Key, ResizeArgs};
 
 const WIDTH: f64 = 800.0;
 const HEIGHT: f64 = 900.0;
@@ -22,6 +26,8 @@ const MAX_LEAVES: usize = 20;
 const LEAF_SIZE: f64 = 5.0;
 const LEAF_RATE: f64 = 0.5;
 
+const RESET: &str = "reset";
+
 struct TreeNode {
     pos: Vec2D,
     next: Vec<Vec2D>,
@@ -66,6 +72,7 @@ impl TreeNode {
 pub struct SpaceColonization {
     attraction_points: Vec<Vec2D>,
     nodes: Vec<TreeNode>,
+    node_index: KdTree<usize>,
 }
 
 impl SpaceColonization {
@@ -73,18 +80,28 @@ impl SpaceColonization {
         SpaceColonization {
             attraction_points: Vec::with_capacity(NUM_POINTS),
             nodes: vec![],
+            node_index: KdTree::new(),
         }
     }
 
+    fn push_node(&mut self, node: TreeNode) {
+        self.node_index.insert(node.pos, self.nodes.len());
+        self.nodes.push(node);
+    }
+
+    fn reset(&mut self, window_width: f64, window_height: f64) {
+        self.nodes.clear();
+        self.node_index.clear();
+        self.generate_attraction(window_width, window_height);
+        self.push_node(TreeNode::new(window_width / 2.0, window_height - 2.0))
+    }
+
     fn closest_node(&self, pt: &Vec2D) -> Option<usize> {
-        self.nodes
-            .iter()
-            .enumerate()
-            .min_by(|(_, node1), (_, node2)| {
-                node1.pos.dist(pt).partial_cmp(&node2.pos.dist(pt)).unwrap()
-            })
-            .filter(|(_, node)| node.pos.dist(pt) < INF_RADIUS)
-            .map(|(ind, _)| ind)
+        self.node_index
+            .nearest(*pt, 1)
+            .first()
+            .filter(|(pos, _)| pos.dist(pt) < INF_RADIUS)
+            .map(|(_, ind)| **ind)
     }
 
     fn generate_attraction(&mut self, window_width: f64, window_height: f64) {
@@ -181,7 +198,6 @@ impl Updatable for SpaceColonization {
         }
 
         let mut new_nodes = vec![];
-        let existing_pos: Vec<Vec2D> = self.nodes.iter().map(|node| node.pos).collect();
 
         for (ind, node) in self.nodes.iter_mut().enumerate() {
             if node.finished {
@@ -198,7 +214,7 @@ impl Updatable for SpaceColonization {
             }
             let n = node.avg_distance(&closest);
             let new_pos = node.pos + DIST_NEXT * n;
-            if existing_pos.contains(&new_pos) {
+            if !self.node_index.within(new_pos, 0.0).is_empty() {
                 continue;
             }
             let new_node = TreeNode::new(new_pos.x, new_pos.y);
@@ -213,7 +229,9 @@ impl Updatable for SpaceColonization {
             new_nodes.push(new_node);
         }
 
-        self.nodes.extend(new_nodes);
+        for new_node in new_nodes {
+            self.push_node(new_node);
+        }
         to_remove.sort();
         to_remove.reverse();
         for ind in to_remove {
@@ -228,14 +246,8 @@ impl EventHandler for SpaceColonization {
     }
 
     fn handle_input(&mut self, ctx: &InputContext) {
-        if ctx.args.state == ButtonState::Release && ctx.args.button == Button::Keyboard(Key::Space)
-        {
-            self.nodes.clear();
-            self.generate_attraction(ctx.window_width, ctx.window_height);
-            self.nodes.push(TreeNode::new(
-                ctx.window_width / 2.0,
-                ctx.window_height - 2.0,
-            ))
+        if ctx.args.state == ButtonState::Release && ctx.action == Some(RESET) {
+            self.reset(ctx.window_width, ctx.window_height);
         }
     }
 }
@@ -250,10 +262,10 @@ impl Runnable for SpaceColonization {
     }
 
     fn setup(&mut self, ctx: &mut SetupContext) {
-        self.generate_attraction(ctx.window_width, ctx.window_height);
-        self.nodes.push(TreeNode::new(
-            ctx.window_width / 2.0,
-            ctx.window_height - 2.0,
-        ))
+        self.reset(ctx.window_width, ctx.window_height);
+    }
+
+    fn shortcuts(&self) -> Shortcuts {
+        Shortcuts::new().with_action(RESET, "grow a new tree", Key::Space)
     }
 }
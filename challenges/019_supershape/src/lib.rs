@@ -1,9 +1,10 @@
 use graphics::{
-    rectangle, ui_elements::Button, Color, Drawable, DrawingContext, EventHandler, Graphics,
-    InputContext, Runnable, SetupContext, Updatable, UpdateContext, WindowConfig,
+    rectangle, shortcuts::Shortcuts, ui_elements::Button, Color, Drawable, DrawingContext,
+    EventHandler, Graphics, InputContext, Runnable, SetupContext, Updatable, UpdateContext,
+    WindowConfig,
 };
 use math::vec2d::Vec2D;
-use piston::{Button as PisButton, ButtonState, Key, ResizeArgs};
+use piston::{ButtonState, Key, ResizeArgs};
 use std::fmt;
 
 const WIDTH: f64 = 800.0;
@@ -21,8 +22,10 @@ const BUTTON_DIST: f64 = 5.0;
 const BUTTON_HEIGHT: f64 = 30.0;
 const VAL_CHANGE: f64 = 0.1;
 
+const UNPAUSE: &str = "unpause";
+
 #[derive(Clone, Copy)]
-enum ConstLabel {
+pub enum ConstLabel {
     M,
     N1,
     N2,
@@ -49,7 +52,7 @@ impl ConstLabel {
         }
     }
 
-    fn next(self) -> Option<ConstLabel> {
+    pub fn next(self) -> Option<ConstLabel> {
         match self {
             ConstLabel::M => Some(ConstLabel::N1),
             ConstLabel::N1 => Some(ConstLabel::N2),
@@ -60,7 +63,7 @@ impl ConstLabel {
         }
     }
 
-    fn default_value(&self) -> f64 {
+    pub fn default_value(&self) -> f64 {
         match self {
             ConstLabel::M => 1.0,
             ConstLabel::N1 => 0.3,
@@ -92,8 +95,8 @@ impl fmt::Display for ConstLabel {
     }
 }
 
-struct ShapeConstant {
-    value: f64,
+pub struct ShapeConstant {
+    pub value: f64,
     inc_button: Button,
     dec_button: Button,
 }
@@ -131,6 +134,14 @@ impl ShapeConstant {
     }
 }
 
+/// Superformula radius at `angle` for constants ordered as m, n1, n2, n3, a, b
+pub fn radius(constants: &[ShapeConstant], angle: f64) -> f64 {
+    let arg_cos = (angle * constants[0].value / 4.0).cos() / constants[4].value;
+    let arg_sin = (angle * constants[0].value / 4.0).sin() / constants[5].value;
+    let sum = arg_cos.abs().powf(constants[2].value) + arg_sin.abs().powf(constants[3].value);
+    1.0 / sum.powf(1.0 / constants[1].value)
+}
+
 impl Drawable for ShapeConstant {
     fn draw(&self, ctx: &mut DrawingContext, gl: &mut Graphics) {
         self.inc_button.draw(ctx, gl);
@@ -178,13 +189,8 @@ impl SuperShape {
     }
 
     fn inside(&self, pt: Vec2D) -> bool {
-        let arg = pt.arg();
         let r = pt.abs();
-        let arg_cos = (arg * self.constants[0].value / 4.0).cos() / self.constants[4].value;
-        let arg_sin = (arg * self.constants[0].value / 4.0).sin() / self.constants[5].value;
-        let sum = arg_cos.abs().powf(self.constants[2].value)
-            + arg_sin.abs().powf(self.constants[3].value);
-        let res = 1.0 / sum.powf(1.0 / self.constants[1].value);
+        let res = radius(&self.constants, pt.arg());
         res >= r - TOLERANCE && res <= r + TOLERANCE
     }
 
@@ -243,9 +249,7 @@ impl EventHandler for SuperShape {
     }
 
     fn handle_input(&mut self, ctx: &InputContext) {
-        if ctx.args.button == PisButton::Keyboard(Key::Space)
-            && ctx.args.state == ButtonState::Release
-        {
+        if ctx.args.state == ButtonState::Release && ctx.action == Some(UNPAUSE) {
             self.paused = false;
         }
 
@@ -267,4 +271,8 @@ impl Runnable for SuperShape {
     fn setup(&mut self, ctx: &mut SetupContext) {
         self.compute(ctx.window_height, ctx.window_width);
     }
+
+    fn shortcuts(&self) -> Shortcuts {
+        Shortcuts::new().with_action(UNPAUSE, "continue the animation", Key::Space)
+    }
 }
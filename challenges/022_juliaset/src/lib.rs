@@ -1,11 +1,12 @@
-use ::image::{Rgba, RgbaImage};
 use graphics::{
-    image, Drawable, DrawingContext, EventHandler, Graphics, InputContext, Runnable, SetupContext,
+    canvas::Canvas,
+    colors::{Colormap, Gradient},
+    shortcuts::Shortcuts,
+    Drawable, DrawingContext, EventHandler, Graphics, InputContext, Runnable, SetupContext,
     Updatable, UpdateContext, WindowConfig,
 };
-use math::vec2d::Vec2D;
-use piston::{Button, ButtonState, Key, ResizeArgs};
-use piston_window::{G2dTexture, TextureSettings};
+use math::complex::Complex;
+use piston::{ButtonState, Key, ResizeArgs};
 
 const WIDTH: f64 = 800.0;
 const HEIGHT: f64 = 900.0;
@@ -22,13 +23,23 @@ const MOVE_X: f64 = 0.1;
 const MOVE_Y: f64 = 0.1;
 const ZOOM_FAC: f64 = 0.5;
 
+const LEFT: &str = "left";
+const RIGHT: &str = "right";
+const UP: &str = "up";
+const DOWN: &str = "down";
+const ZOOM_IN: &str = "zoom_in";
+const ZOOM_OUT: &str = "zoom_out";
+const COLORMAP: &str = "colormap";
+
 pub struct JuliaSet {
     min_x: f64,
     max_x: f64,
     min_y: f64,
     max_y: f64,
-    c: Vec2D,
-    image: RgbaImage,
+    c: Complex,
+    canvas: Canvas,
+    colormap: Colormap,
+    gradient: Gradient,
 }
 
 impl JuliaSet {
@@ -38,20 +49,18 @@ impl JuliaSet {
             max_x: MAX_X,
             min_y: MIN_Y,
             max_y: MAX_Y,
-            c: Vec2D::new(0.285, 0.0001),
-            image: RgbaImage::new(WIDTH as u32 + 1, HEIGHT as u32 + 1),
+            c: Complex::new(0.285, 0.0001),
+            canvas: Canvas::new(WIDTH as u32 + 1, HEIGHT as u32 + 1),
+            colormap: Colormap::default(),
+            gradient: Colormap::default().gradient(),
         }
     }
-    fn inside(&self, pt: Vec2D) -> u8 {
-        let mut next = pt;
+    fn inside(&self, pt: Complex) -> u8 {
+        let mut z = pt;
         for i in 0..MAX_ITER {
-            next = Vec2D {
-                x: next.x * next.x - next.y * next.y,
-                y: 2.0 * next.x * next.y,
-            };
-            next += self.c;
+            z = z * z + self.c;
 
-            if next.abs() > MAX_RADIUS {
+            if z.norm_sqr() > MAX_RADIUS * MAX_RADIUS {
                 return i as u8;
             }
         }
@@ -62,12 +71,13 @@ impl JuliaSet {
         let step_x = (self.max_x - self.min_x) / window_width;
         let step_y = (self.max_y - self.min_y) / window_height;
 
-        for i in 0..self.image.width() as u32 {
-            for j in 0..self.image.height() as u32 {
+        for i in 0..self.canvas.width() {
+            for j in 0..self.canvas.height() {
                 let x = self.min_x + i as f64 * step_x;
                 let y = self.min_y + j as f64 * step_y;
-                let inside = self.inside(Vec2D { x, y });
-                self.image.put_pixel(i, j, Rgba([255, 255, 255, inside]));
+                let inside = self.inside(Complex::new(x, y));
+                let color = self.gradient.map(inside as f64 / MAX_ITER as f64);
+                self.canvas.set(i as i64, j as i64, color);
             }
         }
     }
@@ -76,13 +86,7 @@ impl JuliaSet {
 impl Drawable for JuliaSet {
     fn draw(&self, ctx: &mut DrawingContext, gl: &mut Graphics) {
         let transform = ctx.id_trans();
-        let texture = G2dTexture::from_image(
-            &mut ctx.texture_context,
-            &self.image,
-            &TextureSettings::new(),
-        )
-        .unwrap();
-        image(&texture, transform, gl);
+        self.canvas.draw(transform, ctx, gl);
     }
 }
 
@@ -92,7 +96,7 @@ impl Updatable for JuliaSet {
 
 impl EventHandler for JuliaSet {
     fn handle_resize(&mut self, ctx: &ResizeArgs) {
-        self.image = RgbaImage::new(
+        self.canvas.resize(
             ctx.window_size[0].ceil() as u32 + 1,
             ctx.window_size[1].ceil() as u32 + 1,
         );
@@ -104,30 +108,24 @@ impl EventHandler for JuliaSet {
             return;
         }
 
-        let key = if let Button::Keyboard(key) = ctx.args.button {
-            key
-        } else {
-            return;
-        };
-
-        match key {
-            Key::Left => {
+        match ctx.action {
+            Some(LEFT) => {
                 self.min_x -= MOVE_X;
                 self.max_x -= MOVE_X;
             }
-            Key::Right => {
+            Some(RIGHT) => {
                 self.min_x += MOVE_X;
                 self.max_x += MOVE_X;
             }
-            Key::Up => {
+            Some(UP) => {
                 self.min_y += MOVE_Y;
                 self.max_y += MOVE_Y;
             }
-            Key::Down => {
+            Some(DOWN) => {
                 self.min_y -= MOVE_Y;
                 self.max_y -= MOVE_Y;
             }
-            Key::Z => {
+            Some(ZOOM_IN) => {
                 if self.min_y < 0.0 {
                     self.min_y += ZOOM_FAC
                 } else {
@@ -152,7 +150,7 @@ impl EventHandler for JuliaSet {
                     self.max_x -= ZOOM_FAC
                 }
             }
-            Key::Y => {
+            Some(ZOOM_OUT) => {
                 if self.min_y < 0.0 {
                     self.min_y -= ZOOM_FAC
                 } else {
@@ -177,6 +175,11 @@ impl EventHandler for JuliaSet {
                     self.max_x += ZOOM_FAC
                 }
             }
+            Some(COLORMAP) => {
+                self.colormap = self.colormap.next();
+                self.gradient = self.colormap.gradient();
+                println!("Colormap: {:?}", self.colormap);
+            }
             _ => return,
         }
         self.compute(ctx.window_width, ctx.window_height);
@@ -188,6 +191,17 @@ impl Runnable for JuliaSet {
         self.compute(ctx.window_width, ctx.window_height);
     }
 
+    fn shortcuts(&self) -> Shortcuts {
+        Shortcuts::new()
+            .with_action(LEFT, "move left", Key::Left)
+            .with_action(RIGHT, "move right", Key::Right)
+            .with_action(UP, "move up", Key::Up)
+            .with_action(DOWN, "move down", Key::Down)
+            .with_action(ZOOM_IN, "zoom in", Key::Z)
+            .with_action(ZOOM_OUT, "zoom out", Key::Y)
+            .with_action(COLORMAP, "next colormap", Key::C)
+    }
+
     fn config(&self) -> WindowConfig {
         WindowConfig {
             width: WIDTH,
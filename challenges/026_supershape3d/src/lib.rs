@@ -0,0 +1,223 @@
+use graphics::{
+    polygon, shortcuts::Shortcuts, Color, Drawable, DrawingContext, EventHandler, Graphics,
+    InputContext, Runnable, Updatable, UpdateContext, WindowConfig,
+};
+use math::{mat33::Mat33, vec3d::Vec3D};
+use piston::{ButtonState, Key};
+use supershape::{radius, ConstLabel, ShapeConstant};
+
+const WIDTH: f64 = 800.0;
+const HEIGHT: f64 = 900.0;
+
+const RADIUS: f64 = 200.0;
+const DETAIL: usize = 50;
+const MIN_DETAIL: usize = 4;
+const MAX_DETAIL: usize = 150;
+const DETAIL_STEP: usize = 2;
+
+const CAMERA_DIST: f64 = 700.0;
+const FOCAL_LEN: f64 = 700.0;
+const NEAR: f64 = 1.0;
+const ROT_SPEED: f64 = 0.5;
+
+const SHAPE_COLOR: Color = [0.2, 0.6, 1.0, 1.0];
+const AMBIENT: f32 = 0.15;
+
+const PAUSE: &str = "pause";
+const FINER: &str = "finer";
+const COARSER: &str = "coarser";
+
+mod mesh;
+use mesh::Mesh;
+
+fn default_value(label: ConstLabel) -> f64 {
+    match label {
+        ConstLabel::M => 7.0,
+        ConstLabel::N1 => 0.2,
+        ConstLabel::N2 => 1.7,
+        ConstLabel::N3 => 1.7,
+        ConstLabel::A | ConstLabel::B => 1.0,
+    }
+}
+
+pub struct SuperShape3D {
+    constants: Vec<ShapeConstant>,
+    mesh: Mesh,
+    detail: usize,
+    angle_x: f64,
+    angle_y: f64,
+    paused: bool,
+}
+
+impl SuperShape3D {
+    pub fn new() -> SuperShape3D {
+        let mut new = SuperShape3D::sphere();
+        new.create_constants();
+        new.remesh();
+        new
+    }
+
+    pub fn sphere() -> SuperShape3D {
+        SuperShape3D {
+            constants: vec![],
+            mesh: Mesh::sphere(RADIUS, DETAIL),
+            detail: DETAIL,
+            angle_x: 0.0,
+            angle_y: 0.0,
+            paused: false,
+        }
+    }
+
+    fn create_constants(&mut self) {
+        self.constants.clear();
+        let mut next_label = Some(ConstLabel::default());
+        while let Some(label) = next_label {
+            self.constants
+                .push(ShapeConstant::new(label, default_value(label)));
+            next_label = label.next();
+        }
+    }
+
+    fn values(&self) -> Vec<f64> {
+        self.constants.iter().map(|cons| cons.value).collect()
+    }
+
+    fn remesh(&mut self) {
+        if self.constants.is_empty() {
+            self.mesh = Mesh::sphere(RADIUS, self.detail);
+            return;
+        }
+        let r = |angle| radius(&self.constants, angle);
+        self.mesh = Mesh::spherical_product(RADIUS, self.detail, r, r);
+    }
+
+    fn project(pt: Vec3D) -> [f64; 2] {
+        [FOCAL_LEN * pt.x / pt.z, -FOCAL_LEN * pt.y / pt.z]
+    }
+}
+
+impl Drawable for SuperShape3D {
+    fn draw(&self, ctx: &mut DrawingContext, gl: &mut Graphics) {
+        let transform = ctx.center_trans();
+        let camera_offset = Vec3D::new(0.0, 0.0, CAMERA_DIST);
+        let light_dir = Vec3D::new(-1.0, 1.0, -1.0).normalize();
+        let rotation = Mat33::rotation_x(self.angle_x) * Mat33::rotation_y(self.angle_y);
+
+        let view: Vec<Vec3D> = self
+            .mesh
+            .vertices
+            .iter()
+            .map(|v| rotation * *v + camera_offset)
+            .collect();
+
+        let mut visible = vec![];
+        for face in self.mesh.faces.iter() {
+            let [a, b, c] = face.map(|ind| view[ind]);
+            if a.z < NEAR || b.z < NEAR || c.z < NEAR {
+                continue;
+            }
+
+            let normal = (b - a).cross(c - a);
+            let len = normal.abs();
+            // faces collapsed at the poles have no normal
+            if len == 0.0 || !len.is_finite() {
+                continue;
+            }
+
+            // the camera sits at the origin, so faces pointing away from it are hidden
+            let centroid = (a + b + c) / 3.0;
+            if normal.dot(centroid) >= 0.0 {
+                continue;
+            }
+
+            let light = (normal / len).dot(light_dir).max(0.0) as f32;
+            let shade = AMBIENT + (1.0 - AMBIENT) * light;
+            visible.push((
+                centroid.z,
+                shade,
+                [
+                    SuperShape3D::project(a),
+                    SuperShape3D::project(b),
+                    SuperShape3D::project(c),
+                ],
+            ));
+        }
+
+        // painter's algorithm, draw the faces furthest away first
+        visible.sort_by(|(z1, _, _), (z2, _, _)| z2.partial_cmp(z1).unwrap());
+        for (_, shade, pts) in visible {
+            let color = [
+                SHAPE_COLOR[0] * shade,
+                SHAPE_COLOR[1] * shade,
+                SHAPE_COLOR[2] * shade,
+                SHAPE_COLOR[3],
+            ];
+            polygon(color, &pts, transform, gl);
+        }
+
+        for cons in self.constants.iter() {
+            cons.draw(ctx, gl);
+        }
+    }
+}
+
+impl Updatable for SuperShape3D {
+    fn update(&mut self, ctx: &mut UpdateContext) {
+        if self.paused {
+            return;
+        }
+        self.angle_y += ROT_SPEED * ctx.args.dt;
+        self.angle_x += 0.5 * ROT_SPEED * ctx.args.dt;
+    }
+}
+
+impl EventHandler for SuperShape3D {
+    fn handle_input(&mut self, ctx: &InputContext) {
+        let old_values = self.values();
+        for cons in self.constants.iter_mut() {
+            cons.handle_input(ctx);
+        }
+        if self.values() != old_values {
+            self.remesh();
+        }
+
+        if ctx.args.state != ButtonState::Release {
+            return;
+        }
+        match ctx.action {
+            Some(PAUSE) => self.paused = !self.paused,
+            Some(FINER) => {
+                self.detail = (self.detail + DETAIL_STEP).min(MAX_DETAIL);
+                self.remesh();
+            }
+            Some(COARSER) => {
+                self.detail = self.detail.saturating_sub(DETAIL_STEP).max(MIN_DETAIL);
+                self.remesh();
+            }
+            _ => (),
+        }
+    }
+}
+
+impl Runnable for SuperShape3D {
+    fn config(&self) -> WindowConfig {
+        let title = if self.constants.is_empty() {
+            "Spherical Geometry"
+        } else {
+            "3D Supershape"
+        };
+        WindowConfig {
+            width: WIDTH,
+            height: HEIGHT,
+            title: title.to_owned(),
+        }
+    }
+
+    fn shortcuts(&self) -> Shortcuts {
+        Shortcuts::new()
+            .with_action(PAUSE, "pause or unpause the rotation", Key::Space)
+            .with_action(FINER, "more detail", Key::Plus)
+            .with_binding(FINER, Key::Equals)
+            .with_action(COARSER, "less detail", Key::Minus)
+    }
+}
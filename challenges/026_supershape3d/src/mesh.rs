@@ -0,0 +1,48 @@
+use math::vec3d::Vec3D;
+use std::f64::consts::PI;
+
+/// Triangle mesh, faces are wound counter-clockwise when seen from outside
+pub struct Mesh {
+    pub vertices: Vec<Vec3D>,
+    pub faces: Vec<[usize; 3]>,
+}
+
+impl Mesh {
+    pub fn sphere(radius: f64, detail: usize) -> Mesh {
+        Mesh::spherical_product(radius, detail, |_| 1.0, |_| 1.0)
+    }
+
+    /// Spherical product of two 2D curves given in polar form,
+    /// `r1` is evaluated at the longitude and `r2` at the latitude
+    pub fn spherical_product<F, G>(radius: f64, detail: usize, r1: F, r2: G) -> Mesh
+    where
+        F: Fn(f64) -> f64,
+        G: Fn(f64) -> f64,
+    {
+        let mut vertices = Vec::with_capacity((detail + 1) * (detail + 1));
+        for j in 0..=detail {
+            let lat = -PI / 2.0 + j as f64 * PI / detail as f64;
+            let r_lat = r2(lat);
+            for i in 0..=detail {
+                let lon = -PI + i as f64 * 2.0 * PI / detail as f64;
+                let r_lon = r1(lon);
+                vertices.push(Vec3D::new(
+                    radius * r_lon * lon.cos() * r_lat * lat.cos(),
+                    radius * r_lon * lon.sin() * r_lat * lat.cos(),
+                    radius * r_lat * lat.sin(),
+                ));
+            }
+        }
+
+        let ind = |i: usize, j: usize| j * (detail + 1) + i;
+        let mut faces = Vec::with_capacity(2 * detail * detail);
+        for j in 0..detail {
+            for i in 0..detail {
+                faces.push([ind(i, j), ind(i + 1, j), ind(i, j + 1)]);
+                faces.push([ind(i + 1, j), ind(i + 1, j + 1), ind(i, j + 1)]);
+            }
+        }
+
+        Mesh { vertices, faces }
+    }
+}
@@ -1,11 +1,9 @@
-use ::image::{Rgba, RgbaImage};
 use graphics::{
-    image, Drawable, DrawingContext, EventHandler, Graphics, InputContext, Runnable, Updatable,
-    UpdateContext, WindowConfig,
+    canvas::Canvas, colors::rgba, Drawable, DrawingContext, EventHandler, Graphics, InputContext,
+    Runnable, Updatable, UpdateContext, WindowConfig,
 };
 use math::{rand_between, vec2d::Vec2D};
 use piston::{Button, ButtonState, MouseButton, ResizeArgs};
-use piston_window::{G2dTexture, TextureSettings};
 
 const WIDTH: f64 = 800.0;
 const HEIGHT: f64 = 900.0;
@@ -22,14 +20,14 @@ struct Metaball {
 
 pub struct Metaballs {
     balls: Vec<Metaball>,
-    image: RgbaImage,
+    canvas: Canvas,
 }
 
 impl Metaballs {
     pub fn new() -> Metaballs {
         Metaballs {
             balls: vec![],
-            image: RgbaImage::new(WIDTH as u32 + 1, HEIGHT as u32 + 1),
+            canvas: Canvas::new(WIDTH as u32 + 1, HEIGHT as u32 + 1),
         }
     }
 }
@@ -37,13 +35,7 @@ impl Metaballs {
 impl Drawable for Metaballs {
     fn draw(&self, ctx: &mut DrawingContext, gl: &mut Graphics) {
         let transform = ctx.id_trans();
-        let texture = G2dTexture::from_image(
-            &mut ctx.texture_context,
-            &self.image,
-            &TextureSettings::new(),
-        )
-        .unwrap();
-        image(&texture, transform, gl);
+        self.canvas.draw(transform, ctx, gl);
     }
 }
 
@@ -61,10 +53,10 @@ impl Updatable for Metaballs {
                     let dist = pos.dist(&ball.pos);
                     trans += ball.r / dist;
                 }
-                self.image.put_pixel(
-                    x as u32,
-                    y as u32,
-                    Rgba([255, 255, 255, (trans * 128.0).round() as u8]),
+                self.canvas.set(
+                    x as i64,
+                    y as i64,
+                    rgba::WHITE.with_trans((trans * 128.0).round() as u8),
                 );
             }
         }
@@ -79,7 +71,7 @@ impl Updatable for Metaball {
 
 impl EventHandler for Metaballs {
     fn handle_resize(&mut self, ctx: &ResizeArgs) {
-        self.image = RgbaImage::new(
+        self.canvas.resize(
             ctx.window_size[0].ceil() as u32 + 1,
             ctx.window_size[1].ceil() as u32 + 1,
         );
@@ -2,7 +2,7 @@ use graphics::{
     rectangle, Drawable, DrawingContext, EventHandler, Graphics, Runnable, SetupContext, Updatable,
     UpdateContext, WindowConfig,
 };
-use math::{rand_between, vec2d::Vec2D};
+use math::{geometry::Rect, rand_between, vec2d::Vec2D};
 use piston_window::text::Text;
 
 const WIDTH: f64 = 800.0;
@@ -37,10 +37,7 @@ pub struct Obstacle {
 
 impl Obstacle {
     fn contains(&self, pos: &Vec2D) -> bool {
-        pos.x >= self.pos.x
-            && pos.x <= self.pos.x + self.width
-            && pos.y >= self.pos.y
-            && pos.y <= self.pos.y + self.height
+        Rect::new(self.pos.x, self.pos.y, self.width, self.height).contains(*pos)
     }
 }
 
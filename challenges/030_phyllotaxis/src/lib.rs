@@ -1,5 +1,5 @@
 use graphics::{
-    colors::{Hsv, Rgba},
+    colors::{Palette, Rgba},
     ellipse, Drawable, DrawingContext, EventHandler, Graphics, Runnable, Updatable, UpdateContext,
     WindowConfig,
 };
@@ -12,6 +12,10 @@ const GROW_TIME: f64 = 0.01;
 const PETAL_SIZE: f64 = 10.0;
 const PHI: f64 = 2.0 * PI * 138.0 / 360.0;
 const SCALING: f64 = PETAL_SIZE;
+/// neighboring hues of a random color, petals switch to the next one every `PETALS_PER_SHADE`
+const NUM_SHADES: usize = 12;
+const SHADE_SPREAD: f64 = 120.0;
+const PETALS_PER_SHADE: usize = 20;
 
 pub struct Petal {
     pos: Vec2D,
@@ -26,17 +30,18 @@ impl Petal {
 
 pub struct Phyllotaxis {
     last_growth: f64,
-    last_color: Hsv,
+    palette: Palette,
     petals: Vec<Petal>,
 }
 
 impl Phyllotaxis {
     pub fn new() -> Phyllotaxis {
-        let start_color = Hsv::new(0.0, 1.0, 1.0).unwrap();
+        let base = Palette::vivid(1).get(0);
+        let palette = Palette::analogous(base, NUM_SHADES, SHADE_SPREAD);
         Phyllotaxis {
             last_growth: 0.0,
-            last_color: start_color,
-            petals: vec![Petal::new(Vec2D::default(), start_color.into())],
+            petals: vec![Petal::new(Vec2D::default(), palette.get(0))],
+            palette,
         }
     }
 
@@ -44,10 +49,9 @@ impl Phyllotaxis {
         let n = self.petals.len() as f64;
         let next_phi = n * PHI;
         let next_r = SCALING * n.sqrt();
-        self.last_color.increase_hue(1.0);
-        let next_rgb: Rgba = self.last_color.into();
+        let next_color = self.palette.get(self.petals.len() / PETALS_PER_SHADE);
         self.petals
-            .push(Petal::new(Vec2D::from_polar(next_r, next_phi), next_rgb))
+            .push(Petal::new(Vec2D::from_polar(next_r, next_phi), next_color))
     }
 }
 
@@ -1,5 +1,9 @@
-use super::{BIRD_SIZE, GRAVITY};
-use graphics::{rectangle, Drawable, DrawingContext, Graphics, Updatable, UpdateContext};
+use super::{BIRD_SIZE, GRAVITY, PIPE_SPEED};
+use graphics::{
+    rectangle,
+    sprite::{Sprite, SpriteSheet},
+    Drawable, DrawingContext, Graphics, Updatable, UpdateContext,
+};
 use math::vec2d::Vec2D;
 
 pub struct Bird {
@@ -14,6 +18,21 @@ impl Bird {
             vel: Vec2D::new(0.0, GRAVITY),
         }
     }
+
+    /// The frame is scaled to the width of the bird, which points where it is flying
+    pub fn draw_sprite(
+        &self,
+        sheet: &SpriteSheet,
+        frame: usize,
+        ctx: &mut DrawingContext,
+        gl: &mut Graphics,
+    ) {
+        let center = self.pos + Vec2D::new(BIRD_SIZE / 2.0, BIRD_SIZE / 2.0);
+        let sprite = Sprite::new(center)
+            .with_rotation((self.vel.y / PIPE_SPEED).atan())
+            .with_scale(BIRD_SIZE / sheet.frame(frame).w as f64);
+        sheet.draw(frame, &sprite, ctx.id_trans(), ctx, gl);
+    }
 }
 
 impl Drawable for Bird {
@@ -1,9 +1,11 @@
 use graphics::{
+    shortcuts::Shortcuts,
+    sprite::{Animation, SpriteSheet},
     Drawable, DrawingContext, EventHandler, Graphics, InputContext, Runnable, SetupContext,
     Updatable, UpdateContext, WindowConfig,
 };
 use math::vec2d::Vec2D;
-use piston::{Button, ButtonState, Key};
+use piston::{ButtonState, Key};
 
 const WIDTH: f64 = 800.0;
 const HEIGHT: f64 = 900.0;
@@ -18,6 +20,13 @@ const PIPE_W: f64 = 100.0;
 const PIPE_SPEED: f64 = 300.0;
 const PIPE_SPAWN: f64 = 2.0;
 
+/// frames of the flapping bird, without them it is drawn as a square
+const BIRD_IMAGE: &str = "challenges/031_flappybird/bird.png";
+const BIRD_ATLAS: &str = "challenges/031_flappybird/bird.json";
+const FLAP_FPS: f64 = 10.0;
+
+const FLAP: &str = "flap";
+
 mod bird;
 mod pipe;
 
@@ -28,6 +37,8 @@ pub struct FlappyBird {
     bird: Bird,
     pipes: Vec<Pipe>,
     last_pipe: f64,
+    sprites: Option<SpriteSheet>,
+    flap_animation: Animation,
 }
 
 impl FlappyBird {
@@ -36,9 +47,15 @@ impl FlappyBird {
             bird: Bird::new(),
             pipes: vec![],
             last_pipe: 0.0,
+            sprites: None,
+            flap_animation: Animation::still(0),
         }
     }
 
+    pub fn flap(&mut self) {
+        self.bird.vel.y = -LIFT;
+    }
+
     pub fn game_over(&self, window_height: f64) -> bool {
         let mut bird_back_bot = self.bird.pos;
         bird_back_bot.y += BIRD_SIZE;
@@ -72,7 +89,12 @@ impl FlappyBird {
 
 impl Drawable for FlappyBird {
     fn draw(&self, ctx: &mut DrawingContext, gl: &mut Graphics) {
-        self.bird.draw(ctx, gl);
+        match &self.sprites {
+            Some(sheet) => self
+                .bird
+                .draw_sprite(sheet, self.flap_animation.frame(), ctx, gl),
+            None => self.bird.draw(ctx, gl),
+        }
         for pipe in self.pipes.iter() {
             pipe.draw(ctx, gl)
         }
@@ -84,6 +106,7 @@ impl Updatable for FlappyBird {
         self.last_pipe += ctx.args.dt;
 
         self.bird.update(ctx);
+        self.flap_animation.update(ctx);
         let mut to_remove = vec![];
         for (ind, pipe) in self.pipes.iter_mut().enumerate() {
             pipe.update(ctx);
@@ -113,15 +136,8 @@ impl EventHandler for FlappyBird {
         if ctx.args.state != ButtonState::Release {
             return;
         }
-        let key = if let Button::Keyboard(key) = ctx.args.button {
-            key
-        } else {
-            return;
-        };
-
-        match key {
-            Key::Space => self.bird.vel.y = -LIFT,
-            _ => (),
+        if let Some(FLAP) = ctx.action {
+            self.flap();
         }
     }
 }
@@ -131,6 +147,17 @@ impl Runnable for FlappyBird {
         self.bird.pos = Vec2D::new(2.0 * BIRD_SIZE, ctx.window_height / 2.0);
         self.pipes
             .push(Pipe::new(ctx.window_width, ctx.window_height));
+
+        match SpriteSheet::load_json(BIRD_IMAGE, BIRD_ATLAS, ctx.texture_context) {
+            Ok(sheet) => {
+                let frames = sheet.atlas().sequence("bird_");
+                if !frames.is_empty() {
+                    self.flap_animation = Animation::new(frames, FLAP_FPS);
+                    self.sprites = Some(sheet);
+                }
+            }
+            Err(err) => println!("Could not load the bird, drawing a square instead: {err}"),
+        }
     }
 
     fn config(&self) -> WindowConfig {
@@ -140,4 +167,8 @@ impl Runnable for FlappyBird {
             title: "Flappy Bird".to_owned(),
         }
     }
+
+    fn shortcuts(&self) -> Shortcuts {
+        Shortcuts::new().with_action(FLAP, "flap", Key::Space)
+    }
 }
@@ -2,7 +2,10 @@ use graphics::{
     clear, line, Drawable, DrawingContext, EventHandler, Graphics, Runnable, SetupContext,
     Updatable, UpdateContext, WindowConfig,
 };
-use math::vec2d::Vec2D;
+use math::{
+    spatial::{KdTree, SpatialIndex},
+    vec2d::Vec2D,
+};
 
 const WIDTH: f64 = 800.0;
 const HEIGHT: f64 = 900.0;
@@ -84,36 +87,30 @@ impl Agario {
         }
     }
 
-    fn eat_food(&mut self) {
-        struct Eaten {
-            food_ind: usize,
-            eater_ind: Option<usize>,
-        }
+    fn food_index(&self) -> KdTree<usize> {
+        KdTree::from_points(self.food.iter().map(|food| food.pos).zip(0..))
+    }
 
-        let mut to_be_eaten = vec![];
-        'food_loop: for (ind, food) in self.food.iter().enumerate() {
-            if Self::collision(&food.pos, &self.player.pos, FOOD_SIZE, self.player.size) {
-                to_be_eaten.push(Eaten {
-                    food_ind: ind,
-                    eater_ind: None,
-                });
-                continue 'food_loop;
-            }
-            for (enemy_ind, enemy) in self.enemies.iter().enumerate() {
-                if Self::collision(&food.pos, &enemy.pos, FOOD_SIZE, enemy.size) {
-                    to_be_eaten.push(Eaten {
-                        food_ind: ind,
-                        eater_ind: Some(enemy_ind),
-                    });
-                    continue 'food_loop;
-                }
+    fn eat_food(&mut self, food_index: &KdTree<usize>) {
+        // the player eats first, then the enemies in order, `None` is the player
+        let mut eaters: Vec<Option<Option<usize>>> = vec![None; self.food.len()];
+        let player_reach = (self.player.size + FOOD_SIZE) / 2.0;
+        for (_, ind) in food_index.within(self.player.pos, player_reach) {
+            eaters[*ind] = Some(None);
+        }
+        for (enemy_ind, enemy) in self.enemies.iter().enumerate() {
+            let enemy_reach = (enemy.size + FOOD_SIZE) / 2.0;
+            for (_, ind) in food_index.within(enemy.pos, enemy_reach) {
+                eaters[*ind].get_or_insert(Some(enemy_ind));
             }
         }
 
-        to_be_eaten.reverse();
-        for eat in to_be_eaten {
-            self.food.remove(eat.food_ind);
-            match eat.eater_ind {
+        for (food_ind, eater) in eaters.into_iter().enumerate().rev() {
+            let Some(eater) = eater else {
+                continue;
+            };
+            self.food.swap_remove(food_ind);
+            match eater {
                 None => self.player.size += GROWTH,
                 Some(ind) => self.enemies[ind].size += GROWTH,
             }
@@ -141,15 +138,22 @@ impl Agario {
     }
 
     fn get_colliding_enemies(&self) -> Vec<(usize, usize)> {
+        let enemy_index = KdTree::from_points(self.enemies.iter().map(|enemy| enemy.pos).zip(0..));
+        let max_size = self
+            .enemies
+            .iter()
+            .map(|enemy| enemy.size)
+            .fold(0.0, f64::max);
+
         let mut colliding = vec![];
         for (ind, enemy) in self.enemies.iter().enumerate() {
-            for other_ind in (ind + 1)..(self.enemies.len() - 1) {
-                let other = self.enemies.get(other_ind).unwrap();
-                if Self::collision(&enemy.pos, &other.pos, enemy.size, other.size)
-                    && !colliding.contains(&(ind, other_ind))
-                    && !colliding.contains(&(other_ind, ind))
+            let reach = (enemy.size + max_size) / 2.0;
+            for (_, other_ind) in enemy_index.within(enemy.pos, reach) {
+                let other = &self.enemies[*other_ind];
+                if *other_ind > ind
+                    && Self::collision(&enemy.pos, &other.pos, enemy.size, other.size)
                 {
-                    colliding.push((ind, other_ind));
+                    colliding.push((ind, *other_ind));
                 }
             }
         }
@@ -173,30 +177,19 @@ impl Agario {
             }
         }
 
-        eaten.reverse();
-        for ind in eaten {
+        eaten.sort();
+        eaten.dedup();
+        for ind in eaten.into_iter().rev() {
             self.enemies.remove(ind);
         }
     }
 
-    fn closest_food(&self, pos: Vec2D) -> Vec2D {
-        let mut min_dist = f64::INFINITY;
-        let mut min_pos = Vec2D::default();
-        for food in self.food.iter() {
-            let dist = pos.dist(&food.pos);
-            if dist < min_dist {
-                min_pos = food.pos;
-                min_dist = dist;
+    fn set_enemy_targets(&mut self, food_index: &KdTree<usize>) {
+        for enemy in self.enemies.iter_mut() {
+            if let Some((closest, _)) = food_index.nearest(enemy.pos, 1).first() {
+                enemy.target = *closest;
             }
         }
-        min_pos
-    }
-
-    fn set_enemy_targets(&mut self) {
-        for i in 0..self.enemies.len() {
-            let closest = self.closest_food(self.enemies[i].pos);
-            self.enemies[i].target = closest;
-        }
     }
 
     fn starve(&mut self) {
@@ -234,13 +227,14 @@ impl Updatable for Agario {
     fn update(&mut self, ctx: &mut UpdateContext) {
         self.spawn_food(ctx.window_width, ctx.window_height);
 
+        let food_index = self.food_index();
         self.player.update(ctx);
-        self.set_enemy_targets();
+        self.set_enemy_targets(&food_index);
         for enemy in self.enemies.iter_mut() {
             enemy.update(ctx);
         }
 
-        self.eat_food();
+        self.eat_food(&food_index);
         self.eat_blobs();
 
         self.starve();
@@ -1,9 +1,16 @@
 use graphics::{
-    colors::rgba, colors::Rgba, ellipse, Drawable, DrawingContext, EventHandler, Graphics,
-    InputContext, Runnable, SetupContext, Updatable, UpdateContext, WindowConfig,
+    colors::rgba, colors::Rgba, ellipse, line, polygon, shortcuts::Shortcuts, Drawable,
+    DrawingContext, EventHandler, Graphics, InputContext, Runnable, SetupContext, Updatable,
+    UpdateContext, WindowConfig,
 };
-use math::{rand_between, vec2d::Vec2D};
-use piston::{Button, ButtonState, Key};
+use math::{
+    delaunay::{lloyd_relax, Triangulation},
+    geometry::{Polygon, Rect},
+    rand_between,
+    spatial::{GridHash, SpatialIndex},
+    vec2d::Vec2D,
+};
+use piston::{ButtonState, Key};
 
 const WIDTH: f64 = 800.0;
 const HEIGHT: f64 = 900.0;
@@ -11,6 +18,28 @@ const HEIGHT: f64 = 900.0;
 const SAMPLE_SIZE: f64 = 20.0;
 const MAX_TRIES: usize = 1000;
 const MIN_DIST: f64 = 30.0;
+const EDGE_THICKNESS: f64 = 1.0;
+
+const RESTART: &str = "restart";
+const MODE: &str = "mode";
+const RELAX: &str = "relax";
+
+#[derive(Clone, Copy, PartialEq)]
+enum DrawMode {
+    Samples,
+    Delaunay,
+    Voronoi,
+}
+
+impl DrawMode {
+    fn next(self) -> DrawMode {
+        match self {
+            DrawMode::Samples => DrawMode::Delaunay,
+            DrawMode::Delaunay => DrawMode::Voronoi,
+            DrawMode::Voronoi => DrawMode::Samples,
+        }
+    }
+}
 
 #[derive(Clone, Copy)]
 struct Sample {
@@ -21,6 +50,10 @@ struct Sample {
 pub struct PoissonDisk {
     samples: Vec<Sample>,
     active_list: Vec<usize>,
+    sample_index: GridHash<usize>,
+    mode: DrawMode,
+    triangulation: Triangulation,
+    cells: Vec<Polygon>,
 }
 
 impl PoissonDisk {
@@ -28,15 +61,50 @@ impl PoissonDisk {
         PoissonDisk {
             samples: vec![],
             active_list: vec![],
+            sample_index: GridHash::new(MIN_DIST),
+            mode: DrawMode::Samples,
+            triangulation: Triangulation::new(&[]),
+            cells: vec![],
         }
     }
 
     pub fn init(&mut self, window_width: f64, window_height: f64) {
+        self.add_sample(Vec2D::new(window_width / 2.0, window_height / 2.0));
+    }
+
+    fn add_sample(&mut self, pos: Vec2D) {
+        self.sample_index.insert(pos, self.samples.len());
+        self.active_list.push(self.samples.len());
         self.samples.push(Sample {
-            pos: Vec2D::new(window_width / 2.0, window_height / 2.0),
+            pos,
             color: Rgba::random(),
         });
-        self.active_list.push(0);
+    }
+
+    fn positions(&self) -> Vec<Vec2D> {
+        self.samples.iter().map(|sample| sample.pos).collect()
+    }
+
+    /// Only recomputed when samples were added or moved since the last time
+    fn update_diagrams(&mut self, window_width: f64, window_height: f64) {
+        let positions = self.positions();
+        if self.mode == DrawMode::Samples || self.triangulation.points == positions {
+            return;
+        }
+        self.triangulation = Triangulation::new(&positions);
+        self.cells = self
+            .triangulation
+            .voronoi(Rect::new(0.0, 0.0, window_width, window_height));
+    }
+
+    fn relax(&mut self, window_width: f64, window_height: f64) {
+        let bounds = Rect::new(0.0, 0.0, window_width, window_height);
+        let relaxed = lloyd_relax(&self.positions(), bounds, 1);
+        self.sample_index.clear();
+        for (ind, (sample, pos)) in self.samples.iter_mut().zip(relaxed).enumerate() {
+            sample.pos = pos;
+            self.sample_index.insert(pos, ind);
+        }
     }
 
     fn pick_next(&mut self) {
@@ -45,7 +113,7 @@ impl PoissonDisk {
         }
 
         let next_ind = rand::random::<usize>() % self.active_list.len();
-        let sample = self.samples[next_ind];
+        let sample = self.samples[self.active_list[next_ind]];
 
         for _ in 0..MAX_TRIES {
             let next_dist = rand_between(MIN_DIST, 2.0 * MIN_DIST);
@@ -53,28 +121,18 @@ impl PoissonDisk {
             next_pos.set_abs(next_dist);
             next_pos += sample.pos;
 
-            if self
-                .samples
-                .iter()
-                .any(|sam| sam.pos.dist(&next_pos) < MIN_DIST)
-            {
+            if !self.sample_index.within(next_pos, MIN_DIST).is_empty() {
                 continue;
             }
 
-            self.samples.push(Sample {
-                pos: next_pos,
-                color: Rgba::random(),
-            });
-            self.active_list.push(self.samples.len() - 1);
+            self.add_sample(next_pos);
             return;
         }
 
         self.active_list.remove(next_ind);
     }
-}
 
-impl Drawable for PoissonDisk {
-    fn draw(&self, ctx: &mut DrawingContext, gl: &mut Graphics) {
+    fn draw_samples(&self, ctx: &mut DrawingContext, gl: &mut Graphics) {
         let transform = ctx.id_trans();
         for (ind, sample) in self.samples.iter().enumerate() {
             let color = if self.active_list.contains(&ind) {
@@ -110,9 +168,47 @@ impl Drawable for PoissonDisk {
     }
 }
 
+impl Drawable for PoissonDisk {
+    fn draw(&self, ctx: &mut DrawingContext, gl: &mut Graphics) {
+        let transform = ctx.id_trans();
+        match self.mode {
+            DrawMode::Samples => (),
+            DrawMode::Delaunay => {
+                for [a, b] in self.triangulation.edges() {
+                    let (start, end) = (self.triangulation.points[a], self.triangulation.points[b]);
+                    line(
+                        rgba::WHITE.into(),
+                        EDGE_THICKNESS,
+                        [start.x, start.y, end.x, end.y],
+                        transform,
+                        gl,
+                    );
+                }
+            }
+            DrawMode::Voronoi => {
+                for (cell, sample) in self.cells.iter().zip(self.samples.iter()) {
+                    let verts: Vec<[f64; 2]> = cell.verts.iter().map(|v| [v.x, v.y]).collect();
+                    polygon(sample.color.into(), &verts, transform, gl);
+                    for edge in cell.edges() {
+                        line(
+                            rgba::BLACK.into(),
+                            EDGE_THICKNESS,
+                            [edge.start.x, edge.start.y, edge.end.x, edge.end.y],
+                            transform,
+                            gl,
+                        );
+                    }
+                }
+            }
+        }
+        self.draw_samples(ctx, gl);
+    }
+}
+
 impl Updatable for PoissonDisk {
-    fn update(&mut self, _: &mut UpdateContext) {
+    fn update(&mut self, ctx: &mut UpdateContext) {
         self.pick_next();
+        self.update_diagrams(ctx.window_width, ctx.window_height);
     }
 }
 
@@ -122,18 +218,15 @@ impl EventHandler for PoissonDisk {
             return;
         }
 
-        let key = if let Button::Keyboard(key) = ctx.args.button {
-            key
-        } else {
-            return;
-        };
-
-        match key {
-            Key::Space => {
+        match ctx.action {
+            Some(RESTART) => {
                 self.samples.clear();
                 self.active_list.clear();
+                self.sample_index.clear();
                 self.init(ctx.window_width, ctx.window_height);
             }
+            Some(MODE) => self.mode = self.mode.next(),
+            Some(RELAX) => self.relax(ctx.window_width, ctx.window_height),
             _ => (),
         }
     }
@@ -151,4 +244,11 @@ impl Runnable for PoissonDisk {
             title: "Poisson Disk Sampling".to_owned(),
         }
     }
+
+    fn shortcuts(&self) -> Shortcuts {
+        Shortcuts::new()
+            .with_action(RESTART, "sample again", Key::Space)
+            .with_action(MODE, "draw samples, triangles or cells", Key::M)
+            .with_action(RELAX, "spread the samples out (Lloyd relaxation)", Key::L)
+    }
 }
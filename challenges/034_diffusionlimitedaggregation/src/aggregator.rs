@@ -1,11 +1,15 @@
 use super::{AGG_SIZE, WALKER_SIZE};
 use graphics::{colors::Rgba, ellipse, Drawable, DrawingContext, Graphics};
-use math::vec2d::Vec2D;
+use math::{
+    spatial::{GridHash, SpatialIndex},
+    vec2d::Vec2D,
+};
 
 pub struct Aggregator {
     pub pos: Vec2D,
     pub color: Rgba,
-    pub aggregated: Vec<Vec2D>,
+    aggregated: Vec<Vec2D>,
+    aggregated_index: GridHash<()>,
 }
 
 impl Aggregator {
@@ -14,12 +18,23 @@ impl Aggregator {
             pos: Vec2D::default(),
             color: Rgba::random(),
             aggregated: vec![],
+            aggregated_index: GridHash::new(WALKER_SIZE + AGG_SIZE),
         }
     }
 
+    pub fn aggregate(&mut self, pos: Vec2D) {
+        self.aggregated.push(pos);
+        self.aggregated_index.insert(pos, ());
+    }
+
     pub fn walker_hits(&self, walker_pos: &Vec2D) -> bool {
-        let cmp_fun = |pos: &Vec2D| pos.dist(&walker_pos) < WALKER_SIZE / 2.0 + AGG_SIZE / 2.0;
-        cmp_fun(&self.pos) || self.aggregated.iter().any(|pt| cmp_fun(pt))
+        let hit_dist = WALKER_SIZE / 2.0 + AGG_SIZE / 2.0;
+        self.pos.dist(walker_pos) < hit_dist
+            || self
+                .aggregated_index
+                .within(*walker_pos, hit_dist)
+                .iter()
+                .any(|(pos, _)| pos.dist(walker_pos) < hit_dist)
     }
 }
 
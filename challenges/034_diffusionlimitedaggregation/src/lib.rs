@@ -1,6 +1,6 @@
 use graphics::{
-    Drawable, DrawingContext, EventHandler, Graphics, Runnable, SetupContext, Updatable,
-    UpdateContext, WindowConfig,
+    grid_image::load_gray, Drawable, DrawingContext, EventHandler, Graphics, Runnable,
+    SetupContext, Updatable, UpdateContext, WindowConfig,
 };
 use math::{rand_between, vec2d::Vec2D};
 
@@ -13,6 +13,9 @@ const WALKER_SIZE: f64 = 20.0;
 const AGG_SIZE: f64 = 10.0;
 const STEP_SIZE: f64 = 10.0;
 
+/// white pixels are the seeds, one pixel per aggregate sized cell of the window
+const SEED_FILE: &str = "challenges/034_diffusionlimitedaggregation/seed.png";
+
 mod aggregator;
 mod trace;
 mod walker;
@@ -49,11 +52,37 @@ impl DiffAgg {
         to_remove.reverse();
         for (agg_ind, walker_ind) in to_remove {
             let old_walker = self.walkers.remove(walker_ind);
-            self.aggregators[agg_ind].aggregated.push(old_walker.pos);
+            self.aggregators[agg_ind].aggregate(old_walker.pos);
             //            self.traces.push(old_walker.trace);
         }
     }
 
+    /// Centers of the cells that are white in the seed image scaled to the window
+    fn load_seeds(window_width: f64, window_height: f64) -> Vec<Vec2D> {
+        let cols = (window_width / AGG_SIZE).ceil() as usize;
+        let rows = (window_height / AGG_SIZE).ceil() as usize;
+        let seeds = match load_gray(SEED_FILE).map(|seeds| seeds.resample(cols, rows)) {
+            Ok(Some(seeds)) => seeds,
+            Ok(None) => return vec![],
+            Err(err) => {
+                println!("Could not load {SEED_FILE}: {err}");
+                return vec![];
+            }
+        };
+        let mut positions = vec![];
+        for y in 0..rows {
+            for x in 0..cols {
+                if seeds[(x, y)] > 0.5 {
+                    positions.push(Vec2D::new(
+                        (x as f64 + 0.5) * AGG_SIZE,
+                        (y as f64 + 0.5) * AGG_SIZE,
+                    ));
+                }
+            }
+        }
+        positions
+    }
+
     fn fill_walkers(&mut self, window_width: f64, window_height: f64) {
         while self.walkers.len() < NUM_WALKERS {
             let mut new_walker = Walker::new();
@@ -100,6 +129,17 @@ impl Runnable for DiffAgg {
     fn setup(&mut self, ctx: &mut SetupContext) {
         self.fill_walkers(ctx.window_width, ctx.window_height);
 
+        let mut seeds = DiffAgg::load_seeds(ctx.window_width, ctx.window_height).into_iter();
+        if let Some(pos) = seeds.next() {
+            let mut new_agg = Aggregator::new();
+            new_agg.pos = pos;
+            for pos in seeds {
+                new_agg.aggregate(pos);
+            }
+            self.aggregators.push(new_agg);
+            return;
+        }
+
         for _ in 0..NUM_AGG {
             let mut new_agg = Aggregator::new();
             new_agg.pos = Vec2D::new(
@@ -1,5 +1,8 @@
-use super::{EDGE_THICK, VERT_SIZE};
-use graphics::{colors::Rgba, ellipse, line, Drawable, DrawingContext, Graphics};
+use super::{EDGE_THICK, NUM_VERTS, VERT_SIZE};
+use graphics::{
+    colors::{rgba, Palette, Rgba},
+    ellipse, line, Drawable, DrawingContext, Graphics,
+};
 use math::vec2d::Vec2D;
 
 pub struct Vertex {
@@ -8,16 +11,17 @@ pub struct Vertex {
 }
 
 impl Vertex {
-    pub fn new(x: f64, y: f64) -> Vertex {
+    pub fn new(x: f64, y: f64, color: Rgba) -> Vertex {
         Vertex {
             pos: Vec2D::new(x, y),
-            color: Rgba::random(),
+            color,
         }
     }
 }
 
 pub struct Graph {
     vertices: Vec<Vertex>,
+    palette: Palette,
     active_color: Rgba,
     pub active_path: Vec<usize>,
 }
@@ -26,13 +30,14 @@ impl Graph {
     pub fn new() -> Graph {
         Graph {
             vertices: vec![],
+            palette: Palette::vivid(NUM_VERTS),
             active_path: vec![],
-            active_color: Rgba::random(),
+            active_color: rgba::WHITE,
         }
     }
 
     pub fn add_vertex(&mut self, x: f64, y: f64) {
-        let new_vert = Vertex::new(x, y);
+        let new_vert = Vertex::new(x, y, self.palette.get(self.vertices.len()));
         self.vertices.push(new_vert);
     }
 
@@ -1,6 +1,7 @@
 use graphics::{
-    colors::Rgba, Drawable, DrawingContext, EventHandler, Graphics, Runnable, SetupContext,
-    Updatable, UpdateContext, WindowConfig,
+    colors::{rgba, Rgba},
+    Drawable, DrawingContext, EventHandler, Graphics, Runnable, SetupContext, Updatable,
+    UpdateContext, WindowConfig,
 };
 use math::rand_between;
 use piston_window::text::Text;
@@ -39,7 +40,7 @@ impl TravellingSalesPerson {
     pub fn new() -> TravellingSalesPerson {
         TravellingSalesPerson {
             graph: Graph::new(),
-            text_color: Rgba::random(),
+            text_color: rgba::WHITE,
             //solver: Box::new(BruteForceSolver::new()) as Box<dyn Solver>,
             solver: Box::new(GeneticSolver::new()) as Box<dyn Solver>,
         }
@@ -1,13 +1,17 @@
 use graphics::{
-    colors::Rgba, Drawable, DrawingContext, EventHandler, Graphics, Runnable, Updatable,
-    UpdateContext, WindowConfig,
+    colors::Rgba,
+    fonts::Font,
+    text::{draw_text, HAlign, TextStyle, VAlign},
+    Drawable, DrawingContext, EventHandler, Graphics, Runnable, Updatable, UpdateContext,
+    WindowConfig,
 };
-use piston_window::text::Text;
+use math::vec2d::Vec2D;
 
 const WIDTH: f64 = 800.0;
 const HEIGHT: f64 = 900.0;
 
 const FONT_SIZE: u32 = 18;
+const MARGIN: f64 = 20.0;
 
 pub struct Diastic {
     input_word: String,
@@ -45,29 +49,26 @@ impl Diastic {
 impl Drawable for Diastic {
     fn draw(&self, ctx: &mut DrawingContext, gl: &mut Graphics) {
         let transform = ctx.center_trans();
-        Text::new_color(self.word_color.into(), FONT_SIZE)
-            .draw_pos(
-                &self.input_word,
-                [0.0, -(FONT_SIZE as f64)],
-                ctx.glyphs,
-                &ctx.context.draw_state,
-                transform,
-                gl,
-            )
-            .unwrap();
-        Text::new_color(self.word_color.into(), FONT_SIZE)
-            .draw_pos(
-                &self.output,
-                [
-                    -(FONT_SIZE as f64 * self.output.len() as f64) / 4.0,
-                    FONT_SIZE as f64,
-                ],
-                ctx.glyphs,
-                &ctx.context.draw_state,
-                transform,
-                gl,
-            )
-            .unwrap();
+        let style = TextStyle::new(FONT_SIZE, self.word_color)
+            .with_font(Font::MONOSPACE)
+            .with_align(HAlign::Center, VAlign::Baseline)
+            .with_max_width(ctx.args.window_size[0] - 2.0 * MARGIN);
+        draw_text(
+            &self.input_word,
+            Vec2D::new(0.0, -(FONT_SIZE as f64)),
+            &style,
+            transform,
+            ctx,
+            gl,
+        );
+        draw_text(
+            &self.output,
+            Vec2D::new(0.0, FONT_SIZE as f64),
+            &style,
+            transform,
+            ctx,
+            gl,
+        );
     }
 }
 
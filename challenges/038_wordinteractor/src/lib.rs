@@ -1,8 +1,9 @@
 use graphics::{
-    colors::rgba, line, polygon, ui_elements::TextField, Drawable, DrawingContext, EventHandler,
-    Graphics, InputContext, Runnable, SetupContext, Updatable, UpdateContext, WindowConfig,
+    colors::rgba, line, polygon, shortcuts::Shortcuts, ui_elements::TextField, Drawable,
+    DrawingContext, EventHandler, Graphics, InputContext, Runnable, SetupContext, Updatable,
+    UpdateContext, WindowConfig,
 };
-use piston::{Button, ButtonState, Key};
+use piston::{ButtonState, Key};
 
 const WIDTH: f64 = 800.0;
 const HEIGHT: f64 = 900.0;
@@ -17,6 +18,8 @@ const PAUSE_W: f64 = 3.0;
 const PAUSE_Y: f64 = 10.0;
 const PAUSE_H: f64 = 20.0;
 
+const PAUSE: &str = "pause";
+
 pub struct WordInteractor {
     text_field: TextField,
     replacement: TextField,
@@ -106,7 +109,7 @@ impl EventHandler for WordInteractor {
         if !self.text_field.active()
             && !self.replacement.active()
             && ctx.args.state == ButtonState::Release
-            && ctx.args.button == Button::Keyboard(Key::Space)
+            && ctx.action == Some(PAUSE)
         {
             self.on = !self.on;
         }
@@ -132,4 +135,8 @@ impl Runnable for WordInteractor {
             title: "Word Interactor".to_owned(),
         }
     }
+
+    fn shortcuts(&self) -> Shortcuts {
+        Shortcuts::new().with_action(PAUSE, "start or stop replacing words", Key::Space)
+    }
 }
@@ -1,9 +1,14 @@
 use graphics::{
-    colors::Rgba, ui_elements::TextField, Drawable, DrawingContext, EventHandler, Graphics,
-    InputContext, Runnable, SetupContext, Updatable, UpdateContext, WindowConfig,
+    colors::Rgba,
+    fonts::Font,
+    shortcuts::Shortcuts,
+    text::{draw_text, HAlign, TextStyle, VAlign},
+    ui_elements::TextField,
+    Drawable, DrawingContext, EventHandler, Graphics, InputContext, Runnable, SetupContext,
+    Updatable, UpdateContext, WindowConfig,
 };
-use piston::{Button, ButtonState, Key};
-use piston_window::text::Text;
+use math::vec2d::Vec2D;
+use piston::{ButtonState, Key};
 use std::{convert::Infallible, fmt, fs::read_to_string, path::PathBuf, str::FromStr};
 
 const WIDTH: f64 = 800.0;
@@ -12,6 +17,8 @@ const FONT_SIZE: u32 = 18;
 const SENTENCE_LIST: &str = "challenges/039_madlibs/sentences.txt";
 const INPUT_W: f64 = 200.0;
 
+const SUBMIT: &str = "submit";
+
 #[derive(Debug)]
 enum HoleType {
     Adjective,
@@ -138,11 +145,13 @@ pub struct MadLibs {
 
 impl MadLibs {
     pub fn new() -> MadLibs {
+        let mut input_field = TextField::new(0.0, 0.0, INPUT_W, FONT_SIZE as f64 + 2.0, FONT_SIZE);
+        input_field.set_font(Font::MONOSPACE);
         MadLibs {
             sentences: vec![],
             current_sentence: 0,
             text_color: Rgba::random(),
-            input_field: TextField::new(0.0, 0.0, INPUT_W, FONT_SIZE as f64 + 2.0, FONT_SIZE),
+            input_field,
         }
     }
 
@@ -170,32 +179,27 @@ impl Drawable for MadLibs {
         }
         let (fst_str, snd_str) = sentence_str.split_at(middle);
 
-        let text = Text::new_color(self.text_color.into(), FONT_SIZE);
+        let style = TextStyle::new(FONT_SIZE, self.text_color)
+            .with_font(Font::MONOSPACE)
+            .with_align(HAlign::Center, VAlign::Baseline);
+        let center_x = ctx.args.window_size[0] / 2.0;
         let start_y = ctx.args.window_size[1] / 3.0;
-        text.draw_pos(
-            &fst_str,
-            [
-                ctx.args.window_size[0] / 2.0 - current_sentence.len() as f64 * FONT_SIZE as f64,
-                start_y,
-            ],
-            ctx.glyphs,
-            &ctx.context.draw_state,
+        draw_text(
+            fst_str.trim(),
+            Vec2D::new(center_x, start_y),
+            &style,
             transform,
+            ctx,
             gl,
-        )
-        .unwrap();
-        text.draw_pos(
-            &snd_str,
-            [
-                ctx.args.window_size[0] / 2.0 - current_sentence.len() as f64 * FONT_SIZE as f64,
-                start_y + 2.0 + FONT_SIZE as f64,
-            ],
-            ctx.glyphs,
-            &ctx.context.draw_state,
+        );
+        draw_text(
+            snd_str.trim(),
+            Vec2D::new(center_x, start_y + style.line_height()),
+            &style,
             transform,
+            ctx,
             gl,
-        )
-        .unwrap();
+        );
 
         self.input_field.draw(ctx, gl);
     }
@@ -208,9 +212,7 @@ impl Updatable for MadLibs {
 impl EventHandler for MadLibs {
     fn handle_input(&mut self, ctx: &InputContext) {
         self.input_field.handle_input(ctx);
-        if ctx.args.state != ButtonState::Release
-            || ctx.args.button != Button::Keyboard(Key::Return)
-        {
+        if ctx.args.state != ButtonState::Release || ctx.action != Some(SUBMIT) {
             return;
         }
 
@@ -247,4 +249,8 @@ impl Runnable for MadLibs {
             title: "MadLibs".to_owned(),
         }
     }
+
+    fn shortcuts(&self) -> Shortcuts {
+        Shortcuts::new().with_action(SUBMIT, "fill in the word", Key::Return)
+    }
 }
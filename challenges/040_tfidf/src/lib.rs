@@ -1,8 +1,12 @@
 use graphics::{
-    Drawable, DrawingContext, EventHandler, Graphics, Runnable, SetupContext, Updatable,
-    UpdateContext, WindowConfig,
+    colors::Palette, Drawable, DrawingContext, EventHandler, Graphics, Runnable, SetupContext,
+    Updatable, UpdateContext, WindowConfig,
+};
+use math::{
+    rand_between,
+    spatial::{GridHash, SpatialIndex},
+    vec2d::Vec2D,
 };
-use math::{rand_between, vec2d::Vec2D};
 
 const WIDTH: f64 = 800.0;
 const HEIGHT: f64 = 900.0;
@@ -13,6 +17,9 @@ const SIZE_MULT: f64 = 2000.0;
 
 const FRICTION: f64 = 0.99;
 
+/// bubbles cycle through these colors, labels are black or white depending on the bubble
+const NUM_COLORS: usize = 24;
+
 mod word_freq;
 use word_freq::WordFreq;
 
@@ -27,6 +34,20 @@ impl TfIdf {
         }
     }
 
+    /// Indices of the words at the start of the frame, bucketed by the largest word
+    fn word_index(&self) -> (GridHash<usize>, f64) {
+        let max_radius = self
+            .word_counts
+            .iter()
+            .map(|freq| freq.radius())
+            .fold(0.0, f64::max);
+        let mut index = GridHash::new((2.0 * max_radius).max(1.0));
+        for (ind, freq) in self.word_counts.iter().enumerate() {
+            index.insert(freq.pos, ind);
+        }
+        (index, max_radius)
+    }
+
     fn calc_tf(&mut self) {
         let count_sums = self.word_counts.iter().map(|freq| freq.cnt).sum::<u64>() as f64;
         for freq in self.word_counts.iter_mut() {
@@ -46,8 +67,17 @@ impl Drawable for TfIdf {
 impl Updatable for TfIdf {
     fn update(&mut self, ctx: &mut UpdateContext) {
         let center = Vec2D::new(ctx.window_width / 2.0, ctx.window_height / 2.0);
+        let (word_index, max_radius) = self.word_index();
         for i in 0..self.word_counts.len() {
-            for j in (i + 1)..self.word_counts.len() {
+            let reach = self.word_counts[i].radius() + max_radius;
+            let mut neighbors: Vec<usize> = word_index
+                .within(self.word_counts[i].pos, reach)
+                .into_iter()
+                .map(|(_, j)| *j)
+                .filter(|j| *j > i)
+                .collect();
+            neighbors.sort();
+            for j in neighbors {
                 if self.word_counts[i].collides(&self.word_counts[j]) {
                     let pos_i = self.word_counts[i].pos;
                     let r_i = self.word_counts[i].radius();
@@ -93,6 +123,7 @@ impl EventHandler for TfIdf {}
 impl Runnable for TfIdf {
     fn setup(&mut self, ctx: &mut SetupContext) {
         let contents = std::fs::read_to_string(TEXT_FILE).unwrap();
+        let palette = Palette::random(NUM_COLORS, 0.45..0.85, 0.08..0.16, rand::random());
         for line in contents.lines() {
             for word in line.split(" ") {
                 let word = word.to_lowercase();
@@ -105,6 +136,7 @@ impl Runnable for TfIdf {
                             word,
                             rand_between(0.0, ctx.window_width),
                             rand_between(0.0, ctx.window_height),
+                            palette.get(self.word_counts.len()),
                         ));
                     }
                     Some(freq) => freq.cnt += 1,
@@ -1,10 +1,11 @@
 use super::{FONT_SIZE, FRICTION, SIZE_MULT};
 use graphics::{
-    colors::Rgba, ellipse, Drawable, DrawingContext, Graphics, Transformed, Updatable,
-    UpdateContext,
+    colors::{palette::text_color, Rgba},
+    ellipse,
+    text::{draw_text, HAlign, TextStyle, VAlign},
+    Drawable, DrawingContext, Graphics, Transformed, Updatable, UpdateContext,
 };
 use math::vec2d::Vec2D;
-use piston_window::text::Text;
 
 pub struct WordFreq {
     pub word: String,
@@ -18,13 +19,13 @@ pub struct WordFreq {
 }
 
 impl WordFreq {
-    pub fn new(word: String, x: f64, y: f64) -> WordFreq {
+    pub fn new(word: String, x: f64, y: f64, color: Rgba) -> WordFreq {
         WordFreq {
             word,
             cnt: 1,
             tf: 0.0,
-            color: Rgba::random(),
-            text_color: Rgba::random(),
+            color,
+            text_color: text_color(color),
             pos: Vec2D::new(x, y),
             vel: Vec2D::default(),
             acc: Vec2D::default(),
@@ -71,16 +72,9 @@ impl Drawable for WordFreq {
             transform,
             gl,
         );
-        Text::new_color(self.text_color.into(), FONT_SIZE)
-            .draw_pos(
-                self.word.as_str(),
-                [-(FONT_SIZE as f64 * self.word.len() as f64) / 2.0, 0.0],
-                ctx.glyphs,
-                &ctx.context.draw_state,
-                transform,
-                gl,
-            )
-            .unwrap();
+        let style =
+            TextStyle::new(FONT_SIZE, self.text_color).with_align(HAlign::Center, VAlign::Middle);
+        draw_text(&self.word, Vec2D::default(), &style, transform, ctx, gl);
     }
 }
 
@@ -0,0 +1,233 @@
+use std::{fs, path::Path};
+
+/// Anything that can report how loud it currently is,
+/// a live microphone backend only needs to implement this
+pub trait AudioSource {
+    /// RMS amplitude of the next `dt` seconds of audio, `None` once the source is exhausted
+    fn amplitude(&mut self, dt: f64) -> Option<f64>;
+}
+
+/// Decoded PCM wave file, downmixed to mono samples in [-1,1]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Wav {
+    pub sample_rate: u32,
+    pub samples: Vec<f64>,
+}
+
+fn read_u16(bytes: &[u8], at: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(bytes.get(at..at + 2)?.try_into().ok()?))
+}
+
+fn read_u32(bytes: &[u8], at: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(bytes.get(at..at + 4)?.try_into().ok()?))
+}
+
+fn decode_sample(bytes: &[u8], format: u16) -> Option<f64> {
+    match (format, bytes.len()) {
+        (1, 1) => Some((bytes[0] as f64 - 128.0) / 128.0),
+        (1, 2) => Some(i16::from_le_bytes([bytes[0], bytes[1]]) as f64 / 32768.0),
+        (1, 3) => {
+            let val = i32::from_le_bytes([0, bytes[0], bytes[1], bytes[2]]) >> 8;
+            Some(val as f64 / 8388608.0)
+        }
+        (1, 4) => Some(i32::from_le_bytes(bytes.try_into().ok()?) as f64 / 2147483648.0),
+        (3, 4) => Some(f32::from_le_bytes(bytes.try_into().ok()?) as f64),
+        _ => None,
+    }
+}
+
+impl Wav {
+    pub fn open<P: AsRef<Path>>(path: P) -> Option<Wav> {
+        Wav::from_bytes(&fs::read(path).ok()?)
+    }
+
+    /// Supports 8/16/24/32 bit integer and 32 bit float PCM
+    pub fn from_bytes(bytes: &[u8]) -> Option<Wav> {
+        if bytes.get(0..4)? != b"RIFF" || bytes.get(8..12)? != b"WAVE" {
+            return None;
+        }
+
+        let mut format = None;
+        let mut data = None;
+        let mut pos = 12;
+        while pos + 8 <= bytes.len() {
+            let id = &bytes[pos..pos + 4];
+            let len = read_u32(bytes, pos + 4)? as usize;
+            let start = pos + 8;
+            let end = (start + len).min(bytes.len());
+            match id {
+                b"fmt " => {
+                    let audio_format = read_u16(bytes, start)?;
+                    let channels = read_u16(bytes, start + 2)?;
+                    let sample_rate = read_u32(bytes, start + 4)?;
+                    let bits = read_u16(bytes, start + 14)?;
+                    format = Some((audio_format, channels, sample_rate, bits));
+                }
+                b"data" => data = Some(&bytes[start..end]),
+                _ => (),
+            }
+            // chunks are padded to an even length
+            pos = start + len + len % 2;
+        }
+
+        let (audio_format, channels, sample_rate, bits) = format?;
+        let data = data?;
+        let sample_width = bits as usize / 8;
+        let frame_width = sample_width * channels as usize;
+        if sample_width == 0 || frame_width == 0 || sample_rate == 0 {
+            return None;
+        }
+
+        let mut samples = Vec::with_capacity(data.len() / frame_width);
+        for frame in data.chunks_exact(frame_width) {
+            let mut sum = 0.0;
+            for sample in frame.chunks_exact(sample_width) {
+                sum += decode_sample(sample, audio_format)?;
+            }
+            samples.push(sum / channels as f64);
+        }
+
+        Some(Wav {
+            sample_rate,
+            samples,
+        })
+    }
+
+    pub fn duration(&self) -> f64 {
+        self.samples.len() as f64 / self.sample_rate as f64
+    }
+}
+
+pub fn rms(samples: &[f64]) -> f64 {
+    if samples.is_empty() {
+        return 0.0;
+    }
+    let sum_squares: f64 = samples.iter().map(|sample| sample * sample).sum();
+    (sum_squares / samples.len() as f64).sqrt()
+}
+
+/// Plays back a wave file in real time, one window of `dt` seconds per frame
+pub struct WavSource {
+    wav: Wav,
+    position: usize,
+    looping: bool,
+}
+
+impl WavSource {
+    pub fn new(wav: Wav, looping: bool) -> WavSource {
+        WavSource {
+            wav,
+            position: 0,
+            looping,
+        }
+    }
+}
+
+impl AudioSource for WavSource {
+    fn amplitude(&mut self, dt: f64) -> Option<f64> {
+        if self.position >= self.wav.samples.len() {
+            if !self.looping || self.wav.samples.is_empty() {
+                return None;
+            }
+            self.position = 0;
+        }
+
+        let num_samples = ((dt * self.wav.sample_rate as f64).round() as usize).max(1);
+        let end = (self.position + num_samples).min(self.wav.samples.len());
+        let amplitude = rms(&self.wav.samples[self.position..end]);
+        self.position = end;
+        Some(amplitude)
+    }
+}
+
+/// Fires once whenever the amplitude rises above the threshold
+pub struct FlapTrigger {
+    pub threshold: f64,
+    above: bool,
+}
+
+impl FlapTrigger {
+    pub fn new(threshold: f64) -> FlapTrigger {
+        FlapTrigger {
+            threshold,
+            above: false,
+        }
+    }
+
+    pub fn check(&mut self, amplitude: f64) -> bool {
+        let was_above = self.above;
+        self.above = amplitude > self.threshold;
+        self.above && !was_above
+    }
+}
+
+#[cfg(test)]
+mod audio_tests {
+    use super::{rms, AudioSource, FlapTrigger, Wav, WavSource};
+
+    const TONE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/tone.wav");
+    const SILENCE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/silence.wav");
+
+    #[test]
+    fn read_tone() {
+        let wav = Wav::open(TONE).unwrap();
+        assert_eq!(wav.sample_rate, 8000);
+        assert_eq!(wav.samples.len(), 4000);
+        assert_eq!(wav.duration(), 0.5)
+    }
+
+    #[test]
+    fn read_silence() {
+        let wav = Wav::open(SILENCE).unwrap();
+        assert_eq!(wav.sample_rate, 8000);
+        assert!(wav.samples.iter().all(|sample| *sample == 0.0))
+    }
+
+    #[test]
+    fn read_invalid() {
+        let result = Wav::from_bytes(b"RIFF\0\0\0\0WAVEjunk");
+        assert_eq!(result, None)
+    }
+
+    #[test]
+    fn rms_tone() {
+        let wav = Wav::open(TONE).unwrap();
+        let result = rms(&wav.samples);
+        let expected = 0.5 / 2.0_f64.sqrt();
+        assert!((result - expected).abs() < 1e-3)
+    }
+
+    #[test]
+    fn rms_empty() {
+        assert_eq!(rms(&[]), 0.0)
+    }
+
+    #[test]
+    fn source_frames() {
+        let mut source = WavSource::new(Wav::open(TONE).unwrap(), false);
+        let mut frames = 0;
+        while let Some(amplitude) = source.amplitude(0.1) {
+            assert!(amplitude > 0.3);
+            frames += 1;
+        }
+        assert_eq!(frames, 5)
+    }
+
+    #[test]
+    fn source_looping() {
+        let mut source = WavSource::new(Wav::open(SILENCE).unwrap(), true);
+        for _ in 0..20 {
+            assert_eq!(source.amplitude(0.1), Some(0.0));
+        }
+    }
+
+    #[test]
+    fn trigger_rising_edge() {
+        let mut trigger = FlapTrigger::new(0.2);
+        let result: Vec<bool> = [0.0, 0.3, 0.4, 0.1, 0.5]
+            .into_iter()
+            .map(|amp| trigger.check(amp))
+            .collect();
+        assert_eq!(result, vec![false, true, false, false, true])
+    }
+}
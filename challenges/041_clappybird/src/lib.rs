@@ -0,0 +1,116 @@
+use flappybird::FlappyBird;
+use graphics::{
+    line, rectangle, shortcuts::Shortcuts, Color, Drawable, DrawingContext, EventHandler, Graphics,
+    InputContext, Runnable, SetupContext, Updatable, UpdateContext, WindowConfig,
+};
+use piston::{ButtonState, Key};
+
+const WIDTH: f64 = 800.0;
+const HEIGHT: f64 = 900.0;
+
+const AUDIO_FILE: &str = "./challenges/041_clappybird/clap.wav";
+const THRESHOLD: f64 = 0.15;
+const THRESHOLD_STEP: f64 = 0.01;
+
+const LOUDER: &str = "louder";
+const QUIETER: &str = "quieter";
+
+const METER_W: f64 = 20.0;
+const METER_H: f64 = 200.0;
+const METER_COLOR: Color = [0.0, 0.8, 0.2, 1.0];
+const THRESHOLD_COLOR: Color = [1.0, 0.0, 0.0, 1.0];
+
+pub mod audio;
+use audio::{AudioSource, FlapTrigger, Wav, WavSource};
+
+pub struct ClappyBird {
+    game: FlappyBird,
+    source: Box<dyn AudioSource>,
+    trigger: FlapTrigger,
+    amplitude: f64,
+}
+
+impl ClappyBird {
+    pub fn new() -> ClappyBird {
+        let wav = Wav::open(AUDIO_FILE).expect("Could not read audio file");
+        ClappyBird::with_source(Box::new(WavSource::new(wav, true)))
+    }
+
+    pub fn with_source(source: Box<dyn AudioSource>) -> ClappyBird {
+        ClappyBird {
+            game: FlappyBird::new(),
+            source,
+            trigger: FlapTrigger::new(THRESHOLD),
+            amplitude: 0.0,
+        }
+    }
+}
+
+impl Drawable for ClappyBird {
+    fn draw(&self, ctx: &mut DrawingContext, gl: &mut Graphics) {
+        self.game.draw(ctx, gl);
+
+        let transform = ctx.id_trans();
+        let x = ctx.args.window_size[0] - 2.0 * METER_W;
+        let level = self.amplitude.min(1.0) * METER_H;
+        rectangle(
+            METER_COLOR,
+            [x, METER_W + METER_H - level, METER_W, level],
+            transform,
+            gl,
+        );
+        let threshold_y = METER_W + METER_H - self.trigger.threshold.min(1.0) * METER_H;
+        line(
+            THRESHOLD_COLOR,
+            1.0,
+            [x, threshold_y, x + METER_W, threshold_y],
+            transform,
+            gl,
+        );
+    }
+}
+
+impl Updatable for ClappyBird {
+    fn update(&mut self, ctx: &mut UpdateContext) {
+        self.amplitude = self.source.amplitude(ctx.args.dt).unwrap_or(0.0);
+        if self.trigger.check(self.amplitude) {
+            self.game.flap();
+        }
+        self.game.update(ctx);
+    }
+}
+
+impl EventHandler for ClappyBird {
+    fn handle_input(&mut self, ctx: &InputContext) {
+        if ctx.args.state != ButtonState::Release {
+            return;
+        }
+        match ctx.action {
+            Some(LOUDER) => self.trigger.threshold += THRESHOLD_STEP,
+            Some(QUIETER) => {
+                self.trigger.threshold = (self.trigger.threshold - THRESHOLD_STEP).max(0.0)
+            }
+            _ => (),
+        }
+    }
+}
+
+impl Runnable for ClappyBird {
+    fn setup(&mut self, ctx: &mut SetupContext) {
+        self.game.setup(ctx);
+    }
+
+    fn config(&self) -> WindowConfig {
+        WindowConfig {
+            width: WIDTH,
+            height: HEIGHT,
+            title: "Clappy Bird".to_owned(),
+        }
+    }
+
+    fn shortcuts(&self) -> Shortcuts {
+        Shortcuts::new()
+            .with_action(LOUDER, "claps need to be louder", Key::Up)
+            .with_action(QUIETER, "claps can be quieter", Key::Down)
+    }
+}
@@ -1,9 +1,13 @@
 use graphics::{
-    colors::Rgba, ellipse, line, Drawable, DrawingContext, EventHandler, Graphics, Runnable,
-    SetupContext, Updatable, UpdateContext, WindowConfig,
+    colors::{palette::text_color, rgba, Palette, Rgba},
+    ellipse,
+    fonts::Font,
+    line,
+    text::{draw_text, HAlign, TextStyle, VAlign},
+    Drawable, DrawingContext, EventHandler, Graphics, Runnable, SetupContext, Updatable,
+    UpdateContext, WindowConfig,
 };
 use math::{rand_between, vec2d::Vec2D};
-use piston_window::text::Text;
 use std::{collections::HashMap, fs::read_to_string};
 
 const INPUT_FILE: &str = "challenges/042_markovchain/text.txt";
@@ -13,6 +17,7 @@ const HEIGHT: f64 = 900.0;
 
 const STATE_SIZE: f64 = 20.0;
 const LINE_SIZE: f64 = 2.0;
+const FONT_SIZE: u32 = 18;
 const UPDATE_TIME: f64 = 0.1;
 
 struct MarkovState {
@@ -23,10 +28,10 @@ struct MarkovState {
 }
 
 impl MarkovState {
-    pub fn new(out: &str, next: Vec<(usize, f64)>) -> MarkovState {
+    pub fn new(out: &str, next: Vec<(usize, f64)>, color: Rgba) -> MarkovState {
         MarkovState {
             output: out.to_owned(),
-            color: Rgba::random(),
+            color,
             pos: Vec2D::default(),
             next,
         }
@@ -46,7 +51,7 @@ impl MarkovChain {
         MarkovChain {
             states: vec![],
             active: 0,
-            active_color: Rgba::random(),
+            active_color: rgba::WHITE,
             ticks: 0.0,
             output: "".to_owned(),
         }
@@ -62,6 +67,9 @@ impl Drawable for MarkovChain {
             } else {
                 state.color
             };
+            let label_style = TextStyle::new(FONT_SIZE, text_color(color))
+                .with_font(Font::MONOSPACE)
+                .with_align(HAlign::Center, VAlign::Middle);
 
             for (next, prob) in state.next.iter() {
                 let next_pos = self.states[*next].pos;
@@ -98,16 +106,7 @@ impl Drawable for MarkovChain {
                 transform,
                 gl,
             );
-            Text::new(18)
-                .draw_pos(
-                    &state.output,
-                    [state.pos.x, state.pos.y],
-                    ctx.glyphs,
-                    &ctx.context.draw_state,
-                    transform,
-                    gl,
-                )
-                .unwrap();
+            draw_text(&state.output, state.pos, &label_style, transform, ctx, gl);
         }
     }
 }
@@ -161,7 +160,8 @@ impl Runnable for MarkovChain {
             );
         }
 
-        for (word, next_words) in next.iter() {
+        let palette = Palette::vivid(next.len());
+        for (state_ind, (word, next_words)) in next.iter().enumerate() {
             let total_next = next_words.len() as f64;
             let mut next_probs = vec![];
             for next_word in next_words {
@@ -176,7 +176,7 @@ impl Runnable for MarkovChain {
                 };
             }
 
-            let mut next_state = MarkovState::new(word, next_probs);
+            let mut next_state = MarkovState::new(word, next_probs, palette.get(state_ind));
             next_state.pos = Vec2D::new(
                 rand_between(STATE_SIZE, ctx.window_width - STATE_SIZE),
                 rand_between(STATE_SIZE, ctx.window_height - STATE_SIZE),
@@ -1,9 +1,13 @@
 use graphics::{
-    colors::Rgba, Drawable, DrawingContext, EventHandler, Graphics, InputContext, Runnable,
-    Updatable, UpdateContext, WindowConfig,
+    colors::Rgba,
+    fonts::Font,
+    shortcuts::Shortcuts,
+    text::{draw_lines, draw_text, layout, HAlign, TextStyle, VAlign},
+    Drawable, DrawingContext, EventHandler, Graphics, InputContext, Runnable, Updatable,
+    UpdateContext, WindowConfig,
 };
-use piston::{Button, ButtonState, Key};
-use piston_window::text::Text;
+use math::vec2d::Vec2D;
+use piston::{ButtonState, Key};
 use std::fmt;
 
 const WIDTH: f64 = 800.0;
@@ -12,6 +16,8 @@ const HEIGHT: f64 = 900.0;
 const FONT_SIZE: u32 = 18;
 const UPDATE_TIME: f64 = 0.1;
 
+const NEXT: &str = "next";
+
 type Variable = String;
 type Terminal = String;
 
@@ -346,47 +352,30 @@ impl ContextfreeGrammar {
 impl Drawable for ContextfreeGrammar {
     fn draw(&self, ctx: &mut DrawingContext, gl: &mut Graphics) {
         let transform = ctx.id_trans();
-        let start_x = FONT_SIZE as f64;
-        let y_diff = 2.0 * FONT_SIZE as f64;
-        let mut y = FONT_SIZE as f64;
-
-        let vars_str = self.grammar.variables.join(" | ");
-        Text::new_color(self.var_color.into(), FONT_SIZE)
-            .draw_pos(
-                &vars_str,
-                [start_x, y],
-                ctx.glyphs,
-                &ctx.context.draw_state,
-                transform,
-                gl,
-            )
-            .unwrap();
-
-        y += y_diff;
-        let term_str = self.grammar.terminals.join(" | ");
-        Text::new_color(self.term_color.into(), FONT_SIZE)
-            .draw_pos(
-                &term_str,
-                [start_x, y],
-                ctx.glyphs,
-                &ctx.context.draw_state,
-                transform,
-                gl,
-            )
-            .unwrap();
-
-        y += y_diff;
-        let rules_str = self.grammar.rules_to_str();
-        Text::new_color(self.rule_color.into(), FONT_SIZE)
-            .draw_pos(
-                &rules_str,
-                [start_x, y],
-                ctx.glyphs,
-                &ctx.context.draw_state,
-                transform,
-                gl,
-            )
-            .unwrap();
+        let [width, height] = ctx.args.window_size;
+        let margin = FONT_SIZE as f64;
+        let mut y = margin;
+
+        let headers = [
+            (self.grammar.variables.join(" | "), self.var_color),
+            (self.grammar.terminals.join(" | "), self.term_color),
+            (self.grammar.rules_to_str(), self.rule_color),
+        ];
+        for (text, color) in headers {
+            let style = TextStyle::new(FONT_SIZE, color)
+                .with_font(Font::MONOSPACE)
+                .with_align(HAlign::Left, VAlign::Top)
+                .with_max_width(width - 2.0 * margin);
+            let lines = layout(
+                ctx.glyphs_for(&style.font),
+                &text,
+                Vec2D::new(margin, y),
+                &style,
+            );
+            // an empty line between the blocks
+            y += (lines.len() + 1) as f64 * style.line_height();
+            draw_lines(&lines, &style, transform, ctx, gl);
+        }
 
         let out_str = self
             .output
@@ -394,19 +383,12 @@ impl Drawable for ContextfreeGrammar {
             .map(|vt| vt.to_string())
             .collect::<Vec<String>>()
             .join("");
-        Text::new_color(self.output_color.into(), 2 * FONT_SIZE)
-            .draw_pos(
-                &out_str,
-                [
-                    ctx.args.window_size[0] / 2.0 - self.output.len() as f64 * FONT_SIZE as f64,
-                    ctx.args.window_size[1] / 2.0,
-                ],
-                ctx.glyphs,
-                &ctx.context.draw_state,
-                transform,
-                gl,
-            )
-            .unwrap();
+        let style = TextStyle::new(2 * FONT_SIZE, self.output_color)
+            .with_font(Font::MONOSPACE)
+            .with_align(HAlign::Center, VAlign::Middle)
+            .with_max_width(width - 2.0 * margin);
+        let center = Vec2D::new(width / 2.0, height / 2.0);
+        draw_text(&out_str, center, &style, transform, ctx, gl);
     }
 }
 
@@ -449,7 +431,7 @@ impl EventHandler for ContextfreeGrammar {
             return;
         }
 
-        if ctx.args.button == Button::Keyboard(Key::N) {
+        if ctx.action == Some(NEXT) {
             self.current_grammar += 1;
             self.current_grammar = self.current_grammar % Grammar::num_grammars();
             self.grammar = Grammar::ith(self.current_grammar);
@@ -467,4 +449,8 @@ impl Runnable for ContextfreeGrammar {
             title: "Context-Free Grammar".to_owned(),
         }
     }
+
+    fn shortcuts(&self) -> Shortcuts {
+        Shortcuts::new().with_action(NEXT, "next grammar", Key::N)
+    }
 }
@@ -0,0 +1,50 @@
+use super::{STROKE_COLOR, STROKE_W};
+use graphics::{line, Drawable, DrawingContext, Graphics};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Stroke {
+    pub points: Vec<[f64; 2]>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Drawing {
+    pub strokes: Vec<Stroke>,
+}
+
+impl Drawing {
+    pub fn new() -> Drawing {
+        Drawing { strokes: vec![] }
+    }
+
+    pub fn start_stroke(&mut self, pos: [f64; 2]) {
+        self.strokes.push(Stroke { points: vec![pos] });
+    }
+
+    pub fn extend_stroke(&mut self, pos: [f64; 2]) {
+        let stroke = match self.strokes.last_mut() {
+            None => return,
+            Some(stroke) => stroke,
+        };
+        if stroke.points.last() != Some(&pos) {
+            stroke.points.push(pos);
+        }
+    }
+}
+
+impl Drawable for Drawing {
+    fn draw(&self, ctx: &mut DrawingContext, gl: &mut Graphics) {
+        let transform = ctx.id_trans();
+        for stroke in self.strokes.iter() {
+            for pts in stroke.points.windows(2) {
+                line(
+                    STROKE_COLOR,
+                    STROKE_W,
+                    [pts[0][0], pts[0][1], pts[1][0], pts[1][1]],
+                    transform,
+                    gl,
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,237 @@
+use graphics::{
+    colors::rgba,
+    line,
+    ui_elements::{Button, TextField},
+    Color, Drawable, DrawingContext, EventHandler, Graphics, InputContext, Runnable, Updatable,
+    UpdateContext, WindowConfig,
+};
+use piston::{Button as PisButton, ButtonState, MouseButton};
+use piston_window::text::Text;
+
+const WIDTH: f64 = 800.0;
+const HEIGHT: f64 = 900.0;
+
+const STORE_FILE: &str = "./challenges/045_savingdrawings/drawings.json";
+
+const STROKE_COLOR: Color = [1.0, 1.0, 1.0, 1.0];
+const STROKE_W: f64 = 2.0;
+
+const FONT_SIZE: u32 = 18;
+const BAR_H: f64 = 40.0;
+const MARGIN: f64 = 5.0;
+const FIELD_W: f64 = 200.0;
+const BUTTON_W: f64 = 80.0;
+const BUTTON_COLOR: Color = [0.0, 0.4, 0.8, 1.0];
+const TEXT_COLOR: Color = [0.0, 0.0, 0.0, 1.0];
+const LIST_W: f64 = 200.0;
+const ENTRY_H: f64 = 25.0;
+
+pub mod drawing;
+pub mod store;
+
+use drawing::Drawing;
+use store::{DrawingStore, JsonStore};
+
+pub struct SavingDrawings {
+    store: Box<dyn DrawingStore>,
+    drawing: Drawing,
+    drawing_active: bool,
+    name_field: TextField,
+    save_button: Button,
+    clear_button: Button,
+    entries: Vec<Button>,
+    status: String,
+}
+
+impl SavingDrawings {
+    pub fn new() -> SavingDrawings {
+        let store = JsonStore::open(STORE_FILE).expect("Could not open drawing store");
+        SavingDrawings::with_store(Box::new(store))
+    }
+
+    pub fn with_store(store: Box<dyn DrawingStore>) -> SavingDrawings {
+        let field_h = BAR_H - 2.0 * MARGIN;
+        let save_x = 2.0 * MARGIN + FIELD_W;
+        let mut new = SavingDrawings {
+            store,
+            drawing: Drawing::new(),
+            drawing_active: false,
+            name_field: TextField::new(MARGIN, MARGIN, FIELD_W, field_h, FONT_SIZE),
+            save_button: Button::new(
+                save_x,
+                MARGIN,
+                BUTTON_W,
+                field_h,
+                BUTTON_COLOR,
+                "Save",
+                TEXT_COLOR,
+                FONT_SIZE,
+            ),
+            clear_button: Button::new(
+                save_x + BUTTON_W + MARGIN,
+                MARGIN,
+                BUTTON_W,
+                field_h,
+                BUTTON_COLOR,
+                "Clear",
+                TEXT_COLOR,
+                FONT_SIZE,
+            ),
+            entries: vec![],
+            status: "".to_owned(),
+        };
+        new.create_entries();
+        new
+    }
+
+    fn create_entries(&mut self) {
+        self.entries = self
+            .store
+            .names()
+            .iter()
+            .enumerate()
+            .map(|(ind, name)| {
+                Button::new(
+                    WIDTH - LIST_W + MARGIN,
+                    BAR_H + MARGIN + ind as f64 * (ENTRY_H + MARGIN),
+                    LIST_W - 2.0 * MARGIN,
+                    ENTRY_H,
+                    BUTTON_COLOR,
+                    name,
+                    TEXT_COLOR,
+                    FONT_SIZE,
+                )
+            })
+            .collect();
+    }
+
+    fn save(&mut self) {
+        let name = self.name_field.text().trim().to_owned();
+        if name.is_empty() {
+            self.status = "Enter a name first".to_owned();
+            return;
+        }
+        self.status = match self.store.save(&name, &self.drawing) {
+            Ok(()) => format!("Saved {name}"),
+            Err(err) => format!("Could not save {name}: {err}"),
+        };
+        self.create_entries();
+    }
+
+    fn load(&mut self, name: &str) {
+        match self.store.load(name) {
+            None => self.status = format!("Could not load {name}"),
+            Some(drawing) => {
+                self.drawing = drawing;
+                self.name_field.set_text(name.to_owned());
+                self.status = format!("Loaded {name}");
+            }
+        }
+    }
+
+    fn on_canvas(pos: [f64; 2]) -> bool {
+        pos[0] < WIDTH - LIST_W && pos[1] > BAR_H
+    }
+}
+
+impl Drawable for SavingDrawings {
+    fn draw(&self, ctx: &mut DrawingContext, gl: &mut Graphics) {
+        self.drawing.draw(ctx, gl);
+
+        let transform = ctx.id_trans();
+        line(
+            rgba::WHITE.into(),
+            1.0,
+            [0.0, BAR_H, ctx.args.window_size[0], BAR_H],
+            transform,
+            gl,
+        );
+        line(
+            rgba::WHITE.into(),
+            1.0,
+            [
+                WIDTH - LIST_W,
+                BAR_H,
+                WIDTH - LIST_W,
+                ctx.args.window_size[1],
+            ],
+            transform,
+            gl,
+        );
+
+        self.name_field.draw(ctx, gl);
+        self.save_button.draw(ctx, gl);
+        self.clear_button.draw(ctx, gl);
+        for entry in self.entries.iter() {
+            entry.draw(ctx, gl);
+        }
+
+        Text::new_color(rgba::WHITE.into(), FONT_SIZE)
+            .draw_pos(
+                &self.status,
+                [
+                    2.0 * BUTTON_W + FIELD_W + 4.0 * MARGIN,
+                    MARGIN + FONT_SIZE as f64,
+                ],
+                ctx.glyphs,
+                &ctx.context.draw_state,
+                transform,
+                gl,
+            )
+            .unwrap();
+    }
+}
+
+impl Updatable for SavingDrawings {
+    fn update(&mut self, ctx: &mut UpdateContext) {
+        if self.drawing_active && SavingDrawings::on_canvas(ctx.mouse_pos) {
+            self.drawing.extend_stroke(ctx.mouse_pos);
+        }
+    }
+}
+
+impl EventHandler for SavingDrawings {
+    fn handle_input(&mut self, ctx: &InputContext) {
+        self.name_field.handle_input(ctx);
+
+        if self.save_button.clicked(ctx.mouse_pos, ctx.args) {
+            self.save();
+        }
+
+        if self.clear_button.clicked(ctx.mouse_pos, ctx.args) {
+            self.drawing = Drawing::new();
+            self.status.clear();
+        }
+
+        let clicked = self
+            .entries
+            .iter()
+            .find(|entry| entry.clicked(ctx.mouse_pos, ctx.args))
+            .map(|entry| entry.text.clone());
+        if let Some(name) = clicked {
+            self.load(&name);
+        }
+
+        if ctx.args.button != PisButton::Mouse(MouseButton::Left) {
+            return;
+        }
+        match ctx.args.state {
+            ButtonState::Press if SavingDrawings::on_canvas(ctx.mouse_pos) => {
+                self.drawing_active = true;
+                self.drawing.start_stroke(ctx.mouse_pos);
+            }
+            ButtonState::Release => self.drawing_active = false,
+            _ => (),
+        }
+    }
+}
+
+impl Runnable for SavingDrawings {
+    fn config(&self) -> WindowConfig {
+        WindowConfig {
+            width: WIDTH,
+            height: HEIGHT,
+            title: "Saving Drawings".to_owned(),
+        }
+    }
+}
@@ -0,0 +1,154 @@
+use super::drawing::Drawing;
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// Persistent storage for named drawings
+pub trait DrawingStore {
+    fn save(&mut self, name: &str, drawing: &Drawing) -> io::Result<()>;
+    fn load(&self, name: &str) -> Option<Drawing>;
+    /// Names of all saved drawings in alphabetical order
+    fn names(&self) -> Vec<String>;
+}
+
+#[derive(Default)]
+pub struct MemoryStore {
+    drawings: BTreeMap<String, Drawing>,
+}
+
+impl MemoryStore {
+    pub fn new() -> MemoryStore {
+        MemoryStore {
+            drawings: BTreeMap::new(),
+        }
+    }
+}
+
+impl DrawingStore for MemoryStore {
+    fn save(&mut self, name: &str, drawing: &Drawing) -> io::Result<()> {
+        self.drawings.insert(name.to_owned(), drawing.clone());
+        Ok(())
+    }
+
+    fn load(&self, name: &str) -> Option<Drawing> {
+        self.drawings.get(name).cloned()
+    }
+
+    fn names(&self) -> Vec<String> {
+        self.drawings.keys().cloned().collect()
+    }
+}
+
+/// Keeps all drawings in a single json file, which is rewritten on every save
+pub struct JsonStore {
+    path: PathBuf,
+    drawings: BTreeMap<String, Drawing>,
+}
+
+impl JsonStore {
+    /// Reads the existing drawings at `path`, a missing file is treated as an empty store
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<JsonStore> {
+        let path = path.as_ref().to_path_buf();
+        let drawings = match fs::read_to_string(&path) {
+            Ok(contents) => serde_json::from_str(&contents)?,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => BTreeMap::new(),
+            Err(err) => return Err(err),
+        };
+        Ok(JsonStore { path, drawings })
+    }
+}
+
+impl DrawingStore for JsonStore {
+    fn save(&mut self, name: &str, drawing: &Drawing) -> io::Result<()> {
+        self.drawings.insert(name.to_owned(), drawing.clone());
+        let contents = serde_json::to_string(&self.drawings)?;
+        fs::write(&self.path, contents)
+    }
+
+    fn load(&self, name: &str) -> Option<Drawing> {
+        self.drawings.get(name).cloned()
+    }
+
+    fn names(&self) -> Vec<String> {
+        self.drawings.keys().cloned().collect()
+    }
+}
+
+#[cfg(test)]
+mod store_tests {
+    use super::{DrawingStore, JsonStore, MemoryStore};
+    use crate::drawing::Drawing;
+    use std::{env, fs, path::PathBuf};
+
+    fn example_drawing() -> Drawing {
+        let mut drawing = Drawing::new();
+        drawing.start_stroke([0.0, 0.0]);
+        drawing.extend_stroke([1.0, 1.0]);
+        drawing.extend_stroke([2.0, 1.5]);
+        drawing.start_stroke([10.0, 10.0]);
+        drawing
+    }
+
+    fn temp_file(name: &str) -> PathBuf {
+        let path = env::temp_dir().join(format!("savingdrawings_{}_{name}", std::process::id()));
+        let _ = fs::remove_file(&path);
+        path
+    }
+
+    #[test]
+    fn memory_roundtrip() {
+        let mut store = MemoryStore::new();
+        store.save("example", &example_drawing()).unwrap();
+        let result = store.load("example").unwrap();
+        assert_eq!(result, example_drawing())
+    }
+
+    #[test]
+    fn memory_missing() {
+        let store = MemoryStore::new();
+        assert_eq!(store.load("missing"), None)
+    }
+
+    #[test]
+    fn memory_names() {
+        let mut store = MemoryStore::new();
+        store.save("b", &Drawing::new()).unwrap();
+        store.save("a", &Drawing::new()).unwrap();
+        store.save("b", &example_drawing()).unwrap();
+        assert_eq!(store.names(), vec!["a".to_owned(), "b".to_owned()]);
+        assert_eq!(store.load("b").unwrap(), example_drawing())
+    }
+
+    #[test]
+    fn json_missing_file() {
+        let store = JsonStore::open(temp_file("missing.json")).unwrap();
+        assert!(store.names().is_empty())
+    }
+
+    #[test]
+    fn json_roundtrip() {
+        let path = temp_file("roundtrip.json");
+        let mut store = JsonStore::open(&path).unwrap();
+        store.save("example", &example_drawing()).unwrap();
+        store.save("empty", &Drawing::new()).unwrap();
+
+        let reopened = JsonStore::open(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(
+            reopened.names(),
+            vec!["empty".to_owned(), "example".to_owned()]
+        );
+        assert_eq!(reopened.load("example").unwrap(), example_drawing())
+    }
+
+    #[test]
+    fn json_invalid_file() {
+        let path = temp_file("invalid.json");
+        fs::write(&path, "not json").unwrap();
+        let result = JsonStore::open(&path);
+        fs::remove_file(&path).unwrap();
+        assert!(result.is_err())
+    }
+}
@@ -1,9 +1,12 @@
 use graphics::{
-    ellipse, Drawable, DrawingContext, EventHandler, Graphics, InputContext, Runnable,
-    SetupContext, Updatable, UpdateContext, WindowConfig,
+    ellipse,
+    shortcuts::Shortcuts,
+    sprite::{Animation, SpriteSheet},
+    Drawable, DrawingContext, EventHandler, Graphics, InputContext, Runnable, SetupContext,
+    Updatable, UpdateContext, WindowConfig,
 };
 use math::{rand_between, vec2d::Vec2D};
-use piston::{Button, ButtonState, Key};
+use piston::{ButtonState, Key};
 use std::f64::consts::PI;
 
 const WIDTH: f64 = 800.0;
@@ -31,6 +34,18 @@ const AST_MIN_SPLIT: f64 = 50.0;
 
 const NUM_BG: usize = 200;
 
+/// the ship and two frames of its exhaust, without it the ship is drawn as an outline
+const SHIP_SHEET: &str = "challenges/046_asteroids/ship.png";
+const SHIP_FRAME_SIZE: [u32; 2] = [16, 16];
+const SHIP_FRAME: usize = 0;
+const FLAME_FPS: f64 = 12.0;
+
+const LEFT: &str = "left";
+const RIGHT: &str = "right";
+const FORWARD: &str = "forward";
+const BACKWARD: &str = "backward";
+const SHOOT: &str = "shoot";
+
 mod asteroid;
 mod bullet;
 mod player;
@@ -44,6 +59,8 @@ pub struct Asteroids {
     asteroids: Vec<Asteroid>,
     ticks: f64,
     background: Vec<Vec2D>,
+    sprites: Option<SpriteSheet>,
+    flame_animation: Animation,
 }
 
 impl Asteroids {
@@ -54,6 +71,8 @@ impl Asteroids {
             asteroids: vec![],
             ticks: 0.0,
             background: vec![],
+            sprites: None,
+            flame_animation: Animation::new(vec![1, 2], FLAME_FPS),
         }
     }
 
@@ -126,7 +145,16 @@ impl Drawable for Asteroids {
             );
         }
 
-        self.player.draw(ctx, gl);
+        match &self.sprites {
+            Some(sheet) => {
+                let flame = self
+                    .player
+                    .thrusting()
+                    .then(|| self.flame_animation.frame());
+                self.player.draw_sprite(sheet, SHIP_FRAME, flame, ctx, gl);
+            }
+            None => self.player.draw(ctx, gl),
+        }
         for bullet in self.bullets.iter() {
             bullet.draw(ctx, gl)
         }
@@ -141,6 +169,7 @@ impl Updatable for Asteroids {
         self.ticks += ctx.args.dt;
         self.spawn_asteroid(ctx.window_width, ctx.window_height);
         self.player.update(ctx);
+        self.flame_animation.update(ctx);
         self.check_bullets(ctx);
 
         for asteroid in self.asteroids.iter_mut() {
@@ -162,8 +191,7 @@ impl Updatable for Asteroids {
 impl EventHandler for Asteroids {
     fn handle_input(&mut self, ctx: &InputContext) {
         self.player.handle_input(ctx);
-        if ctx.args.state == ButtonState::Release && ctx.args.button == Button::Keyboard(Key::Space)
-        {
+        if ctx.args.state == ButtonState::Release && ctx.action == Some(SHOOT) {
             self.bullets.push(self.player.shoot());
         }
     }
@@ -180,6 +208,11 @@ impl Runnable for Asteroids {
                 rand_between(0.0, ctx.window_height),
             ))
         }
+
+        match SpriteSheet::load_grid(SHIP_SHEET, SHIP_FRAME_SIZE, 0, ctx.texture_context) {
+            Ok(sheet) => self.sprites = Some(sheet),
+            Err(err) => println!("Could not load the ship, drawing its outline instead: {err}"),
+        }
     }
 
     fn config(&self) -> WindowConfig {
@@ -189,4 +222,17 @@ impl Runnable for Asteroids {
             title: "Asteroids".to_owned(),
         }
     }
+
+    fn shortcuts(&self) -> Shortcuts {
+        Shortcuts::new()
+            .with_action(LEFT, "turn left", Key::A)
+            .with_binding(LEFT, Key::Left)
+            .with_action(RIGHT, "turn right", Key::D)
+            .with_binding(RIGHT, Key::Right)
+            .with_action(FORWARD, "accelerate", Key::W)
+            .with_binding(FORWARD, Key::Up)
+            .with_action(BACKWARD, "accelerate backwards", Key::S)
+            .with_binding(BACKWARD, Key::Down)
+            .with_action(SHOOT, "shoot", Key::Space)
+    }
 }
@@ -1,10 +1,15 @@
-use super::{bullet::Bullet, FRICTION, LINE_THICK, PLAYER_ACC, PLAYER_ROT, PLAYER_SIZE};
+use super::{
+    bullet::Bullet, BACKWARD, FORWARD, FRICTION, LEFT, LINE_THICK, PLAYER_ACC, PLAYER_ROT,
+    PLAYER_SIZE, RIGHT,
+};
 use graphics::{
-    colors::Rgba, poly_outline::PolyOutline, Drawable, DrawingContext, EventHandler, Graphics,
-    InputContext, Updatable, UpdateContext,
+    colors::{rgba, Rgba},
+    poly_outline::PolyOutline,
+    sprite::{Sprite, SpriteSheet},
+    Drawable, DrawingContext, EventHandler, Graphics, InputContext, Updatable, UpdateContext,
 };
 use math::vec2d::Vec2D;
-use piston::{Button, ButtonState, Key};
+use piston::ButtonState;
 
 enum RotationState {
     No,
@@ -73,6 +78,37 @@ impl Player {
         self.shape.set_vertices(new_verts);
     }
 
+    pub fn thrusting(&self) -> bool {
+        matches!(self.mov, MoveState::Forward)
+    }
+
+    /// Draws the ship frame in the color of the outline turned to the heading,
+    /// the flame frame is drawn on top in its own colors
+    pub fn draw_sprite(
+        &self,
+        sheet: &SpriteSheet,
+        ship_frame: usize,
+        flame_frame: Option<usize>,
+        ctx: &mut DrawingContext,
+        gl: &mut Graphics,
+    ) {
+        let heading = self.heading();
+        let sprite = Sprite::new(self.shape.center())
+            .with_rotation(heading.y.atan2(heading.x))
+            .with_scale(PLAYER_SIZE / sheet.frame(ship_frame).w as f64);
+        let transform = ctx.id_trans();
+        sheet.draw(
+            ship_frame,
+            &sprite.with_tint(self.shape.color()),
+            transform,
+            ctx,
+            gl,
+        );
+        if let Some(frame) = flame_frame {
+            sheet.draw(frame, &sprite.with_tint(rgba::WHITE), transform, ctx, gl);
+        }
+    }
+
     pub fn shoot(&self) -> Bullet {
         let pos = self.shape.center();
         Bullet::new(pos.x, pos.y, self.heading())
@@ -108,29 +144,13 @@ impl Updatable for Player {
 
 impl EventHandler for Player {
     fn handle_input(&mut self, ctx: &InputContext) {
-        match (ctx.args.state, ctx.args.button) {
-            (ButtonState::Release, Button::Keyboard(key))
-                if matches!(key, Key::A | Key::D | Key::Left | Key::Right) =>
-            {
-                self.rotating = RotationState::No;
-            }
-            (ButtonState::Release, Button::Keyboard(key))
-                if matches!(key, Key::W | Key::S | Key::Up | Key::Down) =>
-            {
-                self.mov = MoveState::No
-            }
-            (ButtonState::Press, Button::Keyboard(key)) if matches!(key, Key::A | Key::Left) => {
-                self.rotating = RotationState::Left
-            }
-            (ButtonState::Press, Button::Keyboard(key)) if matches!(key, Key::D | Key::Right) => {
-                self.rotating = RotationState::Right
-            }
-            (ButtonState::Press, Button::Keyboard(key)) if matches!(key, Key::W | Key::Up) => {
-                self.mov = MoveState::Forward
-            }
-            (ButtonState::Press, Button::Keyboard(key)) if matches!(key, Key::S | Key::Down) => {
-                self.mov = MoveState::Backward
-            }
+        match (ctx.args.state, ctx.action) {
+            (ButtonState::Release, Some(LEFT | RIGHT)) => self.rotating = RotationState::No,
+            (ButtonState::Release, Some(FORWARD | BACKWARD)) => self.mov = MoveState::No,
+            (ButtonState::Press, Some(LEFT)) => self.rotating = RotationState::Left,
+            (ButtonState::Press, Some(RIGHT)) => self.rotating = RotationState::Right,
+            (ButtonState::Press, Some(FORWARD)) => self.mov = MoveState::Forward,
+            (ButtonState::Press, Some(BACKWARD)) => self.mov = MoveState::Backward,
             _ => (),
         }
     }
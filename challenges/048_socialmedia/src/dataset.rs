@@ -0,0 +1,204 @@
+use chrono::{Days, NaiveDate};
+use serde::Deserialize;
+use std::{
+    collections::HashMap,
+    fs::read_to_string,
+    io::{self, ErrorKind},
+    path::Path,
+};
+
+const STOP_WORDS: [&str; 24] = [
+    "the", "and", "for", "that", "this", "with", "are", "was", "our", "you", "your", "more",
+    "about", "today", "from", "have", "has", "will", "all", "what", "who", "every", "means", "new",
+];
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Post {
+    pub date: NaiveDate,
+    pub text: String,
+}
+
+/// Splits a single csv line into its fields, fields can be quoted with `"`
+/// and quotes inside quoted fields are escaped by doubling them
+fn split_csv_line(line: &str) -> Vec<String> {
+    let mut fields = vec![];
+    let mut current = "".to_owned();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                current.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(std::mem::take(&mut current)),
+            ch => current.push(ch),
+        }
+    }
+    fields.push(current);
+    fields
+}
+
+/// Reads posts from a csv file with a header row containing at least `date` and `text`,
+/// rows with a missing field or a bad date are skipped
+fn parse_csv(contents: &str) -> io::Result<Vec<Post>> {
+    let mut lines = contents.lines();
+    let header = match lines.next() {
+        None => return Ok(vec![]),
+        Some(header) => split_csv_line(header),
+    };
+    let column = |name: &str| {
+        header
+            .iter()
+            .position(|col| col == name)
+            .ok_or_else(|| io::Error::new(ErrorKind::InvalidData, format!("no {name} column")))
+    };
+    let date_ind = column("date")?;
+    let text_ind = column("text")?;
+
+    let mut posts = vec![];
+    for (ind, line) in lines.enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let fields = split_csv_line(line);
+        let (Some(date), Some(text)) = (fields.get(date_ind), fields.get(text_ind)) else {
+            println!("Skipping line {}: missing fields", ind + 2);
+            continue;
+        };
+        match NaiveDate::parse_from_str(date, "%Y-%m-%d") {
+            Ok(date) => posts.push(Post {
+                date,
+                text: text.clone(),
+            }),
+            Err(err) => println!("Skipping line {}: bad date {date}: {err}", ind + 2),
+        }
+    }
+    Ok(posts)
+}
+
+/// Loads posts from either a `.csv` or a `.json` file,
+/// json files contain an array of objects with `date` and `text` fields
+pub fn load_posts<P: AsRef<Path>>(path: P) -> io::Result<Vec<Post>> {
+    let contents = read_to_string(&path)?;
+    match path.as_ref().extension().and_then(|ext| ext.to_str()) {
+        Some("json") => serde_json::from_str(&contents)
+            .map_err(|err| io::Error::new(ErrorKind::InvalidData, err)),
+        _ => parse_csv(&contents),
+    }
+}
+
+pub struct Stats {
+    /// Number of posts for every day between the first and last post
+    pub per_day: Vec<(NaiveDate, usize)>,
+    /// Words sorted by descending frequency
+    pub words: Vec<(String, usize)>,
+}
+
+impl Stats {
+    pub fn new(posts: &[Post]) -> Stats {
+        let mut day_counts: HashMap<NaiveDate, usize> = HashMap::new();
+        let mut word_counts: HashMap<String, usize> = HashMap::new();
+        for post in posts.iter() {
+            *day_counts.entry(post.date).or_default() += 1;
+            for word in post.text.split(|ch: char| !ch.is_alphanumeric()) {
+                let word = word.to_lowercase();
+                if word.len() < 3 || STOP_WORDS.contains(&word.as_str()) {
+                    continue;
+                }
+                *word_counts.entry(word).or_default() += 1;
+            }
+        }
+
+        let mut per_day = vec![];
+        let first = day_counts.keys().min().copied();
+        let last = day_counts.keys().max().copied();
+        if let (Some(mut day), Some(last)) = (first, last) {
+            while day <= last {
+                per_day.push((day, day_counts.get(&day).copied().unwrap_or(0)));
+                day = day + Days::new(1);
+            }
+        }
+
+        let mut words: Vec<(String, usize)> = word_counts.into_iter().collect();
+        words.sort_by(|(w1, c1), (w2, c2)| c2.cmp(c1).then(w1.cmp(w2)));
+
+        Stats { per_day, words }
+    }
+
+    pub fn max_per_day(&self) -> usize {
+        self.per_day.iter().map(|(_, cnt)| *cnt).max().unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+mod dataset_tests {
+    use super::{parse_csv, split_csv_line, Post, Stats};
+    use chrono::NaiveDate;
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 3, day).unwrap()
+    }
+
+    fn post(day: u32, text: &str) -> Post {
+        Post {
+            date: date(day),
+            text: text.to_owned(),
+        }
+    }
+
+    #[test]
+    fn csv_lines() {
+        assert_eq!(split_csv_line("a,b,,c"), vec!["a", "b", "", "c"]);
+        assert_eq!(
+            split_csv_line(r#"1,"hello, world","say ""hi""""#),
+            vec!["1", "hello, world", r#"say "hi""#]
+        );
+    }
+
+    #[test]
+    fn csv_posts() {
+        let csv = "account,date,text\n\
+                   a,2024-03-01,\"first, post\"\n\
+                   \n\
+                   b,not a date,skipped\n\
+                   c,2024-03-02\n\
+                   d,2024-03-03,third\n";
+        let posts = parse_csv(csv).unwrap();
+        assert_eq!(posts.len(), 2);
+        assert_eq!(posts[0].date, date(1));
+        assert_eq!(posts[0].text, "first, post");
+        assert_eq!(posts[1].text, "third");
+
+        assert!(parse_csv("date,account\n2024-03-01,a\n").is_err());
+        assert!(parse_csv("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn stats() {
+        let posts = [
+            post(3, "Health care for the community"),
+            post(1, "health, HEALTH and jobs"),
+            post(3, "Jobs jobs"),
+        ];
+        let stats = Stats::new(&posts);
+        assert_eq!(
+            stats.per_day,
+            vec![(date(1), 1), (date(2), 0), (date(3), 2)]
+        );
+        assert_eq!(stats.max_per_day(), 2);
+        assert_eq!(stats.words[0], ("health".to_owned(), 3));
+        assert_eq!(stats.words[1], ("jobs".to_owned(), 3));
+        assert_eq!(stats.words[2], ("care".to_owned(), 1));
+        // stop words and short words are not counted
+        assert!(stats
+            .words
+            .iter()
+            .all(|(word, _)| word != "the" && word != "for"));
+
+        let empty = Stats::new(&[]);
+        assert!(empty.per_day.is_empty());
+        assert_eq!(empty.max_per_day(), 0);
+    }
+}
@@ -0,0 +1,219 @@
+use graphics::{
+    ellipse, line, rectangle, Color, Drawable, DrawingContext, EventHandler, Graphics, Runnable,
+    Updatable, UpdateContext, WindowConfig,
+};
+use piston_window::text::Text;
+
+const WIDTH: f64 = 800.0;
+const HEIGHT: f64 = 900.0;
+
+const DATA_FILE: &str = "./challenges/048_socialmedia/posts.csv";
+
+const FONT_SIZE: u32 = 14;
+const MARGIN: f64 = 50.0;
+const WORDS_W: f64 = 220.0;
+const NUM_WORDS: usize = 25;
+const WORD_H: f64 = 20.0;
+const LABEL_EVERY: usize = 7;
+const POINT_R: f64 = 3.0;
+
+const AXIS_COLOR: Color = [1.0, 1.0, 1.0, 1.0];
+const LINE_COLOR: Color = [0.2, 0.6, 1.0, 1.0];
+const HIGHLIGHT_COLOR: Color = [1.0, 0.8, 0.0, 1.0];
+const BAR_COLOR: Color = [0.2, 0.6, 1.0, 0.6];
+
+pub mod dataset;
+use dataset::{load_posts, Stats};
+
+pub struct SocialMedia {
+    stats: Stats,
+    hovered: Option<usize>,
+}
+
+impl SocialMedia {
+    pub fn new() -> SocialMedia {
+        let posts = load_posts(DATA_FILE).unwrap_or_else(|err| {
+            println!("Could not load posts from {DATA_FILE}: {err}");
+            vec![]
+        });
+        SocialMedia {
+            stats: Stats::new(&posts),
+            hovered: None,
+        }
+    }
+
+    /// Chart area as `[x, y, width, height]`
+    fn chart_rect(window_width: f64, window_height: f64) -> [f64; 4] {
+        [
+            MARGIN,
+            MARGIN,
+            window_width - WORDS_W - 2.0 * MARGIN,
+            window_height - 2.0 * MARGIN,
+        ]
+    }
+
+    fn point(&self, ind: usize, count: usize, rect: [f64; 4]) -> [f64; 2] {
+        let num_days = self.stats.per_day.len().max(2) - 1;
+        let max_count = self.stats.max_per_day().max(1);
+        [
+            rect[0] + rect[2] * ind as f64 / num_days as f64,
+            rect[1] + rect[3] * (1.0 - count as f64 / max_count as f64),
+        ]
+    }
+
+    fn draw_chart(&self, ctx: &mut DrawingContext, gl: &mut Graphics) {
+        let transform = ctx.id_trans();
+        let rect = SocialMedia::chart_rect(ctx.args.window_size[0], ctx.args.window_size[1]);
+        let bottom = rect[1] + rect[3];
+
+        line(
+            AXIS_COLOR,
+            1.0,
+            [rect[0], rect[1], rect[0], bottom],
+            transform,
+            gl,
+        );
+        line(
+            AXIS_COLOR,
+            1.0,
+            [rect[0], bottom, rect[0] + rect[2], bottom],
+            transform,
+            gl,
+        );
+
+        let text = Text::new_color(AXIS_COLOR, FONT_SIZE);
+        let max_count = self.stats.max_per_day();
+        for count in [0, max_count / 2, max_count] {
+            let [_, y] = self.point(0, count, rect);
+            text.draw_pos(
+                &count.to_string(),
+                [rect[0] - MARGIN / 2.0, y],
+                ctx.glyphs,
+                &ctx.context.draw_state,
+                transform,
+                gl,
+            )
+            .unwrap();
+        }
+
+        let points: Vec<[f64; 2]> = self
+            .stats
+            .per_day
+            .iter()
+            .enumerate()
+            .map(|(ind, (_, count))| self.point(ind, *count, rect))
+            .collect();
+        for pts in points.windows(2) {
+            line(
+                LINE_COLOR,
+                1.0,
+                [pts[0][0], pts[0][1], pts[1][0], pts[1][1]],
+                transform,
+                gl,
+            );
+        }
+
+        for (ind, (pt, (date, _))) in points.iter().zip(self.stats.per_day.iter()).enumerate() {
+            let color = if self.hovered == Some(ind) {
+                HIGHLIGHT_COLOR
+            } else {
+                LINE_COLOR
+            };
+            ellipse(
+                color,
+                [
+                    pt[0] - POINT_R,
+                    pt[1] - POINT_R,
+                    2.0 * POINT_R,
+                    2.0 * POINT_R,
+                ],
+                transform,
+                gl,
+            );
+
+            if ind % LABEL_EVERY == 0 {
+                text.draw_pos(
+                    &date.format("%m-%d").to_string(),
+                    [pt[0] - FONT_SIZE as f64, bottom + FONT_SIZE as f64 + 5.0],
+                    ctx.glyphs,
+                    &ctx.context.draw_state,
+                    transform,
+                    gl,
+                )
+                .unwrap();
+            }
+        }
+
+        if let Some((date, count)) = self.hovered.and_then(|ind| self.stats.per_day.get(ind)) {
+            Text::new_color(HIGHLIGHT_COLOR, FONT_SIZE)
+                .draw_pos(
+                    &format!("{date}: {count} posts"),
+                    [rect[0] + 10.0, rect[1]],
+                    ctx.glyphs,
+                    &ctx.context.draw_state,
+                    transform,
+                    gl,
+                )
+                .unwrap();
+        }
+    }
+
+    fn draw_words(&self, ctx: &mut DrawingContext, gl: &mut Graphics) {
+        let transform = ctx.id_trans();
+        let start_x = ctx.args.window_size[0] - WORDS_W;
+        let max_count = self.stats.words.first().map(|(_, cnt)| *cnt).unwrap_or(1);
+        let text = Text::new_color(AXIS_COLOR, FONT_SIZE);
+        for (ind, (word, count)) in self.stats.words.iter().take(NUM_WORDS).enumerate() {
+            let y = MARGIN + ind as f64 * WORD_H;
+            let bar_w = (WORDS_W - MARGIN) * *count as f64 / max_count as f64;
+            rectangle(BAR_COLOR, [start_x, y, bar_w, WORD_H - 2.0], transform, gl);
+            text.draw_pos(
+                &format!("{word} ({count})"),
+                [start_x + 2.0, y + FONT_SIZE as f64],
+                ctx.glyphs,
+                &ctx.context.draw_state,
+                transform,
+                gl,
+            )
+            .unwrap();
+        }
+    }
+}
+
+impl Drawable for SocialMedia {
+    fn draw(&self, ctx: &mut DrawingContext, gl: &mut Graphics) {
+        self.draw_chart(ctx, gl);
+        self.draw_words(ctx, gl);
+    }
+}
+
+impl Updatable for SocialMedia {
+    fn update(&mut self, ctx: &mut UpdateContext) {
+        let rect = SocialMedia::chart_rect(ctx.window_width, ctx.window_height);
+        let [mouse_x, mouse_y] = ctx.mouse_pos;
+        if self.stats.per_day.is_empty()
+            || mouse_x < rect[0]
+            || mouse_x > rect[0] + rect[2]
+            || mouse_y < rect[1]
+            || mouse_y > rect[1] + rect[3]
+        {
+            self.hovered = None;
+            return;
+        }
+        let num_days = self.stats.per_day.len().max(2) - 1;
+        let ind = ((mouse_x - rect[0]) / rect[2] * num_days as f64).round() as usize;
+        self.hovered = Some(ind.min(self.stats.per_day.len() - 1));
+    }
+}
+
+impl EventHandler for SocialMedia {}
+
+impl Runnable for SocialMedia {
+    fn config(&self) -> WindowConfig {
+        WindowConfig {
+            width: WIDTH,
+            height: HEIGHT,
+            title: "Social Media Data".to_owned(),
+        }
+    }
+}
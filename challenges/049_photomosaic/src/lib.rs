@@ -0,0 +1,126 @@
+use ::image::RgbaImage;
+use graphics::{
+    image as img, shortcuts::Shortcuts, Drawable, DrawingContext, EventHandler, Graphics,
+    InputContext, Runnable, SetupContext, Updatable, WindowConfig,
+};
+use piston::{ButtonState, Key};
+use piston_window::{G2dTexture, TextureSettings};
+
+const WIDTH: f64 = 800.0;
+const HEIGHT: f64 = 900.0;
+
+const IMAGE_FILE: &str = "./challenges/047_pixelsorting/image.png";
+const TILE_DIR: &str = "./challenges/049_photomosaic/tiles";
+
+const TILE_SIZE: u32 = 16;
+const MIN_TILE_SIZE: u32 = 4;
+const MAX_TILE_SIZE: u32 = 64;
+
+const TOGGLE: &str = "toggle";
+const BIGGER: &str = "bigger";
+const SMALLER: &str = "smaller";
+
+mod mosaic;
+use mosaic::{build_mosaic, load_tiles, Tile};
+
+pub struct PhotoMosaic {
+    target: RgbaImage,
+    tiles: Vec<RgbaImage>,
+    tile_size: u32,
+    mosaic: RgbaImage,
+    show_target: bool,
+}
+
+impl PhotoMosaic {
+    pub fn new() -> PhotoMosaic {
+        let target = image::open(IMAGE_FILE).unwrap().into_rgba8();
+        PhotoMosaic {
+            mosaic: target.clone(),
+            target,
+            tiles: vec![],
+            tile_size: TILE_SIZE,
+            show_target: false,
+        }
+    }
+
+    /// Without tiles the mosaic is the target itself
+    fn compute(&mut self) {
+        if self.tiles.is_empty() {
+            self.mosaic = self.target.clone();
+            return;
+        }
+        let tiles: Vec<Tile> = self
+            .tiles
+            .iter()
+            .map(|tile| Tile::new(tile, self.tile_size))
+            .collect();
+        self.mosaic = build_mosaic(&self.target, &tiles, self.tile_size);
+    }
+}
+
+impl Drawable for PhotoMosaic {
+    fn draw(&self, ctx: &mut DrawingContext, gl: &mut Graphics) {
+        let transform = ctx.id_trans();
+        let shown = if self.show_target {
+            &self.target
+        } else {
+            &self.mosaic
+        };
+        let texture =
+            G2dTexture::from_image(&mut ctx.texture_context, shown, &TextureSettings::new())
+                .unwrap();
+        img(&texture, transform, gl);
+    }
+}
+
+impl Updatable for PhotoMosaic {}
+
+impl EventHandler for PhotoMosaic {
+    fn handle_input(&mut self, ctx: &InputContext) {
+        if ctx.args.state != ButtonState::Release {
+            return;
+        }
+
+        match ctx.action {
+            Some(TOGGLE) => self.show_target = !self.show_target,
+            Some(BIGGER) if self.tile_size < MAX_TILE_SIZE => {
+                self.tile_size *= 2;
+                self.compute();
+            }
+            Some(SMALLER) if self.tile_size > MIN_TILE_SIZE => {
+                self.tile_size /= 2;
+                self.compute();
+            }
+            _ => (),
+        }
+    }
+}
+
+impl Runnable for PhotoMosaic {
+    fn setup(&mut self, _: &mut SetupContext) {
+        match load_tiles(TILE_DIR) {
+            Ok(tiles) if tiles.is_empty() => println!("No tile images found in {TILE_DIR}"),
+            Ok(tiles) => {
+                self.tiles = tiles;
+                self.compute();
+            }
+            Err(err) => println!("Could not load tiles from {TILE_DIR}: {err}"),
+        }
+    }
+
+    fn config(&self) -> WindowConfig {
+        WindowConfig {
+            width: WIDTH,
+            height: HEIGHT,
+            title: "Photo Mosaic".to_owned(),
+        }
+    }
+
+    fn shortcuts(&self) -> Shortcuts {
+        Shortcuts::new()
+            .with_action(TOGGLE, "show the mosaic or the original image", Key::Space)
+            .with_action(BIGGER, "double the tile size", Key::Plus)
+            .with_binding(BIGGER, Key::Equals)
+            .with_action(SMALLER, "halve the tile size", Key::Minus)
+    }
+}
@@ -0,0 +1,133 @@
+use image::{
+    imageops::{self, FilterType},
+    ImageError, RgbaImage,
+};
+use std::{
+    fs::read_dir,
+    io::{self, ErrorKind},
+    path::Path,
+};
+
+pub struct Tile {
+    image: RgbaImage,
+    avg: [f64; 3],
+}
+
+impl Tile {
+    pub fn new(image: &RgbaImage, size: u32) -> Tile {
+        let image = imageops::resize(image, size, size, FilterType::Triangle);
+        let avg = average_color(&image, 0, 0, size, size);
+        Tile { image, avg }
+    }
+}
+
+/// Average rgb color of the given region, clipped to the image bounds
+pub fn average_color(image: &RgbaImage, x: u32, y: u32, w: u32, h: u32) -> [f64; 3] {
+    let mut sum = [0.0; 3];
+    let mut num = 0.0;
+    for j in y..(y + h).min(image.height()) {
+        for i in x..(x + w).min(image.width()) {
+            let pixel = image.get_pixel(i, j);
+            for (ch, sum) in sum.iter_mut().enumerate() {
+                *sum += pixel[ch] as f64;
+            }
+            num += 1.0;
+        }
+    }
+    if num == 0.0 {
+        return sum;
+    }
+    sum.map(|ch| ch / num)
+}
+
+/// Loads every image in `dir` in file name order, files that are not images are skipped
+pub fn load_tiles<P: AsRef<Path>>(dir: P) -> io::Result<Vec<RgbaImage>> {
+    let mut paths = read_dir(dir)?
+        .map(|entry| Ok(entry?.path()))
+        .collect::<io::Result<Vec<_>>>()?;
+    paths.sort();
+    let mut tiles = vec![];
+    for path in paths {
+        match image::open(&path) {
+            Ok(tile) => tiles.push(tile.into_rgba8()),
+            Err(ImageError::Unsupported(_)) => (),
+            Err(ImageError::IoError(err)) => return Err(err),
+            Err(err) => {
+                let msg = format!("{}: {err}", path.display());
+                return Err(io::Error::new(ErrorKind::InvalidData, msg));
+            }
+        }
+    }
+    Ok(tiles)
+}
+
+fn closest_tile(tiles: &[Tile], color: [f64; 3]) -> &Tile {
+    let dist = |tile: &Tile| {
+        (0..3)
+            .map(|ch| (tile.avg[ch] - color[ch]).powi(2))
+            .sum::<f64>()
+    };
+    tiles
+        .iter()
+        .min_by(|t1, t2| dist(t1).partial_cmp(&dist(t2)).unwrap())
+        .unwrap()
+}
+
+/// Replaces every `size`x`size` cell of `target` with the tile closest in average color
+pub fn build_mosaic(target: &RgbaImage, tiles: &[Tile], size: u32) -> RgbaImage {
+    let mut mosaic = RgbaImage::new(target.width(), target.height());
+    for y in (0..target.height()).step_by(size as usize) {
+        for x in (0..target.width()).step_by(size as usize) {
+            let color = average_color(target, x, y, size, size);
+            let tile = closest_tile(tiles, color);
+            imageops::replace(&mut mosaic, &tile.image, x as i64, y as i64);
+        }
+    }
+    mosaic
+}
+
+#[cfg(test)]
+mod mosaic_tests {
+    use super::{average_color, build_mosaic, load_tiles, Tile};
+    use image::{Rgba, RgbaImage};
+
+    fn solid(color: [u8; 3], size: u32) -> RgbaImage {
+        RgbaImage::from_pixel(size, size, Rgba([color[0], color[1], color[2], 255]))
+    }
+
+    #[test]
+    fn average() {
+        let mut image = solid([0, 0, 0], 2);
+        image.put_pixel(1, 0, Rgba([200, 100, 40, 255]));
+        assert_eq!(average_color(&image, 0, 0, 2, 2), [50.0, 25.0, 10.0]);
+        assert_eq!(average_color(&image, 1, 0, 1, 1), [200.0, 100.0, 40.0]);
+        // regions are clipped to the image
+        assert_eq!(average_color(&image, 1, 0, 5, 5), [100.0, 50.0, 20.0]);
+        assert_eq!(average_color(&image, 2, 2, 1, 1), [0.0; 3]);
+    }
+
+    #[test]
+    fn nearest_tiles() {
+        let tiles = [
+            Tile::new(&solid([250, 0, 0], 8), 2),
+            Tile::new(&solid([0, 0, 250], 8), 2),
+        ];
+        let mut target = solid([200, 20, 30], 4);
+        for y in 0..4 {
+            for x in 2..4 {
+                target.put_pixel(x, y, Rgba([10, 40, 180, 255]));
+            }
+        }
+        let mosaic = build_mosaic(&target, &tiles, 2);
+        assert_eq!(mosaic.dimensions(), (4, 4));
+        for (x, y, pixel) in mosaic.enumerate_pixels() {
+            let expected = if x < 2 { [250, 0, 0] } else { [0, 0, 250] };
+            assert_eq!(pixel.0[..3], expected, "pixel {x},{y}");
+        }
+    }
+
+    #[test]
+    fn missing_tile_dir() {
+        assert!(load_tiles("./no/such/tile/dir").is_err())
+    }
+}
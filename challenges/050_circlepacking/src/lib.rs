@@ -2,7 +2,11 @@ use graphics::{
     Drawable, DrawingContext, EventHandler, Graphics, Runnable, SetupContext, Updatable,
     UpdateContext, WindowConfig,
 };
-use math::{rand_between, vec2d::Vec2D};
+use math::{
+    rand_between,
+    spatial::{GridHash, SpatialIndex},
+    vec2d::Vec2D,
+};
 use std::f64::consts::PI;
 
 const WIDTH: f64 = 800.0;
@@ -22,6 +26,7 @@ use circle::Circle;
 
 pub struct CirclePacking {
     circles: Vec<Circle>,
+    circle_index: GridHash<usize>,
     fill_area: Area,
 }
 
@@ -29,12 +34,17 @@ impl CirclePacking {
     pub fn new() -> CirclePacking {
         CirclePacking {
             circles: vec![],
+            circle_index: GridHash::new(MAX_R),
             fill_area: Area::new(Vec2D::default(), vec![]),
         }
     }
 
     fn collides(&self, pos: &Vec2D, r: f64) -> bool {
-        self.circles.iter().any(|circ| circ.collides(pos, r))
+        // no circle is larger than MAX_R, so further centers can not collide
+        self.circle_index
+            .within(*pos, r + MAX_R)
+            .iter()
+            .any(|(_, ind)| self.circles[**ind].collides(pos, r))
     }
 }
 
@@ -62,6 +72,7 @@ impl Updatable for CirclePacking {
         }
         r -= R_GROW;
 
+        self.circle_index.insert(new_pos, self.circles.len());
         self.circles.push(Circle::new(new_pos, r));
     }
 }
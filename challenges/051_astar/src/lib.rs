@@ -1,8 +1,13 @@
 use graphics::{
-    colors::rgba, line, rectangle, Drawable, DrawingContext, EventHandler, Graphics, Runnable,
-    Updatable, UpdateContext, WindowConfig,
+    colors::{rgba, Colormap, Gradient},
+    grid_image::load_gray,
+    line, rectangle,
+    shortcuts::Shortcuts,
+    Drawable, DrawingContext, EventHandler, Graphics, InputContext, Runnable, Updatable,
+    UpdateContext, WindowConfig,
 };
-use math::grid::Grid;
+use math::grid::{Boundary, Grid, Neighborhood};
+use piston::{ButtonState, Key};
 use std::cmp::Ordering;
 
 const WIDTH: f64 = 800.0;
@@ -12,6 +17,11 @@ const ROWS: usize = 20;
 const COLS: usize = 20;
 const MAX_W: usize = 255;
 
+/// bright cells are expensive, random weights are used without it
+const HEIGHTMAP_FILE: &str = "challenges/051_astar/heightmap.png";
+
+const COLORMAP: &str = "colormap";
+
 pub type Pos = (usize, usize);
 pub type WeightedPos = (Pos, usize);
 
@@ -49,6 +59,8 @@ pub struct AStar {
     open: Vec<SearchPos>,
     closed: Vec<SearchPos>,
     done: bool,
+    colormap: Colormap,
+    gradient: Gradient,
 }
 
 impl AStar {
@@ -62,7 +74,7 @@ impl AStar {
             rand::random::<usize>() % ROWS,
         );
         AStar {
-            weights: Grid::from_fn(|_, _| rand::random::<usize>() & MAX_W, COLS, ROWS),
+            weights: AStar::load_weights(),
             start,
             end,
             open: vec![SearchPos {
@@ -72,6 +84,20 @@ impl AStar {
             }],
             closed: Vec::new(),
             done: false,
+            colormap: Colormap::default(),
+            gradient: Colormap::default().gradient(),
+        }
+    }
+
+    fn load_weights() -> Grid<usize> {
+        match load_gray(HEIGHTMAP_FILE) {
+            Ok(heights) => heights
+                .resample_bilinear(COLS, ROWS)
+                .map(|height| (height * MAX_W as f64).round() as usize),
+            Err(err) => {
+                println!("Could not load {HEIGHTMAP_FILE}: {err}");
+                Grid::from_fn(|_, _| rand::random::<usize>() & MAX_W, COLS, ROWS)
+            }
         }
     }
 
@@ -125,28 +151,10 @@ impl AStar {
     }
 
     fn neighbors(&self, pos: SearchPos) -> Vec<Pos> {
-        let (x, y) = (pos.pos.0 as i32, pos.pos.1 as i32);
-
-        let mut pos = vec![];
-        let diag_tl = (x > 0 && y > 0).then_some((x - 1, y - 1));
-        pos.push(diag_tl);
-        let diag_tr = (x < COLS as i32 - 1 && y > 0).then_some((x + 1, y - 1));
-        pos.push(diag_tr);
-        let top = (y > 0).then_some((x, y - 1));
-        pos.push(top);
-        let left = (x > 0).then_some((x - 1, y));
-        pos.push(left);
-        let right = (x < COLS as i32 - 1).then_some((x + 1, y));
-        pos.push(right);
-        let diag_bl = (x > 0 && y < ROWS as i32 - 1).then_some((x - 1, y + 1));
-        pos.push(diag_bl);
-        let diag_br = (x < COLS as i32 - 1 && y < ROWS as i32 - 1).then_some((x + 1, y + 1));
-        pos.push(diag_br);
-        let bottom = (y < ROWS as i32 - 1).then_some((x, y + 1));
-        pos.push(bottom);
-
-        pos.into_iter()
-            .filter_map(|x| x.map(|(fst, snd)| (fst as usize, snd as usize)))
+        let (x, y) = pos.pos;
+        self.weights
+            .neighbors(x, y, Neighborhood::Moore, Boundary::Zero)
+            .map(|(pos, _)| pos)
             .collect()
     }
 }
@@ -160,8 +168,8 @@ impl Drawable for AStar {
             for j in 0..ROWS {
                 let x = i as f64 * cell_width;
                 let y = j as f64 * cell_height;
-                let w = self.weights[(i, j)] as u8;
-                let color = rgba::WHITE.with_trans(w);
+                let w = self.weights[(i, j)] as f64 / MAX_W as f64;
+                let color = self.gradient.map(w);
                 rectangle(color.into(), [x, y, cell_width, cell_height], transform, gl);
             }
         }
@@ -251,7 +259,19 @@ impl Updatable for AStar {
     }
 }
 
-impl EventHandler for AStar {}
+impl EventHandler for AStar {
+    fn handle_input(&mut self, ctx: &InputContext) {
+        if ctx.args.state != ButtonState::Release {
+            return;
+        }
+
+        if let Some(COLORMAP) = ctx.action {
+            self.colormap = self.colormap.next();
+            self.gradient = self.colormap.gradient();
+            println!("Colormap: {:?}", self.colormap);
+        }
+    }
+}
 
 impl Runnable for AStar {
     fn config(&self) -> WindowConfig {
@@ -261,4 +281,8 @@ impl Runnable for AStar {
             title: "AStar".to_owned(),
         }
     }
+
+    fn shortcuts(&self) -> Shortcuts {
+        Shortcuts::new().with_action(COLORMAP, "next colormap", Key::C)
+    }
 }
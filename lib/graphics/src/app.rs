@@ -1,13 +1,20 @@
-use super::{DrawingContext, InputContext, Runnable, SetupContext, UpdateContext};
+use super::{
+    fonts::Fonts,
+    shortcuts::{Binding, Shortcuts, HELP},
+    DrawingContext, InputContext, Runnable, SetupContext, UpdateContext, SHORTCUT_DIR,
+};
 use graphics::clear;
 use opengl_graphics::OpenGL;
 use piston::{
     event_loop::{EventSettings, Events},
-    input::{ButtonArgs, RenderArgs, RenderEvent, UpdateArgs, UpdateEvent},
+    input::{
+        Button, ButtonArgs, ButtonState, Key, RenderArgs, RenderEvent, UpdateArgs, UpdateEvent,
+    },
     window::WindowSettings,
     ButtonEvent, Event, MouseCursorEvent, ResizeEvent, Window,
 };
 use piston_window::{G2dTextureContext, Glyphs, PistonWindow, TextureSettings};
+use std::path::Path;
 
 pub struct App<T: Runnable> {
     window: PistonWindow,
@@ -16,7 +23,13 @@ pub struct App<T: Runnable> {
     runnable: T,
     mouse_pos: [f64; 2],
     glyphs: Glyphs,
+    fonts: Fonts,
     texture_context: G2dTextureContext,
+    title: String,
+    shortcuts: Shortcuts,
+    left_shift: bool,
+    right_shift: bool,
+    show_help: bool,
 }
 
 impl<T: Runnable> App<T> {
@@ -25,7 +38,7 @@ impl<T: Runnable> App<T> {
 
         let opengl = OpenGL::V3_2;
         let mut window: PistonWindow =
-            WindowSettings::new(config.title, [config.width, config.height])
+            WindowSettings::new(config.title.clone(), [config.width, config.height])
                 .graphics_api(opengl)
                 .exit_on_esc(true)
                 .build()
@@ -39,6 +52,14 @@ impl<T: Runnable> App<T> {
             TextureSettings::new(),
         )
         .unwrap();
+        let mut fonts = Fonts::new(window.create_texture_context());
+        for file in runnable.fonts() {
+            if let Err(err) = fonts.load(&file, window.create_texture_context()) {
+                println!("Could not load font {}: {err}", file.path.display());
+            }
+        }
+
+        let shortcuts = load_shortcuts(&runnable, &config.title);
 
         let context = window.create_texture_context();
         App {
@@ -47,7 +68,13 @@ impl<T: Runnable> App<T> {
             runnable,
             mouse_pos: [0.0, 0.0],
             glyphs,
+            fonts,
             texture_context: context,
+            title: config.title,
+            shortcuts,
+            left_shift: false,
+            right_shift: false,
+            show_help: false,
         }
     }
 
@@ -59,10 +86,15 @@ impl<T: Runnable> App<T> {
                 context: &c,
                 args,
                 glyphs: &mut self.glyphs,
+                fonts: &mut self.fonts,
                 texture_context: &mut self.texture_context,
             };
             self.runnable.draw(&mut context, gl);
+            if self.show_help {
+                self.shortcuts.draw_help(&self.title, &mut context, gl);
+            }
             self.glyphs.factory.encoder.flush(device);
+            self.fonts.flush(device);
         });
     }
 
@@ -79,12 +111,36 @@ impl<T: Runnable> App<T> {
     }
 
     fn handle_input(&mut self, args: &ButtonArgs) {
+        let action = match args.button {
+            Button::Keyboard(key @ (Key::LShift | Key::RShift)) => {
+                let pressed = args.state == ButtonState::Press;
+                if key == Key::LShift {
+                    self.left_shift = pressed;
+                } else {
+                    self.right_shift = pressed;
+                }
+                self.shortcuts.action(key.into())
+            }
+            Button::Keyboard(key) => self.shortcuts.action(Binding {
+                key,
+                shift: self.left_shift || self.right_shift,
+            }),
+            _ => None,
+        };
+        if action == Some(HELP) {
+            if args.state == ButtonState::Press {
+                self.show_help = !self.show_help;
+            }
+            return;
+        }
+
         let size = self.window.size();
         let ctx = InputContext {
             args,
             window_width: size.width,
             window_height: size.height,
             mouse_pos: self.mouse_pos,
+            action,
         };
         self.runnable.handle_input(&ctx)
     }
@@ -115,3 +171,26 @@ impl<T: Runnable> App<T> {
         }
     }
 }
+
+/// The shortcuts of the runnable with the help overlay and the remapped keys of
+/// `shortcuts/<title>.txt`, the help keys are added to a help action of the runnable
+fn load_shortcuts<T: Runnable>(runnable: &T, title: &str) -> Shortcuts {
+    let mut shortcuts = runnable
+        .shortcuts()
+        .with_action(HELP, "show or hide this help", Key::F1)
+        .with_binding(HELP, Binding::shift(Key::Slash));
+    let path = Path::new(SHORTCUT_DIR).join(format!("{}.txt", title.replace(' ', "")));
+    if path.exists() {
+        if let Err(err) = shortcuts.load_config(&path) {
+            println!("Could not load shortcuts {}: {err}", path.display());
+        }
+    }
+    for (binding, actions) in shortcuts.conflicts() {
+        println!(
+            "{binding} is bound to {}, only {} is used",
+            actions.join(", "),
+            actions[0]
+        );
+    }
+    shortcuts
+}
@@ -0,0 +1,409 @@
+use crate::{colors::Rgba, image as draw_image, DrawingContext, Graphics};
+use image::RgbaImage;
+use math::vec2d::Vec2D;
+use piston_window::{G2dTexture, TextureSettings};
+use std::{io, path::Path};
+
+/// `src` drawn over `dst` with `coverage` (between 0 and 1) of it visible,
+/// the colors are mixed in sRGB like the GPU does
+fn blend(src: Rgba, dst: Rgba, coverage: f64) -> Rgba {
+    let src_a = src.a as f64 / 255.0 * coverage.clamp(0.0, 1.0);
+    let dst_a = dst.a as f64 / 255.0;
+    let out_a = src_a + dst_a * (1.0 - src_a);
+    if out_a <= 0.0 {
+        return Rgba::new(0, 0, 0, 0);
+    }
+    let mix = |src: u8, dst: u8| {
+        let val = (src as f64 * src_a + dst as f64 * dst_a * (1.0 - src_a)) / out_a;
+        val.round() as u8
+    };
+    Rgba::new(
+        mix(src.r, dst.r),
+        mix(src.g, dst.g),
+        mix(src.b, dst.b),
+        (out_a * 255.0).round() as u8,
+    )
+}
+
+/// An image that is drawn on by the CPU, shapes are clipped to its bounds.
+/// Pixel `(x, y)` covers the square from `(x, y)` to `(x + 1, y + 1)`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Canvas {
+    image: RgbaImage,
+}
+
+impl Canvas {
+    /// Fully transparent
+    pub fn new(width: u32, height: u32) -> Canvas {
+        Canvas {
+            image: RgbaImage::new(width, height),
+        }
+    }
+
+    pub fn from_image(image: RgbaImage) -> Canvas {
+        Canvas { image }
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Canvas> {
+        let image = image::open(path).map_err(io::Error::other)?;
+        Ok(Canvas::from_image(image.into_rgba8()))
+    }
+
+    /// The format is picked from the extension by the image crate
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        self.image.save(path).map_err(io::Error::other)
+    }
+
+    pub fn image(&self) -> &RgbaImage {
+        &self.image
+    }
+
+    pub fn image_mut(&mut self) -> &mut RgbaImage {
+        &mut self.image
+    }
+
+    pub fn into_image(self) -> RgbaImage {
+        self.image
+    }
+
+    pub fn width(&self) -> u32 {
+        self.image.width()
+    }
+
+    pub fn height(&self) -> u32 {
+        self.image.height()
+    }
+
+    /// Starts over with a transparent canvas of the new size
+    pub fn resize(&mut self, width: u32, height: u32) {
+        self.image = RgbaImage::new(width, height);
+    }
+
+    /// `None` outside of the canvas
+    pub fn get(&self, x: i64, y: i64) -> Option<Rgba> {
+        if !self.contains(x, y) {
+            return None;
+        }
+        Some((*self.image.get_pixel(x as u32, y as u32)).into())
+    }
+
+    fn contains(&self, x: i64, y: i64) -> bool {
+        x >= 0 && y >= 0 && x < self.width() as i64 && y < self.height() as i64
+    }
+
+    /// Replaces the pixel, transparency included
+    pub fn set(&mut self, x: i64, y: i64, color: Rgba) {
+        if self.contains(x, y) {
+            self.image.put_pixel(x as u32, y as u32, color.into());
+        }
+    }
+
+    /// Draws the color over the pixel, `coverage` scales its opacity
+    pub fn blend(&mut self, x: i64, y: i64, color: Rgba, coverage: f64) {
+        if let Some(dst) = self.get(x, y) {
+            self.set(x, y, blend(color, dst, coverage));
+        }
+    }
+
+    pub fn clear(&mut self, color: Rgba) {
+        for pixel in self.image.pixels_mut() {
+            *pixel = color.into();
+        }
+    }
+
+    /// Draws `color` with the given opacity over the whole canvas,
+    /// old strokes fade away when this is done every frame
+    pub fn fade(&mut self, color: Rgba, amount: f64) {
+        for pixel in self.image.pixels_mut() {
+            *pixel = blend(color, (*pixel).into(), amount).into();
+        }
+    }
+
+    /// Anti-aliased line one pixel wide with Xiaolin Wu's algorithm,
+    /// nothing is drawn if an end point is not finite
+    pub fn line(&mut self, from: Vec2D, to: Vec2D, color: Rgba) {
+        if ![from.x, from.y, to.x, to.y].iter().all(|v| v.is_finite()) {
+            return;
+        }
+        // shifted so the pixel centers are at whole coordinates
+        let (mut x0, mut y0) = (from.x - 0.5, from.y - 0.5);
+        let (mut x1, mut y1) = (to.x - 0.5, to.y - 0.5);
+        let steep = (y1 - y0).abs() > (x1 - x0).abs();
+        if steep {
+            (x0, y0, x1, y1) = (y0, x0, y1, x1);
+        }
+        if x0 > x1 {
+            (x0, y0, x1, y1) = (x1, y1, x0, y0);
+        }
+        // length of the canvas along the axis the line is walked on
+        let len = if steep { self.height() } else { self.width() } as f64;
+        let mut plot = |x: f64, y: f64, coverage: f64| {
+            let (x, y) = if steep { (y, x) } else { (x, y) };
+            self.blend(x as i64, y as i64, color, coverage);
+        };
+
+        // unlike `f64::fract` also in 0..1 for negative values
+        let fract = |v: f64| v - v.floor();
+        let dx = x1 - x0;
+        let gradient = if dx == 0.0 { 1.0 } else { (y1 - y0) / dx };
+        // the end points cover their pixels by how far the line reaches into them
+        let mut end_point = |x: f64, y: f64, reach: f64| {
+            let x_end = x.round();
+            let y_end = y + gradient * (x_end - x);
+            plot(x_end, y_end.floor(), (1.0 - fract(y_end)) * reach);
+            plot(x_end, y_end.floor() + 1.0, fract(y_end) * reach);
+            (x_end, y_end)
+        };
+        let (x_start, y_start) = end_point(x0, y0, 1.0 - fract(x0 + 0.5));
+        let (x_stop, _) = end_point(x1, y1, fract(x1 + 0.5));
+        if x_stop == x_start {
+            return;
+        }
+
+        // only the part of the line over the canvas is walked
+        let mut x = (x_start + 1.0).max(0.0);
+        let x_stop = x_stop.min(len);
+        let mut y = y_start + gradient * (x - x_start);
+        while x < x_stop {
+            plot(x, y.floor(), 1.0 - fract(y));
+            plot(x, y.floor() + 1.0, fract(y));
+            y += gradient;
+            x += 1.0;
+        }
+    }
+
+    /// First and last row or column with its center in `start..end`, clipped to `0..len`
+    fn centers(start: f64, end: f64, len: u32) -> (i64, i64) {
+        let first = (start - 0.5).ceil().max(0.0) as i64;
+        let last = ((end - 0.5).ceil() as i64 - 1).min(len as i64 - 1);
+        (first, last)
+    }
+
+    /// Fills the pixels of row `y` that have their center in `x_start..x_end`,
+    /// so shapes sharing an edge don't both cover the pixels on it
+    fn span(&mut self, y: i64, x_start: f64, x_end: f64, color: Rgba) {
+        let (first, last) = Canvas::centers(x_start, x_end, self.width());
+        for x in first..=last {
+            self.blend(x, y, color, 1.0);
+        }
+    }
+
+    /// Fills every pixel with its center inside the circle
+    pub fn fill_circle(&mut self, center: Vec2D, radius: f64, color: Rgba) {
+        let (first, last) = Canvas::centers(center.y - radius, center.y + radius, self.height());
+        for y in first..=last {
+            let dy = y as f64 + 0.5 - center.y;
+            let half = (radius * radius - dy * dy).max(0.0).sqrt();
+            self.span(y, center.x - half, center.x + half, color);
+        }
+    }
+
+    /// Fills every pixel with its center inside the polygon by the even-odd rule,
+    /// so self intersecting and concave polygons work too
+    pub fn fill_polygon(&mut self, points: &[Vec2D], color: Rgba) {
+        if points.len() < 3 {
+            return;
+        }
+        let (min_y, max_y) = points
+            .iter()
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), p| {
+                (min.min(p.y), max.max(p.y))
+            });
+        let (first, last) = Canvas::centers(min_y, max_y, self.height());
+        let mut crossings = vec![];
+        for y in first..=last {
+            let center_y = y as f64 + 0.5;
+            crossings.clear();
+            for (ind, start) in points.iter().enumerate() {
+                let end = points[(ind + 1) % points.len()];
+                // half open so vertices on the scanline are counted once
+                if (start.y <= center_y) != (end.y <= center_y) {
+                    let t = (center_y - start.y) / (end.y - start.y);
+                    crossings.push(start.x + t * (end.x - start.x));
+                }
+            }
+            crossings.sort_by(f64::total_cmp);
+            for pair in crossings.chunks_exact(2) {
+                self.span(y, pair[0], pair[1], color);
+            }
+        }
+    }
+
+    /// Replaces the pixels below `src` with its top left corner at `(x, y)`
+    pub fn copy(&mut self, src: &Canvas, x: i64, y: i64) {
+        for (src_x, src_y, pixel) in src.image.enumerate_pixels() {
+            self.set(x + src_x as i64, y + src_y as i64, (*pixel).into());
+        }
+    }
+
+    /// Draws `src` over the canvas with its top left corner at `(x, y)`
+    pub fn blit(&mut self, src: &Canvas, x: i64, y: i64) {
+        for (src_x, src_y, pixel) in src.image.enumerate_pixels() {
+            self.blend(x + src_x as i64, y + src_y as i64, (*pixel).into(), 1.0);
+        }
+    }
+
+    /// Uploads the canvas into a new texture and draws it with its top left corner at the origin
+    pub fn draw(&self, transform: [[f64; 3]; 2], ctx: &mut DrawingContext, gl: &mut Graphics) {
+        let texture =
+            G2dTexture::from_image(ctx.texture_context, &self.image, &TextureSettings::new())
+                .unwrap();
+        draw_image(&texture, transform, gl);
+    }
+}
+
+#[cfg(test)]
+mod canvas_tests {
+    use super::{blend, Canvas};
+    use crate::colors::{rgba, Rgba};
+    use math::vec2d::Vec2D;
+
+    fn count(canvas: &Canvas, pred: impl Fn(Rgba) -> bool) -> usize {
+        canvas
+            .image()
+            .pixels()
+            .filter(|pixel| pred((**pixel).into()))
+            .count()
+    }
+
+    #[test]
+    fn blending() {
+        assert_eq!(blend(rgba::RED, rgba::BLUE, 1.0), rgba::RED);
+        assert_eq!(blend(rgba::RED, rgba::BLUE, 0.0), rgba::BLUE);
+        assert_eq!(
+            blend(rgba::WHITE, rgba::BLACK, 0.5),
+            Rgba::new(128, 128, 128, 255)
+        );
+        let transparent = Rgba::new(0, 0, 0, 0);
+        assert_eq!(
+            blend(rgba::RED.with_trans(51), transparent, 1.0),
+            rgba::RED.with_trans(51)
+        );
+        assert_eq!(blend(transparent, transparent, 1.0), transparent);
+
+        let mut canvas = Canvas::new(2, 1);
+        canvas.clear(rgba::BLACK);
+        canvas.fade(rgba::WHITE, 0.25);
+        assert_eq!(canvas.get(1, 0), Some(Rgba::new(64, 64, 64, 255)));
+        assert_eq!(canvas.get(2, 0), None);
+        canvas.set(-1, 0, rgba::RED);
+    }
+
+    #[test]
+    fn lines() {
+        let mut canvas = Canvas::new(10, 10);
+        // through the centers of row 2, every pixel fully covered
+        canvas.line(Vec2D::new(1.0, 2.5), Vec2D::new(9.0, 2.5), rgba::WHITE);
+        assert_eq!(count(&canvas, |c| c == rgba::WHITE), 8);
+        assert_eq!(count(&canvas, |c| c.a > 0), 8);
+
+        // between two rows both get half of it
+        let mut canvas = Canvas::new(10, 10);
+        canvas.line(Vec2D::new(0.5, 3.0), Vec2D::new(9.5, 3.0), rgba::WHITE);
+        assert_eq!(canvas.get(4, 2).unwrap().a, 128);
+        assert_eq!(canvas.get(4, 3).unwrap().a, 128);
+
+        // steep lines are drawn by rows, one or two pixels per row
+        let mut canvas = Canvas::new(10, 10);
+        canvas.line(Vec2D::new(2.0, 0.5), Vec2D::new(5.0, 9.5), rgba::WHITE);
+        for y in 0..10 {
+            let row = (0..10).filter(|x| canvas.get(*x, y).unwrap().a > 0).count();
+            assert!((1..=2).contains(&row), "row {y} has {row} pixels");
+        }
+
+        // clipped at the border
+        canvas.line(Vec2D::new(-20.0, -5.0), Vec2D::new(30.0, 20.0), rgba::WHITE);
+        canvas.line(Vec2D::new(3.0, 3.0), Vec2D::new(3.0, 3.0), rgba::WHITE);
+    }
+
+    #[test]
+    fn lines_near_the_top() {
+        // a quarter of the line is above the canvas
+        let mut canvas = Canvas::new(10, 10);
+        canvas.line(Vec2D::new(0.5, 0.25), Vec2D::new(9.5, 0.25), rgba::WHITE);
+        assert_eq!(canvas.get(4, 0).unwrap().a, 191);
+        assert_eq!(count(&canvas, |c| c.a > 0), 10);
+
+        let mut canvas = Canvas::new(10, 10);
+        canvas.line(Vec2D::new(0.25, 0.5), Vec2D::new(0.25, 9.5), rgba::WHITE);
+        assert_eq!(canvas.get(0, 4).unwrap().a, 191);
+    }
+
+    #[test]
+    fn lines_off_the_canvas() {
+        let mut canvas = Canvas::new(10, 10);
+        canvas.line(Vec2D::new(-1e15, 5.5), Vec2D::new(1e15, 5.5), rgba::WHITE);
+        assert_eq!(count(&canvas, |c| c == rgba::WHITE), 10);
+        assert_eq!(count(&canvas, |c| c.a > 0), 10);
+
+        let mut canvas = Canvas::new(10, 10);
+        canvas.line(Vec2D::new(1e15, 2.0), Vec2D::new(2e15, 3.0), rgba::WHITE);
+        canvas.line(
+            Vec2D::new(2.0, 2.0),
+            Vec2D::new(f64::INFINITY, 3.0),
+            rgba::WHITE,
+        );
+        canvas.line(Vec2D::new(f64::NAN, 2.0), Vec2D::new(3.0, 3.0), rgba::WHITE);
+        canvas.line(
+            Vec2D::new(2.0, f64::NEG_INFINITY),
+            Vec2D::new(3.0, 3.0),
+            rgba::WHITE,
+        );
+        assert_eq!(count(&canvas, |c| c.a > 0), 0);
+    }
+
+    #[test]
+    fn circles() {
+        let mut canvas = Canvas::new(40, 40);
+        canvas.fill_circle(Vec2D::new(20.0, 20.0), 10.0, rgba::RED);
+        let area = count(&canvas, |c| c == rgba::RED) as f64;
+        assert!((area - std::f64::consts::PI * 100.0).abs() < 10.0, "{area}");
+        assert_eq!(canvas.get(20, 20), Some(rgba::RED));
+        assert_eq!(canvas.get(20, 9).unwrap().a, 0);
+
+        canvas.fill_circle(Vec2D::new(0.0, 0.0), 5.0, rgba::BLUE);
+        assert_eq!(canvas.get(0, 0), Some(rgba::BLUE));
+    }
+
+    #[test]
+    fn polygons() {
+        let mut canvas = Canvas::new(20, 20);
+        let square = [
+            Vec2D::new(2.0, 2.0),
+            Vec2D::new(12.0, 2.0),
+            Vec2D::new(12.0, 12.0),
+            Vec2D::new(2.0, 12.0),
+        ];
+        canvas.fill_polygon(&square, rgba::GREEN);
+        assert_eq!(count(&canvas, |c| c == rgba::GREEN), 100);
+
+        // a bow tie crossing itself at (10, 10)
+        let mut canvas = Canvas::new(20, 20);
+        let bow_tie = [
+            Vec2D::new(0.0, 0.0),
+            Vec2D::new(20.0, 20.0),
+            Vec2D::new(20.0, 0.0),
+            Vec2D::new(0.0, 20.0),
+        ];
+        canvas.fill_polygon(&bow_tie, rgba::GREEN);
+        assert_eq!(canvas.get(2, 10), Some(rgba::GREEN));
+        assert_eq!(canvas.get(10, 2).unwrap().a, 0);
+        assert_eq!(count(&canvas, |c| c == rgba::GREEN), 200);
+    }
+
+    #[test]
+    fn copying() {
+        let mut src = Canvas::new(2, 2);
+        src.clear(rgba::RED.with_trans(0));
+        src.set(0, 0, rgba::RED);
+        let mut canvas = Canvas::new(4, 4);
+        canvas.clear(rgba::BLUE);
+        canvas.blit(&src, 3, 3);
+        assert_eq!(canvas.get(3, 3), Some(rgba::RED));
+        canvas.blit(&src, 0, 0);
+        assert_eq!(canvas.get(1, 1), Some(rgba::BLUE));
+        canvas.copy(&src, 0, 0);
+        assert_eq!(canvas.get(1, 1).unwrap().a, 0);
+        assert_eq!(canvas.get(0, 0), Some(rgba::RED));
+    }
+}
@@ -0,0 +1,467 @@
+use super::{hsv::Hsv, rgba::Rgba};
+use std::{
+    f64::consts::PI,
+    fs::File,
+    io::{self, Read},
+    path::Path,
+};
+
+/// Color space in which a gradient interpolates between its stops
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Blend {
+    /// Gamma encoded components, like `Rgba::lerp`
+    #[default]
+    Srgb,
+    Linear,
+    Oklab,
+}
+
+impl Blend {
+    fn mix(&self, from: Rgba, to: Rgba, t: f64) -> Rgba {
+        match self {
+            Blend::Srgb => from.lerp(to, t),
+            Blend::Linear => from.lerp_linear(to, t),
+            Blend::Oklab => from.lerp_oklab(to, t),
+        }
+    }
+}
+
+/// Colors at positions between 0 and 1, `map` blends between the two stops around a value
+#[derive(Debug, Clone, PartialEq)]
+pub struct Gradient {
+    /// sorted by position
+    stops: Vec<(f64, Rgba)>,
+    blend: Blend,
+    /// cyclic gradients repeat outside of 0 to 1 and blend from the last stop to the first
+    cyclic: bool,
+}
+
+impl Gradient {
+    pub fn new(mut stops: Vec<(f64, Rgba)>) -> Gradient {
+        assert!(!stops.is_empty(), "gradient without stops");
+        stops.sort_by(|(pos1, _), (pos2, _)| pos1.total_cmp(pos2));
+        Gradient {
+            stops,
+            blend: Blend::default(),
+            cyclic: false,
+        }
+    }
+
+    /// Stops evenly spaced from 0 to 1
+    pub fn even(colors: &[Rgba]) -> Gradient {
+        let last = (colors.len().max(2) - 1) as f64;
+        Gradient::new(
+            colors
+                .iter()
+                .enumerate()
+                .map(|(ind, color)| (ind as f64 / last, *color))
+                .collect(),
+        )
+    }
+
+    /// `num_stops` evenly spaced samples of `fun` between 0 and 1
+    pub fn from_fn<F: Fn(f64) -> Rgba>(fun: F, num_stops: usize) -> Gradient {
+        let last = (num_stops.max(2) - 1) as f64;
+        Gradient::new(
+            (0..num_stops)
+                .map(|ind| ind as f64 / last)
+                .map(|pos| (pos, fun(pos)))
+                .collect(),
+        )
+    }
+
+    /// Parses one color per line as hex (`#ff8800`), optionally preceded by its position.
+    /// Without positions the colors are spread evenly, empty lines and lines starting
+    /// with `//` are skipped
+    pub fn read_text<R: Read>(mut reader: R) -> io::Result<Gradient> {
+        let mut contents = String::new();
+        reader.read_to_string(&mut contents)?;
+        let mut stops = vec![];
+        for line in contents.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with("//") {
+                continue;
+            }
+            let tokens: Vec<&str> = line.split_whitespace().collect();
+            let (pos, hex) = match tokens[..] {
+                [hex] => (None, hex),
+                [pos, hex] => (
+                    Some(
+                        pos.parse::<f64>()
+                            .map_err(|_| invalid_data(format!("invalid position {pos:?}")))?,
+                    ),
+                    hex,
+                ),
+                _ => return Err(invalid_data(format!("invalid stop {line:?}"))),
+            };
+            let color = Rgba::from_hex(hex)
+                .ok_or_else(|| invalid_data(format!("invalid color {hex:?}")))?;
+            stops.push((pos, color));
+        }
+
+        if stops.is_empty() {
+            return Err(invalid_data("no colors".to_owned()));
+        }
+        if stops.iter().all(|(pos, _)| pos.is_none()) {
+            let colors: Vec<Rgba> = stops.into_iter().map(|(_, color)| color).collect();
+            return Ok(Gradient::even(&colors));
+        }
+        stops
+            .into_iter()
+            .map(|(pos, color)| {
+                pos.map(|pos| (pos, color))
+                    .ok_or_else(|| invalid_data("stops without position".to_owned()))
+            })
+            .collect::<io::Result<Vec<_>>>()
+            .map(Gradient::new)
+    }
+
+    /// Reads a GIMP palette, the colors are spread evenly in the order of the file
+    pub fn read_gpl<R: Read>(mut reader: R) -> io::Result<Gradient> {
+        let mut contents = String::new();
+        reader.read_to_string(&mut contents)?;
+        let mut lines = contents.lines().map(str::trim);
+        if lines.next() != Some("GIMP Palette") {
+            return Err(invalid_data("missing GIMP Palette header".to_owned()));
+        }
+
+        let mut colors = vec![];
+        for line in lines {
+            if line.is_empty()
+                || line.starts_with('#')
+                || line.starts_with("Name:")
+                || line.starts_with("Columns:")
+            {
+                continue;
+            }
+            let channels = line
+                .split_whitespace()
+                .take(3)
+                .map(|channel| channel.parse::<u8>())
+                .collect::<Result<Vec<u8>, _>>();
+            match channels.as_deref() {
+                Ok([r, g, b]) => colors.push(Rgba::new(*r, *g, *b, 255)),
+                _ => return Err(invalid_data(format!("invalid color {line:?}"))),
+            }
+        }
+
+        if colors.is_empty() {
+            return Err(invalid_data("no colors".to_owned()));
+        }
+        Ok(Gradient::even(&colors))
+    }
+
+    /// `.gpl` files are read with `read_gpl`, everything else with `read_text`
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Gradient> {
+        let path = path.as_ref();
+        let file = File::open(path)?;
+        if path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("gpl"))
+        {
+            Gradient::read_gpl(file)
+        } else {
+            Gradient::read_text(file)
+        }
+    }
+
+    pub fn with_blend(self, blend: Blend) -> Gradient {
+        Gradient { blend, ..self }
+    }
+
+    pub fn with_cyclic(self, cyclic: bool) -> Gradient {
+        Gradient { cyclic, ..self }
+    }
+
+    pub fn stops(&self) -> &[(f64, Rgba)] {
+        &self.stops
+    }
+
+    pub fn is_cyclic(&self) -> bool {
+        self.cyclic
+    }
+
+    pub fn reversed(&self) -> Gradient {
+        let stops = self
+            .stops
+            .iter()
+            .map(|(pos, color)| (1.0 - pos, *color))
+            .collect();
+        Gradient::new(stops)
+            .with_blend(self.blend)
+            .with_cyclic(self.cyclic)
+    }
+
+    /// Color at `t`, values outside of the stops get the color of the closest stop
+    /// unless the gradient is cyclic. NaN maps to the start
+    pub fn map(&self, t: f64) -> Rgba {
+        let t = if t.is_nan() { 0.0 } else { t };
+        let (first, last) = (self.stops[0], self.stops[self.stops.len() - 1]);
+        if self.cyclic {
+            let t = t.rem_euclid(1.0);
+            // the wrapping segment from the last stop to the first one
+            let (before, after) = if t < first.0 {
+                ((last.0 - 1.0, last.1), first)
+            } else if t >= last.0 {
+                (last, (first.0 + 1.0, first.1))
+            } else {
+                self.segment(t)
+            };
+            return self.mix(before, after, t);
+        }
+
+        if t <= first.0 {
+            return first.1;
+        }
+        if t >= last.0 {
+            return last.1;
+        }
+        let (before, after) = self.segment(t);
+        self.mix(before, after, t)
+    }
+
+    /// `num` colors evenly spaced along the gradient, for discrete palettes.
+    /// The samples of cyclic gradients do not repeat the start at the end
+    pub fn colors(&self, num: usize) -> Vec<Rgba> {
+        let steps = if self.cyclic { num } else { num.max(2) - 1 };
+        (0..num)
+            .map(|ind| self.map(ind as f64 / steps as f64))
+            .collect()
+    }
+
+    /// The stops around `t`, which lies within the range of the stops
+    fn segment(&self, t: f64) -> ((f64, Rgba), (f64, Rgba)) {
+        let after = self
+            .stops
+            .partition_point(|(pos, _)| *pos <= t)
+            .clamp(1, self.stops.len() - 1);
+        (self.stops[after - 1], self.stops[after])
+    }
+
+    fn mix(&self, (pos1, col1): (f64, Rgba), (pos2, col2): (f64, Rgba), t: f64) -> Rgba {
+        if pos2 <= pos1 {
+            return col2;
+        }
+        self.blend.mix(col1, col2, (t - pos1) / (pos2 - pos1))
+    }
+}
+
+fn invalid_data(msg: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+const VIRIDIS: [&str; 11] = [
+    "#440154", "#482475", "#414487", "#355f8d", "#2a788e", "#21918c", "#22a884", "#44bf70",
+    "#7ad151", "#bddf26", "#fde725",
+];
+const MAGMA: [&str; 11] = [
+    "#000004", "#140e36", "#3b0f70", "#641a80", "#8c2981", "#b73779", "#de4968", "#f7705c",
+    "#fe9f6d", "#fecf92", "#fcfdbf",
+];
+const INFERNO: [&str; 11] = [
+    "#000004", "#160b39", "#420a68", "#6a176e", "#932667", "#bc3754", "#dd513a", "#f37819",
+    "#fca50a", "#f6d746", "#fcffa4",
+];
+
+fn from_hex(stops: &[&str]) -> Gradient {
+    let colors: Vec<Rgba> = stops
+        .iter()
+        .map(|hex| Rgba::from_hex(hex).unwrap())
+        .collect();
+    Gradient::even(&colors)
+}
+
+fn from_unit([r, g, b]: [f64; 3]) -> Rgba {
+    let byte = |c: f64| (c.clamp(0.0, 1.0) * 255.0).round() as u8;
+    Rgba::new(byte(r), byte(g), byte(b), 255)
+}
+
+/// Polynomial approximation of Google's Turbo by Ruofei Du
+fn turbo(t: f64) -> Rgba {
+    let poly = |c: [f64; 6]| c[0] + t * (c[1] + t * (c[2] + t * (c[3] + t * (c[4] + t * c[5]))));
+    from_unit([
+        poly([
+            0.13572138,
+            4.61539260,
+            -42.66032258,
+            132.13108234,
+            -152.94239396,
+            59.28637943,
+        ]),
+        poly([
+            0.09140261,
+            2.19418839,
+            4.84296658,
+            -14.18503333,
+            4.27729857,
+            2.82956604,
+        ]),
+        poly([
+            0.10667330,
+            12.64194608,
+            -60.58204836,
+            110.36276771,
+            -89.90310912,
+            27.34824973,
+        ]),
+    ])
+}
+
+/// Rainbow of shifted squared sines by Jim Bumgardner, evenly bright unlike the hsv wheel
+fn sinebow(t: f64) -> Rgba {
+    let channel = |offset: f64| (PI * (0.5 - t + offset)).sin().powi(2);
+    from_unit([channel(0.0), channel(1.0 / 3.0), channel(2.0 / 3.0)])
+}
+
+/// Built-in gradients for scalar fields
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Colormap {
+    #[default]
+    Grayscale,
+    /// black over red and yellow to white
+    Heat,
+    Viridis,
+    Magma,
+    Inferno,
+    Turbo,
+    /// cyclic hsv color wheel
+    Rainbow,
+    /// cyclic
+    Sinebow,
+}
+
+impl Colormap {
+    pub const ALL: [Colormap; 8] = [
+        Colormap::Grayscale,
+        Colormap::Heat,
+        Colormap::Viridis,
+        Colormap::Magma,
+        Colormap::Inferno,
+        Colormap::Turbo,
+        Colormap::Rainbow,
+        Colormap::Sinebow,
+    ];
+
+    pub fn gradient(&self) -> Gradient {
+        match self {
+            Colormap::Grayscale => {
+                Gradient::even(&[Rgba::new(0, 0, 0, 255), Rgba::new(255, 255, 255, 255)])
+            }
+            Colormap::Heat => from_hex(&["#000000", "#ff0000", "#ffff00", "#ffffff"]),
+            Colormap::Viridis => from_hex(&VIRIDIS),
+            Colormap::Magma => from_hex(&MAGMA),
+            Colormap::Inferno => from_hex(&INFERNO),
+            Colormap::Turbo => Gradient::from_fn(turbo, 33),
+            Colormap::Rainbow => {
+                Gradient::from_fn(|t| Hsv::new(360.0 * t, 1.0, 1.0).unwrap().into(), 7)
+                    .with_cyclic(true)
+            }
+            Colormap::Sinebow => Gradient::from_fn(sinebow, 25).with_cyclic(true),
+        }
+    }
+
+    /// The colormap after this one in `ALL`, for switching with a key
+    pub fn next(&self) -> Colormap {
+        let ind = Colormap::ALL.iter().position(|map| map == self).unwrap();
+        Colormap::ALL[(ind + 1) % Colormap::ALL.len()]
+    }
+}
+
+#[cfg(test)]
+mod gradient_tests {
+    use super::{Blend, Colormap, Gradient};
+    use crate::colors::{rgba, Rgba};
+
+    fn black_white() -> Gradient {
+        Gradient::even(&[rgba::BLACK, rgba::WHITE])
+    }
+
+    #[test]
+    fn map() {
+        let gradient = Gradient::new(vec![
+            (1.0, rgba::BLUE),
+            (0.0, rgba::BLACK),
+            (0.5, rgba::RED),
+        ]);
+        assert_eq!(gradient.map(-1.0), rgba::BLACK);
+        assert_eq!(gradient.map(0.25), Rgba::new(128, 0, 0, 255));
+        assert_eq!(gradient.map(0.5), rgba::RED);
+        assert_eq!(gradient.map(0.75), Rgba::new(128, 0, 128, 255));
+        assert_eq!(gradient.map(2.0), rgba::BLUE);
+        assert_eq!(gradient.map(f64::NAN), rgba::BLACK);
+        assert_eq!(gradient.reversed().map(0.0), rgba::BLUE);
+
+        let single = Gradient::new(vec![(0.3, rgba::GREEN)]);
+        assert_eq!(single.map(0.0), rgba::GREEN);
+        assert_eq!(single.map(1.0), rgba::GREEN)
+    }
+
+    #[test]
+    fn blends() {
+        let gray = |gradient: Gradient| gradient.map(0.5).r;
+        assert_eq!(gray(black_white()), 128);
+        assert_eq!(gray(black_white().with_blend(Blend::Linear)), 188);
+        assert_eq!(gray(black_white().with_blend(Blend::Oklab)), 99)
+    }
+
+    #[test]
+    fn cyclic() {
+        let gradient =
+            Gradient::new(vec![(0.25, rgba::BLACK), (0.75, rgba::WHITE)]).with_cyclic(true);
+        // halfway around the wrap from 0.75 to 1.25
+        assert_eq!(gradient.map(0.0), Rgba::new(128, 128, 128, 255));
+        assert_eq!(gradient.map(1.0), gradient.map(0.0));
+        assert_eq!(gradient.map(-0.75), rgba::BLACK);
+        assert_eq!(gradient.colors(4)[1], rgba::BLACK);
+
+        let rainbow = Colormap::Rainbow.gradient();
+        assert_eq!(rainbow.map(0.0), rainbow.map(1.0));
+        assert_eq!(rainbow.map(1.0 / 3.0), rgba::GREEN)
+    }
+
+    #[test]
+    fn colormaps() {
+        let mut map = Colormap::default();
+        for _ in 0..Colormap::ALL.len() {
+            let gradient = map.gradient();
+            assert!(gradient.stops().len() >= 2);
+            assert!(gradient.stops().windows(2).all(|w| w[0].0 <= w[1].0));
+            map = map.next();
+        }
+        assert_eq!(map, Colormap::default());
+        assert_eq!(
+            Colormap::Viridis.gradient().map(0.0),
+            Rgba::new(68, 1, 84, 255)
+        );
+        assert_eq!(
+            Colormap::Turbo.gradient().map(0.0),
+            Rgba::new(35, 23, 27, 255)
+        );
+        assert_eq!(
+            Colormap::Sinebow.gradient().map(0.0),
+            Rgba::new(255, 64, 64, 255)
+        )
+    }
+
+    #[test]
+    fn parse_text() {
+        let even = Gradient::read_text("// comment\n#000000\n\nffffff\n".as_bytes()).unwrap();
+        assert_eq!(even, black_white());
+        let positioned = Gradient::read_text("0.75 #ffffff\n0 #000000".as_bytes()).unwrap();
+        assert_eq!(positioned.stops()[1], (0.75, rgba::WHITE));
+
+        assert!(Gradient::read_text("".as_bytes()).is_err());
+        assert!(Gradient::read_text("#00000g".as_bytes()).is_err());
+        assert!(Gradient::read_text("0.5 #000000\n#ffffff".as_bytes()).is_err());
+        assert!(Gradient::read_text("x #000000".as_bytes()).is_err())
+    }
+
+    #[test]
+    fn parse_gpl() {
+        let gpl =
+            "GIMP Palette\nName: test\nColumns: 2\n#\n  0   0   0 Black\n255 255 255\tWhite\n";
+        assert_eq!(Gradient::read_gpl(gpl.as_bytes()).unwrap(), black_white());
+        assert!(Gradient::read_gpl("0 0 0".as_bytes()).is_err());
+        assert!(Gradient::read_gpl("GIMP Palette\n0 0".as_bytes()).is_err());
+        assert!(Gradient::read_gpl("GIMP Palette\n".as_bytes()).is_err())
+    }
+}
@@ -0,0 +1,99 @@
+use super::{hue, rgba::Rgba, unit_to_byte};
+use std::fmt;
+
+/// HSL color
+/// 0<=h<=360
+/// 0<=s,l,alpha<=1
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Hsl {
+    pub(crate) h: f64,
+    pub(crate) s: f64,
+    pub(crate) l: f64,
+    pub(crate) alpha: f64,
+}
+
+impl Hsl {
+    pub fn new(h: f64, s: f64, l: f64) -> Option<Hsl> {
+        if !(0.0..=360.0).contains(&h) || !(0.0..=1.0).contains(&s) || !(0.0..=1.0).contains(&l) {
+            return None;
+        }
+        Some(Hsl {
+            h,
+            s,
+            l,
+            alpha: 1.0,
+        })
+    }
+
+    pub fn with_alpha(self, alpha: f64) -> Hsl {
+        Hsl {
+            alpha: alpha.clamp(0.0, 1.0),
+            ..self
+        }
+    }
+
+    pub fn from_rgba(rgba: Rgba) -> Hsl {
+        let r = rgba.r as f64 / 255.0;
+        let g = rgba.g as f64 / 255.0;
+        let b = rgba.b as f64 / 255.0;
+        let max = r.max(g).max(b);
+        let min = r.min(g).min(b);
+        let delta = max - min;
+
+        let l = (max + min) / 2.0;
+        let s = if delta == 0.0 {
+            0.0
+        } else {
+            delta / (1.0 - (2.0 * l - 1.0).abs())
+        };
+        Hsl {
+            h: hue(r, g, b, max, delta),
+            s,
+            l,
+            alpha: rgba.a as f64 / 255.0,
+        }
+    }
+
+    pub fn to_rgba(self) -> Rgba {
+        let chroma = (1.0 - (2.0 * self.l - 1.0).abs()) * self.s;
+        let sector = (self.h % 360.0) / 60.0;
+        let x = chroma * (1.0 - (sector % 2.0 - 1.0).abs());
+        let (r, g, b) = match sector as u8 {
+            0 => (chroma, x, 0.0),
+            1 => (x, chroma, 0.0),
+            2 => (0.0, chroma, x),
+            3 => (0.0, x, chroma),
+            4 => (x, 0.0, chroma),
+            _ => (chroma, 0.0, x),
+        };
+        let m = self.l - chroma / 2.0;
+        Rgba::new(
+            unit_to_byte(r + m),
+            unit_to_byte(g + m),
+            unit_to_byte(b + m),
+            unit_to_byte(self.alpha),
+        )
+    }
+
+    pub fn increase_hue(&mut self, amount: f64) {
+        self.h = (self.h + amount).rem_euclid(360.0);
+    }
+}
+
+impl From<Rgba> for Hsl {
+    fn from(rgba: Rgba) -> Hsl {
+        Hsl::from_rgba(rgba)
+    }
+}
+
+impl From<Hsl> for Rgba {
+    fn from(hsl: Hsl) -> Rgba {
+        hsl.to_rgba()
+    }
+}
+
+impl fmt::Display for Hsl {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({},{},{})", self.h, self.s, self.l)
+    }
+}
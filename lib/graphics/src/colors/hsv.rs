@@ -1,4 +1,4 @@
-use super::rgba::Rgba;
+use super::{hue, rgba::Rgba};
 use std::fmt;
 
 /// HSV color
@@ -13,7 +13,7 @@ pub struct Hsv {
 
 impl Hsv {
     pub fn new(h: f64, s: f64, v: f64) -> Option<Hsv> {
-        if h < 0.0 || h > 360.0 || s < 0.0 || s > 1.0 || v < 0.0 || v > 1.0 {
+        if !(0.0..=360.0).contains(&h) || !(0.0..=1.0).contains(&s) || !(0.0..=1.0).contains(&v) {
             return None;
         }
         Some(Hsv { h, s, v })
@@ -27,23 +27,9 @@ impl Hsv {
         let c_min = r_prime.min(g_prime).min(b_prime);
         let delta = c_max - c_min;
 
-        let mut h = 60.0
-            * if c_max == r_prime {
-                (g_prime - b_prime) / delta
-            } else if c_max == g_prime {
-                (b_prime - r_prime) / delta
-            } else if c_max == b_prime {
-                (r_prime - g_prime) / delta
-            } else {
-                panic!("Invalid Rgb values")
-            };
-
-        if h < 0.0 {
-            h += 360.0;
-        }
-
+        let h = hue(r_prime, g_prime, b_prime, c_max, delta);
         let v = c_max;
-        let s = delta / c_max;
+        let s = if c_max == 0.0 { 0.0 } else { delta / c_max };
         Hsv { h, s, v }
     }
 
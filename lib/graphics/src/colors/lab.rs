@@ -0,0 +1,185 @@
+use super::{
+    lch::Lch,
+    lerp,
+    rgba::Rgba,
+    xyz::{Xyz, D65},
+};
+use std::fmt;
+
+const EPSILON: f64 = 6.0 / 29.0;
+
+fn f(t: f64) -> f64 {
+    if t > EPSILON.powi(3) {
+        t.cbrt()
+    } else {
+        t / (3.0 * EPSILON * EPSILON) + 4.0 / 29.0
+    }
+}
+
+fn f_inv(t: f64) -> f64 {
+    if t > EPSILON {
+        t.powi(3)
+    } else {
+        3.0 * EPSILON * EPSILON * (t - 4.0 / 29.0)
+    }
+}
+
+/// CIE L*a*b* color relative to D65,
+/// `l` goes from 0 (black) to 100 (white), `a` from green to red and `b` from blue to yellow
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Lab {
+    pub l: f64,
+    pub a: f64,
+    pub b: f64,
+    pub alpha: f64,
+}
+
+impl Lab {
+    pub fn new(l: f64, a: f64, b: f64) -> Lab {
+        Lab {
+            l,
+            a,
+            b,
+            alpha: 1.0,
+        }
+    }
+
+    pub fn with_alpha(self, alpha: f64) -> Lab {
+        Lab { alpha, ..self }
+    }
+
+    pub fn lerp(self, other: Lab, t: f64) -> Lab {
+        Lab {
+            l: lerp(self.l, other.l, t),
+            a: lerp(self.a, other.a, t),
+            b: lerp(self.b, other.b, t),
+            alpha: lerp(self.alpha, other.alpha, t),
+        }
+    }
+
+    /// CIE76 color difference, the euclidean distance in Lab.
+    /// Differences around 2.3 are just noticeable
+    pub fn delta_e76(&self, other: &Lab) -> f64 {
+        ((self.l - other.l).powi(2) + (self.a - other.a).powi(2) + (self.b - other.b).powi(2))
+            .sqrt()
+    }
+
+    /// CIEDE2000 color difference, corrects CIE76 for the lower sensitivity
+    /// to differences in saturated colors and for blue hues
+    pub fn delta_e2000(&self, other: &Lab) -> f64 {
+        let pow7 = |x: f64| x.powi(7);
+        let chroma_mean = (self.a.hypot(self.b) + other.a.hypot(other.b)) / 2.0;
+        let g = 0.5 * (1.0 - (pow7(chroma_mean) / (pow7(chroma_mean) + pow7(25.0))).sqrt());
+        let prime = |lab: &Lab| {
+            let a = (1.0 + g) * lab.a;
+            let chroma = a.hypot(lab.b);
+            let hue = if chroma == 0.0 {
+                0.0
+            } else {
+                lab.b.atan2(a).to_degrees().rem_euclid(360.0)
+            };
+            (chroma, hue)
+        };
+        let ((c1, h1), (c2, h2)) = (prime(self), prime(other));
+
+        let delta_l = other.l - self.l;
+        let delta_c = c2 - c1;
+        let delta_h = if c1 * c2 == 0.0 {
+            0.0
+        } else {
+            let diff = h2 - h1;
+            if diff > 180.0 {
+                diff - 360.0
+            } else if diff < -180.0 {
+                diff + 360.0
+            } else {
+                diff
+            }
+        };
+        let delta_hue = 2.0 * (c1 * c2).sqrt() * (delta_h / 2.0).to_radians().sin();
+
+        let l_mean = (self.l + other.l) / 2.0;
+        let c_mean = (c1 + c2) / 2.0;
+        let h_mean = if c1 * c2 == 0.0 {
+            h1 + h2
+        } else if (h1 - h2).abs() <= 180.0 {
+            (h1 + h2) / 2.0
+        } else if h1 + h2 < 360.0 {
+            (h1 + h2 + 360.0) / 2.0
+        } else {
+            (h1 + h2 - 360.0) / 2.0
+        };
+
+        let cos = |deg: f64| deg.to_radians().cos();
+        let t = 1.0 - 0.17 * cos(h_mean - 30.0)
+            + 0.24 * cos(2.0 * h_mean)
+            + 0.32 * cos(3.0 * h_mean + 6.0)
+            - 0.20 * cos(4.0 * h_mean - 63.0);
+        let delta_theta = 30.0 * (-((h_mean - 275.0) / 25.0).powi(2)).exp();
+        let r_c = 2.0 * (pow7(c_mean) / (pow7(c_mean) + pow7(25.0))).sqrt();
+        let s_l = 1.0 + 0.015 * (l_mean - 50.0).powi(2) / (20.0 + (l_mean - 50.0).powi(2)).sqrt();
+        let s_c = 1.0 + 0.045 * c_mean;
+        let s_h = 1.0 + 0.015 * c_mean * t;
+        let r_t = -(2.0 * delta_theta).to_radians().sin() * r_c;
+
+        let (l_term, c_term, h_term) = (delta_l / s_l, delta_c / s_c, delta_hue / s_h);
+        (l_term * l_term + c_term * c_term + h_term * h_term + r_t * c_term * h_term).sqrt()
+    }
+}
+
+impl From<Xyz> for Lab {
+    fn from(xyz: Xyz) -> Lab {
+        let fx = f(xyz.x / D65.x);
+        let fy = f(xyz.y / D65.y);
+        let fz = f(xyz.z / D65.z);
+        Lab {
+            l: 116.0 * fy - 16.0,
+            a: 500.0 * (fx - fy),
+            b: 200.0 * (fy - fz),
+            alpha: xyz.alpha,
+        }
+    }
+}
+
+impl From<Lab> for Xyz {
+    fn from(lab: Lab) -> Xyz {
+        let fy = (lab.l + 16.0) / 116.0;
+        Xyz {
+            x: D65.x * f_inv(fy + lab.a / 500.0),
+            y: D65.y * f_inv(fy),
+            z: D65.z * f_inv(fy - lab.b / 200.0),
+            alpha: lab.alpha,
+        }
+    }
+}
+
+impl From<Lch> for Lab {
+    fn from(lch: Lch) -> Lab {
+        let (a, b) = super::from_polar(lch.c, lch.h);
+        Lab {
+            l: lch.l,
+            a,
+            b,
+            alpha: lch.alpha,
+        }
+    }
+}
+
+impl From<Rgba> for Lab {
+    fn from(rgba: Rgba) -> Lab {
+        Xyz::from(rgba).into()
+    }
+}
+
+/// Colors out of the sRGB gamut are clipped
+impl From<Lab> for Rgba {
+    fn from(lab: Lab) -> Rgba {
+        Xyz::from(lab).into()
+    }
+}
+
+impl fmt::Display for Lab {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({},{},{},{})", self.l, self.a, self.b, self.alpha)
+    }
+}
@@ -0,0 +1,67 @@
+use super::{lab::Lab, lerp, lerp_hue, rgba::Rgba};
+use std::fmt;
+
+/// CIE LCh(ab), the polar form of `Lab`:
+/// lightness, chroma and the hue in degrees between 0 and 360
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Lch {
+    pub l: f64,
+    pub c: f64,
+    pub h: f64,
+    pub alpha: f64,
+}
+
+impl Lch {
+    pub fn new(l: f64, c: f64, h: f64) -> Lch {
+        Lch {
+            l,
+            c,
+            h: h.rem_euclid(360.0),
+            alpha: 1.0,
+        }
+    }
+
+    pub fn with_alpha(self, alpha: f64) -> Lch {
+        Lch { alpha, ..self }
+    }
+
+    /// Interpolates the hue along the shorter arc, see `Oklch::lerp`
+    pub fn lerp(self, other: Lch, t: f64) -> Lch {
+        Lch {
+            l: lerp(self.l, other.l, t),
+            c: lerp(self.c, other.c, t),
+            h: lerp_hue((self.c, self.h), (other.c, other.h), t),
+            alpha: lerp(self.alpha, other.alpha, t),
+        }
+    }
+}
+
+impl From<Lab> for Lch {
+    fn from(lab: Lab) -> Lch {
+        let (c, h) = super::to_polar(lab.a, lab.b);
+        Lch {
+            l: lab.l,
+            c,
+            h,
+            alpha: lab.alpha,
+        }
+    }
+}
+
+impl From<Rgba> for Lch {
+    fn from(rgba: Rgba) -> Lch {
+        Lab::from(rgba).into()
+    }
+}
+
+impl From<Lch> for Rgba {
+    fn from(lch: Lch) -> Rgba {
+        Lab::from(lch).into()
+    }
+}
+
+impl fmt::Display for Lch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({},{},{},{})", self.l, self.c, self.h, self.alpha)
+    }
+}
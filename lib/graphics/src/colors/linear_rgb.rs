@@ -0,0 +1,89 @@
+use super::{lerp, rgba::Rgba, unit_to_byte};
+use std::fmt;
+
+/// sRGB with the gamma curve removed, so mixing components mixes light physically.
+/// In gamut colors have components between 0 and 1
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct LinearRgb {
+    pub r: f64,
+    pub g: f64,
+    pub b: f64,
+    pub alpha: f64,
+}
+
+fn decode(channel: u8) -> f64 {
+    let c = channel as f64 / 255.0;
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn encode(c: f64) -> u8 {
+    let c = c.clamp(0.0, 1.0);
+    unit_to_byte(if c <= 0.0031308 {
+        12.92 * c
+    } else {
+        1.055 * c.powf(1.0 / 2.4) - 0.055
+    })
+}
+
+impl LinearRgb {
+    pub fn new(r: f64, g: f64, b: f64) -> LinearRgb {
+        LinearRgb {
+            r,
+            g,
+            b,
+            alpha: 1.0,
+        }
+    }
+
+    pub fn with_alpha(self, alpha: f64) -> LinearRgb {
+        LinearRgb { alpha, ..self }
+    }
+
+    pub fn in_gamut(&self) -> bool {
+        [self.r, self.g, self.b]
+            .iter()
+            .all(|c| (-1e-9..=1.0 + 1e-9).contains(c))
+    }
+
+    pub fn lerp(self, other: LinearRgb, t: f64) -> LinearRgb {
+        LinearRgb {
+            r: lerp(self.r, other.r, t),
+            g: lerp(self.g, other.g, t),
+            b: lerp(self.b, other.b, t),
+            alpha: lerp(self.alpha, other.alpha, t),
+        }
+    }
+}
+
+impl From<Rgba> for LinearRgb {
+    fn from(rgba: Rgba) -> LinearRgb {
+        LinearRgb {
+            r: decode(rgba.r),
+            g: decode(rgba.g),
+            b: decode(rgba.b),
+            alpha: rgba.a as f64 / 255.0,
+        }
+    }
+}
+
+/// Colors out of gamut are clipped
+impl From<LinearRgb> for Rgba {
+    fn from(lin: LinearRgb) -> Rgba {
+        Rgba::new(
+            encode(lin.r),
+            encode(lin.g),
+            encode(lin.b),
+            unit_to_byte(lin.alpha),
+        )
+    }
+}
+
+impl fmt::Display for LinearRgb {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({},{},{},{})", self.r, self.g, self.b, self.alpha)
+    }
+}
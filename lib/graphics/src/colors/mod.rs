@@ -1,4 +1,205 @@
+pub mod gradient;
+pub mod hsl;
 pub mod hsv;
+pub mod lab;
+pub mod lch;
+pub mod linear_rgb;
+pub mod oklab;
+pub mod oklch;
+pub mod palette;
 pub mod rgba;
+pub mod xyz;
+pub use gradient::{Colormap, Gradient};
+pub use hsl::Hsl;
 pub use hsv::Hsv;
+pub use lab::Lab;
+pub use lch::Lch;
+pub use linear_rgb::LinearRgb;
+pub use oklab::Oklab;
+pub use oklch::Oklch;
+pub use palette::Palette;
 pub use rgba::Rgba;
+pub use xyz::Xyz;
+
+/// Hue in degrees between 0 and 360 of a color with components between 0 and 1,
+/// `max` and `delta` are the largest component and its difference to the smallest one.
+/// Grays have no hue, it is 0 for them
+fn hue(r: f64, g: f64, b: f64, max: f64, delta: f64) -> f64 {
+    if delta == 0.0 {
+        return 0.0;
+    }
+    let h = 60.0
+        * if max == r {
+            (g - b) / delta
+        } else if max == g {
+            (b - r) / delta + 2.0
+        } else {
+            (r - g) / delta + 4.0
+        };
+    if h < 0.0 {
+        h + 360.0
+    } else {
+        h
+    }
+}
+
+/// Colors of Lab like spaces with a smaller chroma count as gray
+const ACHROMATIC: f64 = 1e-4;
+
+/// Polar form of the `(a, b)` plane of Lab like spaces,
+/// the hue is in degrees between 0 and 360 and 0 for grays
+fn to_polar(a: f64, b: f64) -> (f64, f64) {
+    let chroma = a.hypot(b);
+    if chroma < ACHROMATIC {
+        return (chroma, 0.0);
+    }
+    (chroma, b.atan2(a).to_degrees().rem_euclid(360.0))
+}
+
+fn from_polar(chroma: f64, hue: f64) -> (f64, f64) {
+    let (sin, cos) = hue.to_radians().sin_cos();
+    (chroma * cos, chroma * sin)
+}
+
+/// Interpolates hues in degrees along the shorter arc of the circle,
+/// grays take the hue of the other color so they do not pass through unrelated hues
+fn lerp_hue((c1, h1): (f64, f64), (c2, h2): (f64, f64), t: f64) -> f64 {
+    let (h1, h2) = match (c1 < ACHROMATIC, c2 < ACHROMATIC) {
+        (true, false) => (h2, h2),
+        (false, true) => (h1, h1),
+        _ => (h1, h2),
+    };
+    let mut diff = (h2 - h1).rem_euclid(360.0);
+    if diff > 180.0 {
+        diff -= 360.0;
+    }
+    (h1 + t * diff).rem_euclid(360.0)
+}
+
+fn lerp(x1: f64, x2: f64, t: f64) -> f64 {
+    x1 + t * (x2 - x1)
+}
+
+/// Component between 0 and 1 to a byte, clamping values outside of that range
+fn unit_to_byte(value: f64) -> u8 {
+    (value.clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+#[cfg(test)]
+mod colors_tests {
+    use super::{rgba, Hsl, Hsv, Lab, Lch, LinearRgb, Oklab, Oklch, Rgba, Xyz};
+
+    fn assert_close(found: [f64; 3], expected: [f64; 3], tolerance: f64) {
+        for (f, e) in found.iter().zip(expected.iter()) {
+            assert!((f - e).abs() < tolerance, "{found:?} != {expected:?}")
+        }
+    }
+
+    /// Every 5th value of every channel with varying transparency
+    fn samples() -> impl Iterator<Item = Rgba> {
+        (0..=255u8).step_by(5).flat_map(|r| {
+            (0..=255u8).step_by(5).flat_map(move |g| {
+                (0..=255u8)
+                    .step_by(5)
+                    .map(move |b| Rgba::new(r, g, b, r ^ b))
+            })
+        })
+    }
+
+    #[test]
+    fn round_trips() {
+        for color in samples() {
+            assert_eq!(Rgba::from(Hsl::from(color)), color);
+            assert_eq!(Rgba::from(LinearRgb::from(color)), color);
+            assert_eq!(Rgba::from(Xyz::from(color)), color);
+            assert_eq!(Rgba::from(Lab::from(color)), color);
+            assert_eq!(Rgba::from(Lch::from(color)), color);
+            assert_eq!(Rgba::from(Oklab::from(color)), color);
+            assert_eq!(Rgba::from(Oklch::from(color)), color);
+            let hsv = Rgba::from(Hsv::from(color));
+            assert_eq!(hsv, color.with_trans(255));
+        }
+    }
+
+    #[test]
+    fn grays() {
+        for val in [0, 1, 128, 255] {
+            let gray = Rgba::new(val, val, val, 255);
+            let hsv = Hsv::from(gray);
+            assert_eq!((hsv.h, hsv.s), (0.0, 0.0));
+            let hsl = Hsl::from(gray);
+            assert_eq!((hsl.h, hsl.s), (0.0, 0.0));
+            let lch = Lch::from(gray);
+            assert!(lch.c < 1e-4 && lch.h == 0.0, "{lch}");
+            let oklch = Oklch::from(gray);
+            assert!(oklch.c < 1e-4 && oklch.h == 0.0, "{oklch}");
+        }
+        let black = Lab::from(rgba::BLACK);
+        assert_close([black.l, black.a, black.b], [0.0, 0.0, 0.0], 1e-9);
+        let white = Lab::from(rgba::WHITE);
+        assert_close([white.l, white.a, white.b], [100.0, 0.0, 0.0], 1e-4);
+        let white = Oklab::from(rgba::WHITE);
+        assert_close([white.l, white.a, white.b], [1.0, 0.0, 0.0], 1e-6)
+    }
+
+    #[test]
+    fn reference_values() {
+        let red = Lab::from(rgba::RED);
+        assert_close([red.l, red.a, red.b], [53.2408, 80.0925, 67.2032], 1e-3);
+        let red = Oklab::from(rgba::RED);
+        assert_close([red.l, red.a, red.b], [0.627955, 0.224863, 0.125846], 1e-5);
+        let hsv = Hsv::from(Rgba::new(0, 128, 255, 255));
+        assert!((hsv.h - 209.88).abs() < 0.01, "{hsv}");
+        let hsl = Hsl::from(Rgba::new(64, 191, 64, 255));
+        assert_close([hsl.h, hsl.s, hsl.l], [120.0, 0.4980, 0.5], 1e-3);
+        let lin = LinearRgb::from(Rgba::new(128, 128, 128, 255));
+        assert!((lin.r - 0.2158605).abs() < 1e-6)
+    }
+
+    #[test]
+    fn delta_e() {
+        // pairs from Sharma, Wu and Dalal, "The CIEDE2000 Color-Difference Formula"
+        let pairs = [
+            ([50.0, 2.6772, -79.7751], [50.0, 0.0, -82.7485], 2.0425),
+            ([50.0, 0.0, 0.0], [50.0, -1.0, 2.0], 2.3669),
+            ([50.0, 2.5, 0.0], [73.0, 25.0, -18.0], 27.1492),
+            ([50.0, 2.5, 0.0], [50.0, 0.0, -2.5], 4.3065),
+        ];
+        for ([l1, a1, b1], [l2, a2, b2], expected) in pairs {
+            let (lab1, lab2) = (Lab::new(l1, a1, b1), Lab::new(l2, a2, b2));
+            assert!((lab1.delta_e2000(&lab2) - expected).abs() < 1e-4);
+            assert!((lab2.delta_e2000(&lab1) - expected).abs() < 1e-4)
+        }
+        assert_eq!(
+            Lab::new(0.0, 3.0, 4.0).delta_e76(&Lab::new(0.0, 0.0, 0.0)),
+            5.0
+        );
+        assert_eq!(rgba::RED.delta_e(rgba::RED), 0.0);
+        assert!((rgba::BLACK.delta_e(rgba::WHITE) - 100.0).abs() < 1e-3)
+    }
+
+    #[test]
+    fn perceptual_interpolation() {
+        let (blue, yellow) = (rgba::BLUE, rgba::YELLOW);
+        // gamma encoded sRGB goes through a dull gray, Oklab keeps some color
+        let srgb = Oklch::from(blue.lerp(yellow, 0.5));
+        let oklab = Oklch::from(blue.lerp_oklab(yellow, 0.5));
+        assert!(srgb.c < 1e-4);
+        assert!(oklab.l > srgb.l);
+        assert_eq!(blue.lerp_oklab(yellow, 0.0), blue);
+        assert_eq!(blue.lerp_oklab(yellow, 1.0), yellow);
+        assert_eq!(blue.lerp_linear(yellow, 0.5), Rgba::new(188, 188, 188, 255));
+
+        // hues go the short way around and grays do not pick up a hue of their own
+        let mid = Oklch::new(0.5, 0.1, 350.0).lerp(Oklch::new(0.5, 0.1, 30.0), 0.5);
+        assert!((mid.h - 10.0).abs() < 1e-9);
+        let from_gray = Lch::from(rgba::WHITE).lerp(Lch::from(rgba::RED), 0.5);
+        assert!((from_gray.h - Lch::from(rgba::RED).h).abs() < 1e-9)
+    }
+
+    #[test]
+    fn with_trans() {
+        let color = Rgba::new(1, 2, 3, 255).with_trans(4);
+        assert_eq!(color, Rgba::new(1, 2, 3, 4))
+    }
+}
@@ -0,0 +1,103 @@
+use super::{lerp, linear_rgb::LinearRgb, oklch::Oklch, rgba::Rgba};
+use std::fmt;
+
+/// Perceptual color space by Björn Ottosson, more uniform than `Lab` especially for blues.
+/// `l` goes from 0 (black) to 1 (white), `a` and `b` stay within about ±0.4 for sRGB colors
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Oklab {
+    pub l: f64,
+    pub a: f64,
+    pub b: f64,
+    pub alpha: f64,
+}
+
+impl Oklab {
+    pub fn new(l: f64, a: f64, b: f64) -> Oklab {
+        Oklab {
+            l,
+            a,
+            b,
+            alpha: 1.0,
+        }
+    }
+
+    pub fn with_alpha(self, alpha: f64) -> Oklab {
+        Oklab { alpha, ..self }
+    }
+
+    /// Gradients through Oklab keep their perceived lightness and do not turn gray
+    /// in the middle the way gamma encoded sRGB gradients do
+    pub fn lerp(self, other: Oklab, t: f64) -> Oklab {
+        Oklab {
+            l: lerp(self.l, other.l, t),
+            a: lerp(self.a, other.a, t),
+            b: lerp(self.b, other.b, t),
+            alpha: lerp(self.alpha, other.alpha, t),
+        }
+    }
+
+    /// Euclidean distance, differences around 0.02 are just noticeable
+    pub fn distance(&self, other: &Oklab) -> f64 {
+        ((self.l - other.l).powi(2) + (self.a - other.a).powi(2) + (self.b - other.b).powi(2))
+            .sqrt()
+    }
+}
+
+impl From<LinearRgb> for Oklab {
+    fn from(lin: LinearRgb) -> Oklab {
+        let l = (0.4122214708 * lin.r + 0.5363325363 * lin.g + 0.0514459929 * lin.b).cbrt();
+        let m = (0.2119034982 * lin.r + 0.6806995451 * lin.g + 0.1073969566 * lin.b).cbrt();
+        let s = (0.0883024619 * lin.r + 0.2817188376 * lin.g + 0.6299787005 * lin.b).cbrt();
+        Oklab {
+            l: 0.2104542553 * l + 0.7936177850 * m - 0.0040720468 * s,
+            a: 1.9779984951 * l - 2.4285922050 * m + 0.4505937099 * s,
+            b: 0.0259040371 * l + 0.7827717662 * m - 0.8086757660 * s,
+            alpha: lin.alpha,
+        }
+    }
+}
+
+impl From<Oklab> for LinearRgb {
+    fn from(lab: Oklab) -> LinearRgb {
+        let l = (lab.l + 0.3963377774 * lab.a + 0.2158037573 * lab.b).powi(3);
+        let m = (lab.l - 0.1055613458 * lab.a - 0.0638541728 * lab.b).powi(3);
+        let s = (lab.l - 0.0894841775 * lab.a - 1.2914855480 * lab.b).powi(3);
+        LinearRgb {
+            r: 4.0767416621 * l - 3.3077115913 * m + 0.2309699292 * s,
+            g: -1.2684380046 * l + 2.6097574011 * m - 0.3413193965 * s,
+            b: -0.0041960863 * l - 0.7034186147 * m + 1.7076147010 * s,
+            alpha: lab.alpha,
+        }
+    }
+}
+
+impl From<Oklch> for Oklab {
+    fn from(lch: Oklch) -> Oklab {
+        let (a, b) = super::from_polar(lch.c, lch.h);
+        Oklab {
+            l: lch.l,
+            a,
+            b,
+            alpha: lch.alpha,
+        }
+    }
+}
+
+impl From<Rgba> for Oklab {
+    fn from(rgba: Rgba) -> Oklab {
+        LinearRgb::from(rgba).into()
+    }
+}
+
+/// Colors out of the sRGB gamut are clipped
+impl From<Oklab> for Rgba {
+    fn from(lab: Oklab) -> Rgba {
+        LinearRgb::from(lab).into()
+    }
+}
+
+impl fmt::Display for Oklab {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({},{},{},{})", self.l, self.a, self.b, self.alpha)
+    }
+}
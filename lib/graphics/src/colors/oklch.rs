@@ -0,0 +1,68 @@
+use super::{lerp, lerp_hue, oklab::Oklab, rgba::Rgba};
+use std::fmt;
+
+/// Polar form of `Oklab`:
+/// lightness, chroma and the hue in degrees between 0 and 360
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Oklch {
+    pub l: f64,
+    pub c: f64,
+    pub h: f64,
+    pub alpha: f64,
+}
+
+impl Oklch {
+    pub fn new(l: f64, c: f64, h: f64) -> Oklch {
+        Oklch {
+            l,
+            c,
+            h: h.rem_euclid(360.0),
+            alpha: 1.0,
+        }
+    }
+
+    pub fn with_alpha(self, alpha: f64) -> Oklch {
+        Oklch { alpha, ..self }
+    }
+
+    /// Interpolates the hue along the shorter arc,
+    /// grays take the hue of the other color so they do not pass through unrelated hues
+    pub fn lerp(self, other: Oklch, t: f64) -> Oklch {
+        Oklch {
+            l: lerp(self.l, other.l, t),
+            c: lerp(self.c, other.c, t),
+            h: lerp_hue((self.c, self.h), (other.c, other.h), t),
+            alpha: lerp(self.alpha, other.alpha, t),
+        }
+    }
+}
+
+impl From<Oklab> for Oklch {
+    fn from(lab: Oklab) -> Oklch {
+        let (c, h) = super::to_polar(lab.a, lab.b);
+        Oklch {
+            l: lab.l,
+            c,
+            h,
+            alpha: lab.alpha,
+        }
+    }
+}
+
+impl From<Rgba> for Oklch {
+    fn from(rgba: Rgba) -> Oklch {
+        Oklab::from(rgba).into()
+    }
+}
+
+impl From<Oklch> for Rgba {
+    fn from(lch: Oklch) -> Rgba {
+        Oklab::from(lch).into()
+    }
+}
+
+impl fmt::Display for Oklch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({},{},{},{})", self.l, self.c, self.h, self.alpha)
+    }
+}
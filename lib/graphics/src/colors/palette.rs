@@ -0,0 +1,226 @@
+use super::{linear_rgb::LinearRgb, oklab::Oklab, oklch::Oklch, rgba, rgba::Rgba};
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+use std::ops::Range;
+
+/// The golden angle in degrees, consecutive hues stepped by it never line up
+const GOLDEN_ANGLE: f64 = 137.50776405003785;
+
+/// Color with the given lightness and hue in Oklch, the chroma is reduced
+/// until the color fits into sRGB so the hue stays the same
+fn in_gamut(lch: Oklch) -> Rgba {
+    let fits = |chroma: f64| LinearRgb::from(Oklab::from(Oklch { c: chroma, ..lch })).in_gamut();
+    if fits(lch.c) {
+        return Oklab::from(lch).into();
+    }
+    let (mut low, mut high) = (0.0, lch.c);
+    for _ in 0..20 {
+        let mid = (low + high) / 2.0;
+        if fits(mid) {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+    Oklab::from(Oklch { c: low, ..lch }).into()
+}
+
+/// `base` with its hue turned by every offset in degrees, lightness and chroma are kept
+fn rotations(base: Rgba, offsets: impl Iterator<Item = f64>) -> Vec<Rgba> {
+    let lch = Oklch::from(base);
+    offsets
+        .map(|offset| {
+            if offset == 0.0 {
+                base
+            } else {
+                in_gamut(Oklch::new(lch.l, lch.c, lch.h + offset).with_alpha(lch.alpha))
+            }
+        })
+        .collect()
+}
+
+/// Colors that go together, hues are spaced in Oklch so every color
+/// of a palette has the same perceived lightness
+#[derive(Debug, Clone, PartialEq)]
+pub struct Palette {
+    colors: Vec<Rgba>,
+}
+
+impl Palette {
+    pub fn new(colors: Vec<Rgba>) -> Palette {
+        assert!(!colors.is_empty(), "palette without colors");
+        Palette { colors }
+    }
+
+    /// `base` and the color opposite of it on the hue circle
+    pub fn complementary(base: Rgba) -> Palette {
+        Palette::new(rotations(base, [0.0, 180.0].into_iter()))
+    }
+
+    /// `base` and the two colors a third of the hue circle away
+    pub fn triadic(base: Rgba) -> Palette {
+        Palette::new(rotations(base, [0.0, 120.0, 240.0].into_iter()))
+    }
+
+    /// `num` neighboring hues spread over `spread` degrees with `base` in the middle
+    pub fn analogous(base: Rgba, num: usize, spread: f64) -> Palette {
+        let step = spread / (num.max(2) - 1) as f64;
+        let start = if num > 1 { -spread / 2.0 } else { 0.0 };
+        Palette::new(rotations(
+            base,
+            (0..num.max(1)).map(|ind| start + ind as f64 * step),
+        ))
+    }
+
+    /// `num` hues stepped by the golden angle starting at `base`,
+    /// neighbors differ a lot however many colors are taken
+    pub fn golden(base: Rgba, num: usize) -> Palette {
+        Palette::new(rotations(
+            base,
+            (0..num.max(1)).map(|ind| ind as f64 * GOLDEN_ANGLE),
+        ))
+    }
+
+    /// `num` colors with golden angle hue steps from a random start hue,
+    /// lightness and chroma (in Oklch) are random within the given ranges.
+    /// The same seed always gives the same palette
+    pub fn random(num: usize, lightness: Range<f64>, chroma: Range<f64>, seed: u64) -> Palette {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let start_hue = rng.gen_range(0.0..360.0);
+        let mut sample = |range: &Range<f64>| {
+            if range.is_empty() {
+                range.start
+            } else {
+                rng.gen_range(range.clone())
+            }
+        };
+        Palette::new(
+            (0..num.max(1))
+                .map(|ind| {
+                    let lch = Oklch::new(
+                        sample(&lightness),
+                        sample(&chroma),
+                        start_hue + ind as f64 * GOLDEN_ANGLE,
+                    );
+                    in_gamut(lch)
+                })
+                .collect(),
+        )
+    }
+
+    /// Random saturated colors of medium lightness, readable on dark backgrounds
+    pub fn vivid(num: usize) -> Palette {
+        Palette::random(num, 0.65..0.8, 0.12..0.18, rand::random())
+    }
+
+    /// Random light desaturated colors
+    pub fn pastel(num: usize) -> Palette {
+        Palette::random(num, 0.85..0.93, 0.04..0.08, rand::random())
+    }
+
+    pub fn colors(&self) -> &[Rgba] {
+        &self.colors
+    }
+
+    /// Color at `ind`, starting over at the front after the last color
+    pub fn get(&self, ind: usize) -> Rgba {
+        self.colors[ind % self.colors.len()]
+    }
+
+    pub fn sample(&self) -> Rgba {
+        *self.colors.choose(&mut rand::thread_rng()).unwrap()
+    }
+}
+
+/// WCAG contrast ratio between 1 (equal) and 21 (black and white), transparency is ignored.
+/// Text should have a ratio of at least 4.5 to its background
+pub fn contrast_ratio(col1: Rgba, col2: Rgba) -> f64 {
+    let luminance = |col: Rgba| {
+        let lin = LinearRgb::from(col);
+        0.2126 * lin.r + 0.7152 * lin.g + 0.0722 * lin.b
+    };
+    let (lum1, lum2) = (luminance(col1), luminance(col2));
+    (lum1.max(lum2) + 0.05) / (lum1.min(lum2) + 0.05)
+}
+
+/// Black or white, whichever contrasts more with `background`
+pub fn text_color(background: Rgba) -> Rgba {
+    if contrast_ratio(background, rgba::BLACK) >= contrast_ratio(background, rgba::WHITE) {
+        rgba::BLACK
+    } else {
+        rgba::WHITE
+    }
+}
+
+#[cfg(test)]
+mod palette_tests {
+    use super::{contrast_ratio, text_color, Palette};
+    use crate::colors::{rgba, Oklch, Rgba};
+
+    fn hue_dist(col1: Rgba, col2: Rgba) -> f64 {
+        let diff = (Oklch::from(col1).h - Oklch::from(col2).h).rem_euclid(360.0);
+        diff.min(360.0 - diff)
+    }
+
+    #[test]
+    fn harmonies() {
+        let base = Rgba::new(200, 80, 40, 255);
+        let comp = Palette::complementary(base);
+        assert_eq!(comp.colors().len(), 2);
+        assert_eq!(comp.get(0), base);
+        assert!((hue_dist(comp.get(0), comp.get(1)) - 180.0).abs() < 1.0);
+
+        let triadic = Palette::triadic(base);
+        for (ind, color) in triadic.colors().iter().enumerate() {
+            let next = triadic.get(ind + 1);
+            assert!((hue_dist(*color, next) - 120.0).abs() < 1.0);
+            // lightness is kept, the chroma may be reduced to stay in gamut
+            let (lch, base_lch) = (Oklch::from(*color), Oklch::from(base));
+            assert!((lch.l - base_lch.l).abs() < 0.01);
+            assert!(lch.c <= base_lch.c + 0.01)
+        }
+
+        let analogous = Palette::analogous(base, 5, 60.0);
+        assert_eq!(analogous.get(2), base);
+        assert!((hue_dist(analogous.get(0), analogous.get(4)) - 60.0).abs() < 1.0);
+        assert_eq!(Palette::analogous(base, 1, 60.0).colors(), &[base]);
+    }
+
+    #[test]
+    fn golden() {
+        let palette = Palette::golden(rgba::RED, 20);
+        assert_eq!(palette.get(20), rgba::RED);
+        for pair in palette.colors().windows(2) {
+            assert!(hue_dist(pair[0], pair[1]) > 130.0)
+        }
+    }
+
+    #[test]
+    fn random() {
+        let palette = Palette::random(10, 0.6..0.7, 0.05..0.1, 42);
+        assert_eq!(palette, Palette::random(10, 0.6..0.7, 0.05..0.1, 42));
+        assert_ne!(palette, Palette::random(10, 0.6..0.7, 0.05..0.1, 43));
+        for color in palette.colors() {
+            let lch = Oklch::from(*color);
+            assert!((0.595..0.705).contains(&lch.l), "{lch}");
+            assert!(lch.c < 0.105, "{lch}")
+        }
+        let fixed = Palette::random(3, 0.5..0.5, 0.0..0.0, 1);
+        assert!(fixed
+            .colors()
+            .iter()
+            .all(|col| col.r == col.g && col.g == col.b));
+        assert!(palette.colors().contains(&palette.sample()))
+    }
+
+    #[test]
+    fn contrast() {
+        assert!((contrast_ratio(rgba::BLACK, rgba::WHITE) - 21.0).abs() < 1e-9);
+        assert_eq!(contrast_ratio(rgba::RED, rgba::RED), 1.0);
+        assert_eq!(text_color(rgba::YELLOW), rgba::BLACK);
+        assert_eq!(text_color(rgba::BLUE), rgba::WHITE);
+        assert_eq!(text_color(Rgba::new(40, 40, 40, 255)), rgba::WHITE);
+        for color in Palette::vivid(30).colors() {
+            assert!(contrast_ratio(*color, text_color(*color)) >= 4.5)
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use super::hsv::Hsv;
+use super::{hsv::Hsv, lab::Lab, linear_rgb::LinearRgb, oklab::Oklab};
 use std::fmt;
 
 fn hex2num(hex: char) -> Option<u8> {
@@ -172,6 +172,7 @@ impl Rgba {
         }
     }
 
+    /// Interpolates the gamma encoded components, see `lerp_oklab` for even gradients
     pub fn lerp(self, other: Rgba, t: f64) -> Rgba {
         if t < 0.0 {
             return self;
@@ -188,23 +189,40 @@ impl Rgba {
         }
     }
 
+    /// Mixes the light of both colors, gradients stay brighter than with `lerp`
+    pub fn lerp_linear(self, other: Rgba, t: f64) -> Rgba {
+        let t = t.clamp(0.0, 1.0);
+        LinearRgb::from(self).lerp(LinearRgb::from(other), t).into()
+    }
+
+    /// Perceptually even gradient, see `Oklab::lerp`
+    pub fn lerp_oklab(self, other: Rgba, t: f64) -> Rgba {
+        let t = t.clamp(0.0, 1.0);
+        Oklab::from(self).lerp(Oklab::from(other), t).into()
+    }
+
+    /// CIEDE2000 color difference, 0 for equal colors and around 100 for black and white
+    pub fn delta_e(self, other: Rgba) -> f64 {
+        Lab::from(self).delta_e2000(&Lab::from(other))
+    }
+
     pub fn with_trans(self, trans: u8) -> Rgba {
         Rgba {
             r: self.r,
             g: self.g,
-            b: self.g,
+            b: self.b,
             a: trans,
         }
     }
 }
 
-impl Into<[f32; 4]> for Rgba {
-    fn into(self) -> [f32; 4] {
+impl From<Rgba> for [f32; 4] {
+    fn from(col: Rgba) -> [f32; 4] {
         [
-            self.r as f32 / 255.0,
-            self.g as f32 / 255.0,
-            self.b as f32 / 255.0,
-            self.a as f32 / 255.0,
+            col.r as f32 / 255.0,
+            col.g as f32 / 255.0,
+            col.b as f32 / 255.0,
+            col.a as f32 / 255.0,
         ]
     }
 }
@@ -219,6 +237,19 @@ impl From<[f32; 4]> for Rgba {
     }
 }
 
+impl From<image::Rgba<u8>> for Rgba {
+    fn from(col: image::Rgba<u8>) -> Rgba {
+        let [r, g, b, a] = col.0;
+        Rgba::new(r, g, b, a)
+    }
+}
+
+impl From<Rgba> for image::Rgba<u8> {
+    fn from(col: Rgba) -> image::Rgba<u8> {
+        image::Rgba([col.r, col.g, col.b, col.a])
+    }
+}
+
 impl From<Hsv> for Rgba {
     fn from(hsv: Hsv) -> Rgba {
         Rgba::from_hsv(hsv)
@@ -0,0 +1,74 @@
+use super::{linear_rgb::LinearRgb, rgba::Rgba};
+use std::fmt;
+
+/// Reference white of sRGB (D65), the XYZ coordinates of `rgba::WHITE`
+pub const D65: Xyz = Xyz {
+    x: 0.95047,
+    y: 1.0,
+    z: 1.08883,
+    alpha: 1.0,
+};
+
+/// CIE 1931 XYZ color relative to the D65 white point, `y` is the luminance
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Xyz {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+    pub alpha: f64,
+}
+
+impl Xyz {
+    pub fn new(x: f64, y: f64, z: f64) -> Xyz {
+        Xyz {
+            x,
+            y,
+            z,
+            alpha: 1.0,
+        }
+    }
+
+    pub fn with_alpha(self, alpha: f64) -> Xyz {
+        Xyz { alpha, ..self }
+    }
+}
+
+impl From<LinearRgb> for Xyz {
+    fn from(lin: LinearRgb) -> Xyz {
+        Xyz {
+            x: 0.4124564 * lin.r + 0.3575761 * lin.g + 0.1804375 * lin.b,
+            y: 0.2126729 * lin.r + 0.7151522 * lin.g + 0.0721750 * lin.b,
+            z: 0.0193339 * lin.r + 0.1191920 * lin.g + 0.9503041 * lin.b,
+            alpha: lin.alpha,
+        }
+    }
+}
+
+impl From<Xyz> for LinearRgb {
+    fn from(xyz: Xyz) -> LinearRgb {
+        LinearRgb {
+            r: 3.2404542 * xyz.x - 1.5371385 * xyz.y - 0.4985314 * xyz.z,
+            g: -0.9692660 * xyz.x + 1.8760108 * xyz.y + 0.0415560 * xyz.z,
+            b: 0.0556434 * xyz.x - 0.2040259 * xyz.y + 1.0572252 * xyz.z,
+            alpha: xyz.alpha,
+        }
+    }
+}
+
+impl From<Rgba> for Xyz {
+    fn from(rgba: Rgba) -> Xyz {
+        LinearRgb::from(rgba).into()
+    }
+}
+
+impl From<Xyz> for Rgba {
+    fn from(xyz: Xyz) -> Rgba {
+        LinearRgb::from(xyz).into()
+    }
+}
+
+impl fmt::Display for Xyz {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({},{},{},{})", self.x, self.y, self.z, self.alpha)
+    }
+}
@@ -0,0 +1,127 @@
+use piston_window::{G2dTextureContext, Glyphs, TextureSettings};
+use std::{borrow::Cow, collections::HashMap, io, path::PathBuf};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum FontStyle {
+    #[default]
+    Regular,
+    Bold,
+    Italic,
+    BoldItalic,
+}
+
+/// Face to draw text with, `family` is the name the font was registered with,
+/// names known at runtime like the ones from config files are owned
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Font {
+    pub family: Cow<'static, str>,
+    pub style: FontStyle,
+}
+
+impl Font {
+    /// The font every window starts with
+    pub const DEFAULT: Font = Font::named("default");
+    /// Built in, every character has the same width
+    pub const MONOSPACE: Font = Font::named("monospace");
+
+    pub fn new<S: Into<Cow<'static, str>>>(family: S) -> Font {
+        Font {
+            family: family.into(),
+            style: FontStyle::Regular,
+        }
+    }
+
+    /// `new` for constants
+    pub const fn named(family: &'static str) -> Font {
+        Font {
+            family: Cow::Borrowed(family),
+            style: FontStyle::Regular,
+        }
+    }
+
+    pub fn with_style(mut self, style: FontStyle) -> Font {
+        self.style = style;
+        self
+    }
+
+    pub fn bold(self) -> Font {
+        self.with_style(FontStyle::Bold)
+    }
+
+    pub fn italic(self) -> Font {
+        self.with_style(FontStyle::Italic)
+    }
+}
+
+impl Default for Font {
+    fn default() -> Self {
+        Font::DEFAULT
+    }
+}
+
+/// TTF or OTF file that is loaded as `font` when the window opens, see `Runnable::fonts`
+#[derive(Debug, Clone, PartialEq)]
+pub struct FontFile {
+    pub font: Font,
+    pub path: PathBuf,
+}
+
+impl FontFile {
+    pub fn new<P: Into<PathBuf>>(font: Font, path: P) -> FontFile {
+        FontFile {
+            font,
+            path: path.into(),
+        }
+    }
+}
+
+/// Glyph caches of the fonts besides the default one, which is `DrawingContext::glyphs`
+pub struct Fonts {
+    faces: HashMap<Font, Glyphs>,
+}
+
+impl Fonts {
+    /// Only the built in monospace font, DejaVu Sans Mono under the license
+    /// in `font_mono_LICENSE.txt`
+    pub(crate) fn new(texture_context: G2dTextureContext) -> Fonts {
+        let monospace = Glyphs::from_bytes(
+            include_bytes!("font_mono.ttf"),
+            texture_context,
+            TextureSettings::new(),
+        )
+        .unwrap();
+        Fonts {
+            faces: HashMap::from([(Font::MONOSPACE, monospace)]),
+        }
+    }
+
+    pub(crate) fn load(
+        &mut self,
+        file: &FontFile,
+        texture_context: G2dTextureContext,
+    ) -> io::Result<()> {
+        let glyphs = Glyphs::new(&file.path, texture_context, TextureSettings::new())?;
+        self.faces.insert(file.font.clone(), glyphs);
+        Ok(())
+    }
+
+    pub fn contains(&self, font: &Font) -> bool {
+        self.faces.contains_key(font)
+    }
+
+    /// Glyphs of the font, the regular style of the family stands in for missing styles
+    pub fn get_mut(&mut self, font: &Font) -> Option<&mut Glyphs> {
+        if self.contains(font) {
+            return self.faces.get_mut(font);
+        }
+        self.faces
+            .get_mut(&font.clone().with_style(FontStyle::Regular))
+    }
+
+    /// Uploads the glyphs drawn since the last flush
+    pub(crate) fn flush(&mut self, device: &mut gfx_device_gl::Device) {
+        for glyphs in self.faces.values_mut() {
+            glyphs.factory.encoder.flush(device);
+        }
+    }
+}
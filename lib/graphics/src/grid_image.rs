@@ -0,0 +1,305 @@
+use crate::colors::{Gradient, Rgba};
+use image::{GrayImage, Luma, RgbaImage};
+use math::grid::Grid;
+use std::{
+    fs::File,
+    io::{self, BufWriter, Read, Write},
+    path::Path,
+};
+
+/// Plain text formats are written with this maximum value
+const MAX_VAL: u16 = 255;
+
+/// Rescales the values of the grid so the smallest one becomes 0 and the largest 1,
+/// constant grids become 0 everywhere
+pub fn normalized(grid: &Grid<f64>) -> Grid<f64> {
+    let (min, max) = grid
+        .iter()
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), val| {
+            (min.min(*val), max.max(*val))
+        });
+    let range = max - min;
+    grid.map_ref(|val| {
+        if range > 0.0 {
+            (val - min) / range
+        } else {
+            0.0
+        }
+    })
+}
+
+pub fn colorize(grid: &Grid<f64>, gradient: &Gradient) -> Grid<Rgba> {
+    grid.map_ref(|val| gradient.map(*val))
+}
+
+/// Luma between 0 and 1, the Rec. 709 weights applied to the gamma encoded channels
+/// so mid gray is about 0.5. Not the relative luminance, which needs linear channels.
+/// Ignores transparency
+pub fn luma(color: Rgba) -> f64 {
+    (0.2126 * color.r as f64 + 0.7152 * color.g as f64 + 0.0722 * color.b as f64) / 255.0
+}
+
+fn to_byte(value: f64) -> u8 {
+    (value.clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+/// Values between 0 (black) and 1 (white), everything else is clamped
+pub fn to_gray_image(grid: &Grid<f64>) -> GrayImage {
+    GrayImage::from_fn(grid.num_cols() as u32, grid.num_rows() as u32, |x, y| {
+        Luma([to_byte(grid[(x as usize, y as usize)])])
+    })
+}
+
+pub fn from_gray_image(image: &GrayImage) -> Grid<f64> {
+    Grid::from_fn(
+        |x, y| image.get_pixel(x as u32, y as u32).0[0] as f64 / 255.0,
+        image.width() as usize,
+        image.height() as usize,
+    )
+}
+
+pub fn to_rgba_image(grid: &Grid<Rgba>) -> RgbaImage {
+    RgbaImage::from_fn(grid.num_cols() as u32, grid.num_rows() as u32, |x, y| {
+        grid[(x as usize, y as usize)].into()
+    })
+}
+
+pub fn from_rgba_image(image: &RgbaImage) -> Grid<Rgba> {
+    Grid::from_fn(
+        |x, y| (*image.get_pixel(x as u32, y as u32)).into(),
+        image.width() as usize,
+        image.height() as usize,
+    )
+}
+
+/// Writes the grid as a plain text (P2) graymap, see `to_gray_image` for the value range
+pub fn write_pgm<W: Write>(grid: &Grid<f64>, mut writer: W) -> io::Result<()> {
+    writeln!(writer, "P2")?;
+    writeln!(writer, "{} {}", grid.num_cols(), grid.num_rows())?;
+    writeln!(writer, "{MAX_VAL}")?;
+    for y in 0..grid.num_rows() {
+        let row: Vec<String> = grid
+            .row(y)
+            .iter()
+            .map(|v| to_byte(*v).to_string())
+            .collect();
+        writeln!(writer, "{}", row.join(" "))?;
+    }
+    Ok(())
+}
+
+/// Writes the grid as a plain text (P3) pixmap, transparency is dropped
+pub fn write_ppm<W: Write>(grid: &Grid<Rgba>, mut writer: W) -> io::Result<()> {
+    writeln!(writer, "P3")?;
+    writeln!(writer, "{} {}", grid.num_cols(), grid.num_rows())?;
+    writeln!(writer, "{MAX_VAL}")?;
+    for y in 0..grid.num_rows() {
+        let row: Vec<String> = grid
+            .row(y)
+            .iter()
+            .map(|c| format!("{} {} {}", c.r, c.g, c.b))
+            .collect();
+        writeln!(writer, "{}", row.join(" "))?;
+    }
+    Ok(())
+}
+
+fn invalid_data(msg: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+/// Header and samples of a plain text netpbm file, samples are scaled to 0..1
+fn read_netpbm<R: Read>(
+    mut reader: R,
+    magic: &str,
+    channels: usize,
+) -> io::Result<(usize, usize, Vec<f64>)> {
+    let mut contents = String::new();
+    reader.read_to_string(&mut contents)?;
+    let mut tokens = contents
+        .lines()
+        .map(|line| line.split('#').next().unwrap_or_default())
+        .flat_map(|line| line.split_whitespace());
+
+    let found = tokens.next().unwrap_or_default();
+    if found != magic {
+        return Err(invalid_data(format!("expected {magic}, found {found:?}")));
+    }
+    let mut numbers = tokens.map(|token| {
+        token
+            .parse::<u16>()
+            .map_err(|_| invalid_data(format!("invalid number {token:?}")))
+    });
+    let mut next = |what: &str| {
+        numbers
+            .next()
+            .unwrap_or_else(|| Err(invalid_data(format!("missing {what}"))))
+    };
+
+    let width = next("width")? as usize;
+    let height = next("height")? as usize;
+    let max_val = next("maximum value")?;
+    if max_val == 0 {
+        return Err(invalid_data("maximum value is zero".to_owned()));
+    }
+    let samples = (0..width * height * channels)
+        .map(|_| {
+            let sample = next("sample")?;
+            if sample > max_val {
+                return Err(invalid_data(format!("sample {sample} above {max_val}")));
+            }
+            Ok(sample as f64 / max_val as f64)
+        })
+        .collect::<io::Result<Vec<f64>>>()?;
+    Ok((width, height, samples))
+}
+
+/// Reads a plain text (P2) graymap with values between 0 and 1
+pub fn read_pgm<R: Read>(reader: R) -> io::Result<Grid<f64>> {
+    let (width, height, samples) = read_netpbm(reader, "P2", 1)?;
+    Ok(Grid::from_vec(samples, width, height))
+}
+
+/// Reads a plain text (P3) pixmap, every color is opaque
+pub fn read_ppm<R: Read>(reader: R) -> io::Result<Grid<Rgba>> {
+    let (width, height, samples) = read_netpbm(reader, "P3", 3)?;
+    let colors = samples
+        .chunks(3)
+        .map(|rgb| Rgba::new(to_byte(rgb[0]), to_byte(rgb[1]), to_byte(rgb[2]), 255))
+        .collect();
+    Ok(Grid::from_vec(colors, width, height))
+}
+
+fn has_extension(path: &Path, ext: &str) -> bool {
+    path.extension()
+        .is_some_and(|found| found.eq_ignore_ascii_case(ext))
+}
+
+/// Saves the grid as a grayscale image, `.pgm` files are written as plain text
+/// and every other format is picked from the extension by the image crate
+pub fn save_gray<P: AsRef<Path>>(grid: &Grid<f64>, path: P) -> io::Result<()> {
+    let path = path.as_ref();
+    if has_extension(path, "pgm") {
+        let mut writer = BufWriter::new(File::create(path)?);
+        write_pgm(grid, &mut writer)?;
+        return writer.flush();
+    }
+    to_gray_image(grid).save(path).map_err(io::Error::other)
+}
+
+/// Saves the grid with every value mapped through `gradient`, see `save_rgba`
+pub fn save_colored<P: AsRef<Path>>(
+    grid: &Grid<f64>,
+    path: P,
+    gradient: &Gradient,
+) -> io::Result<()> {
+    save_rgba(&colorize(grid, gradient), path)
+}
+
+/// `.ppm` files are written as plain text,
+/// every other format is picked from the extension by the image crate
+pub fn save_rgba<P: AsRef<Path>>(grid: &Grid<Rgba>, path: P) -> io::Result<()> {
+    let path = path.as_ref();
+    if has_extension(path, "ppm") {
+        let mut writer = BufWriter::new(File::create(path)?);
+        write_ppm(grid, &mut writer)?;
+        return writer.flush();
+    }
+    to_rgba_image(grid).save(path).map_err(io::Error::other)
+}
+
+/// Loads an image with values between 0 (black) and 1 (white),
+/// colored images are converted with `luma`
+pub fn load_gray<P: AsRef<Path>>(path: P) -> io::Result<Grid<f64>> {
+    let path = path.as_ref();
+    if has_extension(path, "pgm") {
+        if let Ok(grid) = read_pgm(File::open(path)?) {
+            return Ok(grid);
+        }
+    }
+    Ok(load_rgba(path)?.map_ref(|color| luma(*color)))
+}
+
+/// Loads plain text `.ppm` files or anything the image crate can open
+pub fn load_rgba<P: AsRef<Path>>(path: P) -> io::Result<Grid<Rgba>> {
+    let path = path.as_ref();
+    if has_extension(path, "ppm") {
+        if let Ok(grid) = read_ppm(File::open(path)?) {
+            return Ok(grid);
+        }
+    }
+    // binary netpbm files and everything else
+    let image = image::open(path).map_err(io::Error::other)?;
+    Ok(from_rgba_image(&image.into_rgba8()))
+}
+
+#[cfg(test)]
+mod grid_image_tests {
+    use super::{
+        colorize, from_gray_image, normalized, read_pgm, read_ppm, to_gray_image, write_pgm,
+        write_ppm,
+    };
+    use crate::colors::{Colormap, Rgba};
+    use math::grid::Grid;
+
+    fn gradient() -> Grid<f64> {
+        Grid::from_fn(|x, y| (x + 4 * y) as f64 / 11.0, 4, 3)
+    }
+
+    #[test]
+    fn pgm_round_trip() {
+        let grid = gradient();
+        let mut buf = vec![];
+        write_pgm(&grid, &mut buf).unwrap();
+        assert!(buf.starts_with(b"P2\n4 3\n255\n0 23 46 70\n"));
+        let read = read_pgm(buf.as_slice()).unwrap();
+        assert_eq!((read.num_cols(), read.num_rows()), (4, 3));
+        for (read, val) in read.iter().zip(grid.iter()) {
+            assert!((read - val).abs() <= 0.5 / 255.0)
+        }
+    }
+
+    #[test]
+    fn ppm_round_trip() {
+        let grid = Grid::from_fn(|x, y| Rgba::new(x as u8 * 100, y as u8, 7, 255), 3, 2);
+        let mut buf = vec![];
+        write_ppm(&grid, &mut buf).unwrap();
+        assert_eq!(read_ppm(buf.as_slice()).unwrap(), grid)
+    }
+
+    #[test]
+    fn plain_text_parsing() {
+        let pgm = "P2 # comment\n# another comment\n2 2 15\n0 15\n 5\n10";
+        let grid = read_pgm(pgm.as_bytes()).unwrap();
+        assert_eq!(grid[(1, 0)], 1.0);
+        assert_eq!(grid[(1, 1)], 10.0 / 15.0);
+
+        assert!(read_pgm("P3\n1 1\n255\n0 0 0".as_bytes()).is_err());
+        assert!(read_pgm("P2\n2 2\n255\n0 0 0".as_bytes()).is_err());
+        assert!(read_pgm("P2\n1 1\n15\n16".as_bytes()).is_err());
+        assert!(read_ppm("P3\n1 1\n255\n0 x 0".as_bytes()).is_err())
+    }
+
+    #[test]
+    fn image_conversion() {
+        let grid = gradient();
+        let image = to_gray_image(&grid);
+        assert_eq!(image.dimensions(), (4, 3));
+        assert_eq!(image.get_pixel(3, 2).0, [255]);
+        let back = from_gray_image(&image);
+        assert!(back
+            .iter()
+            .zip(grid.iter())
+            .all(|(b, g)| (b - g).abs() < 1e-2))
+    }
+
+    #[test]
+    fn colorized() {
+        let grid = normalized(&gradient().map_ref(|v| 10.0 * v - 3.0));
+        assert_eq!(grid[(0, 0)], 0.0);
+        assert_eq!(grid[(3, 2)], 1.0);
+        let heat = colorize(&grid, &Colormap::Heat.gradient());
+        assert_eq!(heat[(0, 0)], Rgba::new(0, 0, 0, 255));
+        assert_eq!(heat[(3, 2)], Rgba::new(255, 255, 255, 255))
+    }
+}
@@ -2,13 +2,22 @@ use gfx_device_gl::{CommandBuffer, Resources};
 use piston_window::TextureContext;
 
 const SCREENSHOT_DIR: &str = "./screenshots";
+const SHORTCUT_DIR: &str = "./shortcuts";
 
 pub type TextureCtx = TextureContext<gfx_device_gl::Factory, Resources, CommandBuffer>;
 pub type Graphics<'a> = gfx_graphics::GfxGraphics<'a, Resources, CommandBuffer>;
 
 pub mod app;
+pub mod canvas;
 pub mod colors;
+pub mod fonts;
+pub mod grid_image;
+pub mod particles;
+pub mod path;
 pub mod poly_outline;
+pub mod shortcuts;
+pub mod sprite;
+pub mod text;
 mod traits;
 pub mod ui_elements;
 
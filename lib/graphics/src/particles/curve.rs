@@ -0,0 +1,58 @@
+/// Value that changes over the life of a particle, linearly interpolated between points.
+/// Before the first and after the last point the curve stays constant
+#[derive(Debug, Clone, PartialEq)]
+pub struct Curve {
+    /// sorted by position
+    points: Vec<(f64, f64)>,
+}
+
+impl Curve {
+    pub fn new(mut points: Vec<(f64, f64)>) -> Curve {
+        assert!(!points.is_empty(), "curve without points");
+        points.sort_by(|(pos1, _), (pos2, _)| pos1.total_cmp(pos2));
+        Curve { points }
+    }
+
+    pub fn constant(value: f64) -> Curve {
+        Curve::new(vec![(0.0, value)])
+    }
+
+    /// From `start` at birth to `end` at death
+    pub fn linear(start: f64, end: f64) -> Curve {
+        Curve::new(vec![(0.0, start), (1.0, end)])
+    }
+
+    pub fn points(&self) -> &[(f64, f64)] {
+        &self.points
+    }
+
+    pub fn map(&self, t: f64) -> f64 {
+        let next = self.points.partition_point(|(pos, _)| *pos <= t);
+        if next == 0 {
+            return self.points[0].1;
+        }
+        if next == self.points.len() {
+            return self.points[next - 1].1;
+        }
+        let (pos1, val1) = self.points[next - 1];
+        let (pos2, val2) = self.points[next];
+        val1 + (val2 - val1) * (t - pos1) / (pos2 - pos1)
+    }
+}
+
+#[cfg(test)]
+mod curve_tests {
+    use super::Curve;
+
+    #[test]
+    fn map() {
+        let curve = Curve::new(vec![(1.0, 0.0), (0.0, 2.0), (0.5, 4.0)]);
+        assert_eq!(curve.map(-1.0), 2.0);
+        assert_eq!(curve.map(0.25), 3.0);
+        assert_eq!(curve.map(0.5), 4.0);
+        assert_eq!(curve.map(0.75), 2.0);
+        assert_eq!(curve.map(2.0), 0.0);
+        assert_eq!(Curve::constant(3.0).map(0.7), 3.0);
+        assert_eq!(Curve::linear(1.0, 0.0).map(0.25), 0.75)
+    }
+}
@@ -0,0 +1,186 @@
+use super::{Curve, Particle};
+use crate::colors::{rgba, Gradient, Palette, Rgba};
+use math::{rand_between, vec2d::Vec2D};
+use std::{f64::consts::PI, ops::Range};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Shape {
+    #[default]
+    Square,
+    Circle,
+    /// line along the velocity, longer for faster particles
+    Streak,
+}
+
+/// Spawns particles and decides how they look over their life
+#[derive(Debug, Clone)]
+pub struct Emitter {
+    pub pos: Vec2D,
+    /// particles are spawned anywhere in the rectangle from `pos` to `pos + area`
+    area: Vec2D,
+    /// particles per second
+    rate: f64,
+    /// fraction of a particle left over from the last spawn
+    pending: f64,
+    life_time: Range<f64>,
+    speed: Range<f64>,
+    /// angle of the velocity in radians, spread evenly by `spread` around `direction`
+    direction: f64,
+    spread: f64,
+    acceleration: Vec2D,
+    /// exponential decay rate of the velocity, it is scaled by `exp(-drag * dt)`
+    /// each step so a drag of ln 2 halves the speed every second
+    drag: f64,
+    colors: Gradient,
+    start_colors: Option<Palette>,
+    size: Curve,
+    shape: Shape,
+}
+
+impl Emitter {
+    pub fn new(pos: Vec2D) -> Emitter {
+        Emitter {
+            pos,
+            area: Vec2D::default(),
+            rate: 0.0,
+            pending: 0.0,
+            life_time: 1.0..1.0,
+            speed: 0.0..0.0,
+            direction: 0.0,
+            spread: 2.0 * PI,
+            acceleration: Vec2D::default(),
+            drag: 0.0,
+            colors: Gradient::even(&[rgba::WHITE]),
+            start_colors: None,
+            size: Curve::constant(1.0),
+            shape: Shape::default(),
+        }
+    }
+
+    pub fn with_area(mut self, area: Vec2D) -> Emitter {
+        self.area = area;
+        self
+    }
+
+    /// Particles spawned per second, 0 only spawns bursts
+    pub fn with_rate(mut self, rate: f64) -> Emitter {
+        self.rate = rate;
+        self
+    }
+
+    /// Life time in seconds picked at random for every particle
+    pub fn with_life_time(mut self, life_time: Range<f64>) -> Emitter {
+        self.life_time = life_time;
+        self
+    }
+
+    pub fn with_speed(mut self, speed: Range<f64>) -> Emitter {
+        self.speed = speed;
+        self
+    }
+
+    /// Particles move towards `direction` (radians) give or take half of `spread`
+    pub fn with_direction(mut self, direction: f64, spread: f64) -> Emitter {
+        self.direction = direction;
+        self.spread = spread;
+        self
+    }
+
+    /// Acceleration only for the particles of this emitter, added to the forces of the system
+    pub fn with_acceleration(mut self, acceleration: Vec2D) -> Emitter {
+        self.acceleration = acceleration;
+        self
+    }
+
+    pub fn with_drag(mut self, drag: f64) -> Emitter {
+        self.drag = drag;
+        self
+    }
+
+    /// Color over the life of a particle, multiplied with its tint
+    pub fn with_colors(mut self, colors: Gradient) -> Emitter {
+        self.colors = colors;
+        self
+    }
+
+    /// Every particle is tinted with a random color of `palette` when it is spawned
+    pub fn with_start_colors(mut self, palette: Palette) -> Emitter {
+        self.start_colors = Some(palette);
+        self
+    }
+
+    /// Size in pixels over the life of a particle
+    pub fn with_size(mut self, size: Curve) -> Emitter {
+        self.size = size;
+        self
+    }
+
+    pub fn with_shape(mut self, shape: Shape) -> Emitter {
+        self.shape = shape;
+        self
+    }
+
+    pub fn set_area(&mut self, area: Vec2D) {
+        self.area = area;
+    }
+
+    pub fn rate(&self) -> f64 {
+        self.rate
+    }
+
+    pub fn set_rate(&mut self, rate: f64) {
+        self.rate = rate;
+    }
+
+    pub fn shape(&self) -> Shape {
+        self.shape
+    }
+
+    pub fn acceleration(&self) -> Vec2D {
+        self.acceleration
+    }
+
+    pub fn drag(&self) -> f64 {
+        self.drag
+    }
+
+    pub fn color(&self, particle: &Particle) -> Rgba {
+        let color = self.colors.map(particle.progress());
+        let tint = particle.tint;
+        let mult = |val1: u8, val2: u8| (val1 as u16 * val2 as u16 / 255) as u8;
+        Rgba::new(
+            mult(color.r, tint.r),
+            mult(color.g, tint.g),
+            mult(color.b, tint.b),
+            mult(color.a, tint.a),
+        )
+    }
+
+    pub fn size(&self, particle: &Particle) -> f64 {
+        self.size.map(particle.progress())
+    }
+
+    /// Number of particles to spawn after `dt` seconds
+    pub(crate) fn num_due(&mut self, dt: f64) -> usize {
+        self.pending += self.rate * dt;
+        let num = self.pending.floor();
+        self.pending -= num;
+        num as usize
+    }
+
+    /// A new particle in the spawning area around `pos`
+    pub(crate) fn spawn(&self, pos: Vec2D, ind: usize) -> Particle {
+        let offset = Vec2D::new(
+            rand_between(0.0, self.area.x),
+            rand_between(0.0, self.area.y),
+        );
+        let angle = self.direction + rand_between(-self.spread / 2.0, self.spread / 2.0);
+        let vel = Vec2D::from_polar(rand_between(self.speed.start, self.speed.end), angle);
+        let life_time = rand_between(self.life_time.start, self.life_time.end);
+        let mut particle = Particle::new(pos + offset, vel, life_time, ind);
+        if let Some(palette) = &self.start_colors {
+            particle.tint = palette.sample();
+        }
+        particle
+    }
+}
@@ -0,0 +1,63 @@
+use super::Particle;
+use math::vec2d::Vec2D;
+
+/// Acceleration acting on every particle of a system,
+/// closures taking a particle work as forces as well
+pub trait Force {
+    fn acceleration(&self, particle: &Particle) -> Vec2D;
+}
+
+impl<F: Fn(&Particle) -> Vec2D> Force for F {
+    fn acceleration(&self, particle: &Particle) -> Vec2D {
+        self(particle)
+    }
+}
+
+/// The same acceleration everywhere
+pub struct Gravity(pub Vec2D);
+
+impl Gravity {
+    /// Pulls down with `strength` in pixels per second squared
+    pub fn down(strength: f64) -> Gravity {
+        Gravity(Vec2D::new(0.0, strength))
+    }
+}
+
+impl Force for Gravity {
+    fn acceleration(&self, _: &Particle) -> Vec2D {
+        self.0
+    }
+}
+
+/// Drags particles along until they move with the wind,
+/// `strength` is the fraction of the difference in velocity that is matched per second
+pub struct Wind {
+    pub vel: Vec2D,
+    pub strength: f64,
+}
+
+impl Force for Wind {
+    fn acceleration(&self, particle: &Particle) -> Vec2D {
+        (self.vel - particle.vel) * self.strength
+    }
+}
+
+/// Pulls particles towards `pos` with `strength / dist²`, negative strengths repel.
+/// Distances below `min_dist` count as `min_dist` so particles are not flung away
+pub struct Attractor {
+    pub pos: Vec2D,
+    pub strength: f64,
+    pub min_dist: f64,
+}
+
+impl Force for Attractor {
+    fn acceleration(&self, particle: &Particle) -> Vec2D {
+        let between = self.pos - particle.pos;
+        let dist = between.abs();
+        if dist == 0.0 {
+            return Vec2D::default();
+        }
+        let clamped = dist.max(self.min_dist);
+        between * (self.strength / (clamped * clamped * dist))
+    }
+}
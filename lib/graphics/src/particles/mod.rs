@@ -0,0 +1,11 @@
+pub mod curve;
+pub mod emitter;
+pub mod force;
+pub mod particle;
+pub mod system;
+
+pub use curve::Curve;
+pub use emitter::{Emitter, Shape};
+pub use force::{Attractor, Force, Gravity, Wind};
+pub use particle::Particle;
+pub use system::ParticleSystem;
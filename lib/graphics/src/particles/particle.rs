@@ -0,0 +1,40 @@
+use crate::colors::{rgba, Rgba};
+use math::vec2d::Vec2D;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Particle {
+    pub pos: Vec2D,
+    pub vel: Vec2D,
+    /// seconds since the particle was spawned
+    pub age: f64,
+    pub life_time: f64,
+    /// multiplied with the color of the emitter, white keeps it unchanged
+    pub tint: Rgba,
+    /// index of the emitter in its system
+    pub emitter: usize,
+}
+
+impl Particle {
+    pub fn new(pos: Vec2D, vel: Vec2D, life_time: f64, emitter: usize) -> Particle {
+        Particle {
+            pos,
+            vel,
+            age: 0.0,
+            life_time,
+            tint: rgba::WHITE,
+            emitter,
+        }
+    }
+
+    /// Fraction of the life time that has passed, between 0 and 1
+    pub fn progress(&self) -> f64 {
+        if self.life_time <= 0.0 {
+            return 1.0;
+        }
+        (self.age / self.life_time).min(1.0)
+    }
+
+    pub fn is_alive(&self) -> bool {
+        self.age < self.life_time
+    }
+}
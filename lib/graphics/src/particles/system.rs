@@ -0,0 +1,300 @@
+use super::{Emitter, Force, Particle, Shape};
+use crate::{
+    ellipse, line, rectangle, Drawable, DrawingContext, Graphics, Updatable, UpdateContext,
+};
+use math::vec2d::Vec2D;
+
+/// Streaks are as long as the distance a particle moves in this many seconds
+const STREAK_TIME: f64 = 0.05;
+
+/// Emitters and forces with the particles they spawned. Particles are kept in one pool,
+/// the slots of dead ones are reused by the next spawns
+pub struct ParticleSystem {
+    emitters: Vec<Emitter>,
+    forces: Vec<Box<dyn Force>>,
+    /// the first `num_alive` particles are alive
+    particles: Vec<Particle>,
+    num_alive: usize,
+    max_particles: usize,
+    /// particles that died during the last step
+    died: Vec<Particle>,
+}
+
+impl ParticleSystem {
+    pub fn new() -> ParticleSystem {
+        ParticleSystem {
+            emitters: vec![],
+            forces: vec![],
+            particles: vec![],
+            num_alive: 0,
+            max_particles: usize::MAX,
+            died: vec![],
+        }
+    }
+
+    /// Spawns are skipped while `max_particles` are alive
+    pub fn with_max_particles(mut self, max_particles: usize) -> ParticleSystem {
+        self.max_particles = max_particles;
+        self
+    }
+
+    /// Returns the index of the emitter, particles refer to their emitter by it
+    pub fn add_emitter(&mut self, emitter: Emitter) -> usize {
+        self.emitters.push(emitter);
+        self.emitters.len() - 1
+    }
+
+    pub fn emitter(&self, ind: usize) -> &Emitter {
+        &self.emitters[ind]
+    }
+
+    pub fn emitter_mut(&mut self, ind: usize) -> &mut Emitter {
+        &mut self.emitters[ind]
+    }
+
+    pub fn add_force<F: Force + 'static>(&mut self, force: F) {
+        self.forces.push(Box::new(force));
+    }
+
+    pub fn clear_forces(&mut self) {
+        self.forces.clear();
+    }
+
+    /// The particles that are alive, in no particular order
+    pub fn particles(&self) -> &[Particle] {
+        &self.particles[..self.num_alive]
+    }
+
+    pub fn len(&self) -> usize {
+        self.num_alive
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.num_alive == 0
+    }
+
+    /// Particles that died during the last step, for example to spawn new ones where they were
+    pub fn died(&self) -> &[Particle] {
+        &self.died
+    }
+
+    /// Removes every particle, emitters and forces stay
+    pub fn clear(&mut self) {
+        self.num_alive = 0;
+        self.died.clear();
+    }
+
+    /// Spawns `num` particles of the emitter at once and returns them to adjust them further
+    pub fn burst(&mut self, emitter: usize, num: usize) -> &mut [Particle] {
+        let pos = self.emitters[emitter].pos;
+        self.burst_at(emitter, pos, num)
+    }
+
+    /// Like `burst`, but with the spawning area of the emitter moved to `pos`
+    pub fn burst_at(&mut self, emitter: usize, pos: Vec2D, num: usize) -> &mut [Particle] {
+        let start = self.num_alive;
+        let num = num.min(self.max_particles.saturating_sub(self.num_alive));
+        for _ in 0..num {
+            let particle = self.emitters[emitter].spawn(pos, emitter);
+            if self.num_alive < self.particles.len() {
+                self.particles[self.num_alive] = particle;
+            } else {
+                self.particles.push(particle);
+            }
+            self.num_alive += 1;
+        }
+        &mut self.particles[start..self.num_alive]
+    }
+
+    /// Ages and moves every particle by `dt` seconds, then spawns the particles due
+    pub fn step(&mut self, dt: f64) {
+        self.died.clear();
+        let mut ind = 0;
+        while ind < self.num_alive {
+            let particle = &mut self.particles[ind];
+            particle.age += dt;
+            if !particle.is_alive() {
+                self.died.push(*particle);
+                self.num_alive -= 1;
+                self.particles.swap(ind, self.num_alive);
+                continue;
+            }
+
+            let emitter = &self.emitters[particle.emitter];
+            let mut acc = emitter.acceleration();
+            for force in self.forces.iter() {
+                acc += force.acceleration(particle);
+            }
+            particle.vel += acc * dt;
+            particle.vel *= (-emitter.drag() * dt).exp();
+            particle.pos += particle.vel * dt;
+            ind += 1;
+        }
+
+        for emitter in 0..self.emitters.len() {
+            let num = self.emitters[emitter].num_due(dt);
+            self.burst(emitter, num);
+        }
+    }
+}
+
+impl Default for ParticleSystem {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drawable for ParticleSystem {
+    fn draw(&self, ctx: &mut DrawingContext, gl: &mut Graphics) {
+        let transform = ctx.id_trans();
+        for particle in self.particles() {
+            let emitter = &self.emitters[particle.emitter];
+            let color = emitter.color(particle).into();
+            let size = emitter.size(particle);
+            let pos = particle.pos;
+            let rect = [pos.x - size / 2.0, pos.y - size / 2.0, size, size];
+            match emitter.shape() {
+                Shape::Square => rectangle(color, rect, transform, gl),
+                Shape::Circle => ellipse(color, rect, transform, gl),
+                Shape::Streak => {
+                    let tail = pos - particle.vel * STREAK_TIME;
+                    line(
+                        color,
+                        size / 2.0,
+                        [tail.x, tail.y, pos.x, pos.y],
+                        transform,
+                        gl,
+                    )
+                }
+            }
+        }
+    }
+}
+
+impl Updatable for ParticleSystem {
+    fn update(&mut self, ctx: &mut UpdateContext) {
+        self.step(ctx.args.dt);
+    }
+}
+
+#[cfg(test)]
+mod particle_system_tests {
+    use super::ParticleSystem;
+    use crate::{
+        colors::{rgba, Gradient, Palette, Rgba},
+        particles::{Attractor, Curve, Emitter, Force, Gravity, Particle, Wind},
+    };
+    use math::vec2d::Vec2D;
+
+    fn assert_close(vec: Vec2D, x: f64, y: f64) {
+        assert!(
+            (vec.x - x).abs() < 1e-9 && (vec.y - y).abs() < 1e-9,
+            "{vec}"
+        )
+    }
+
+    #[test]
+    fn spawn_rate() {
+        let mut system = ParticleSystem::new();
+        system.add_emitter(
+            Emitter::new(Vec2D::default())
+                .with_rate(4.0)
+                .with_life_time(10.0..10.0),
+        );
+        for _ in 0..5 {
+            system.step(0.125);
+        }
+        assert_eq!(system.len(), 2);
+        system.step(0.125);
+        assert_eq!(system.len(), 3);
+        system.emitter_mut(0).set_rate(0.0);
+        system.step(0.5);
+        assert_eq!(system.len(), 3);
+    }
+
+    #[test]
+    fn life_time_and_pool() {
+        let mut system = ParticleSystem::new().with_max_particles(8);
+        let emitter =
+            system.add_emitter(Emitter::new(Vec2D::new(1.0, 2.0)).with_life_time(1.0..1.0));
+        assert_eq!(system.burst(emitter, 5).len(), 5);
+        system.step(0.5);
+        assert_eq!(system.burst(emitter, 5).len(), 3);
+        assert_eq!(system.len(), 8);
+
+        system.step(0.6);
+        assert_eq!(system.len(), 3);
+        assert_eq!(system.died().len(), 5);
+        assert!(system.died().iter().all(|p| p.pos == Vec2D::new(1.0, 2.0)));
+        system.burst(emitter, 5);
+        // dead slots are reused
+        assert_eq!(system.particles.len(), 8);
+        system.step(0.0);
+        assert!(system.died().is_empty());
+    }
+
+    #[test]
+    fn motion() {
+        let mut system = ParticleSystem::new();
+        let emitter = system.add_emitter(
+            Emitter::new(Vec2D::default())
+                .with_life_time(10.0..10.0)
+                .with_speed(2.0..2.0)
+                .with_direction(0.0, 0.0),
+        );
+        system.add_force(Gravity::down(1.0));
+        system.burst(emitter, 1);
+        system.step(1.0);
+        let particle = system.particles()[0];
+        assert_close(particle.vel, 2.0, 1.0);
+        assert_close(particle.pos, 2.0, 1.0);
+
+        // drag of ln 2 halves the speed every second
+        let mut drag = ParticleSystem::new();
+        drag.add_emitter(system.emitter(emitter).clone().with_drag(2.0f64.ln()));
+        drag.burst(0, 1);
+        drag.step(1.0);
+        assert_close(drag.particles()[0].vel, 1.0, 0.0);
+    }
+
+    #[test]
+    fn forces() {
+        let particle = Particle::new(Vec2D::new(3.0, 0.0), Vec2D::new(1.0, 0.0), 1.0, 0);
+        let wind = Wind {
+            vel: Vec2D::new(3.0, 1.0),
+            strength: 0.5,
+        };
+        assert_close(Force::acceleration(&wind, &particle), 1.0, 0.5);
+        let attractor = Attractor {
+            pos: Vec2D::default(),
+            strength: 9.0,
+            min_dist: 1.0,
+        };
+        assert_close(Force::acceleration(&attractor, &particle), -1.0, 0.0);
+        let close = Attractor {
+            min_dist: 10.0,
+            ..attractor
+        };
+        assert_close(Force::acceleration(&close, &particle), -0.09, 0.0);
+        let custom = |p: &Particle| p.vel * 2.0;
+        assert_close(Force::acceleration(&custom, &particle), 2.0, 0.0);
+    }
+
+    #[test]
+    fn over_life_time() {
+        let emitter = Emitter::new(Vec2D::default())
+            .with_life_time(2.0..2.0)
+            .with_colors(Gradient::even(&[rgba::WHITE, rgba::BLACK]))
+            .with_start_colors(Palette::new(vec![rgba::RED]))
+            .with_size(Curve::linear(4.0, 0.0));
+        let mut particle = emitter.spawn(Vec2D::default(), 0);
+        assert_eq!(particle.tint, rgba::RED);
+        assert_eq!(emitter.color(&particle), rgba::RED);
+        assert_eq!(emitter.size(&particle), 4.0);
+        particle.age = 1.0;
+        assert_eq!(emitter.size(&particle), 2.0);
+        particle.age = 2.0;
+        assert_eq!(emitter.color(&particle), Rgba::new(0, 0, 0, 255));
+        assert_eq!(emitter.size(&particle), 0.0);
+    }
+}
@@ -0,0 +1,65 @@
+use math::{geometry::Polygon, vec2d::Vec2D};
+
+/// Points without consecutive duplicates, the last point is dropped if it equals the first
+pub(crate) fn dedup(points: &[Vec2D], closed: bool) -> Vec<Vec2D> {
+    let mut unique: Vec<Vec2D> = Vec::with_capacity(points.len());
+    for pt in points {
+        if unique.last() != Some(pt) {
+            unique.push(*pt);
+        }
+    }
+    if closed && unique.len() > 1 && unique.first() == unique.last() {
+        unique.pop();
+    }
+    unique
+}
+
+fn inside_triangle(pt: Vec2D, tri: [Vec2D; 3]) -> bool {
+    let [a, b, c] = tri;
+    (b - a).cross(pt - a) > 0.0 && (c - b).cross(pt - b) > 0.0 && (a - c).cross(pt - c) > 0.0
+}
+
+/// Triangles covering the polygon through `points` by ear clipping, in either orientation.
+/// Holes are not supported, self-intersecting outlines give overlapping triangles
+pub fn triangulate(points: &[Vec2D]) -> Vec<[Vec2D; 3]> {
+    let mut verts = dedup(points, true);
+    if verts.len() < 3 {
+        return vec![];
+    }
+    if Polygon::new(verts.clone()).signed_area() < 0.0 {
+        verts.reverse();
+    }
+
+    let mut remaining: Vec<usize> = (0..verts.len()).collect();
+    let mut triangles = Vec::with_capacity(verts.len() - 2);
+    let mut cur = 0;
+    // vertices checked since the last ear, every vertex failing means the outline intersects itself
+    let mut failed = 0;
+    while remaining.len() > 3 {
+        let num = remaining.len();
+        let prev = remaining[(cur + num - 1) % num];
+        let next = remaining[(cur + 1) % num];
+        let tri = [verts[prev], verts[remaining[cur]], verts[next]];
+        let convex = (tri[1] - tri[0]).cross(tri[2] - tri[1]) >= 0.0;
+        let is_ear = convex
+            && !remaining
+                .iter()
+                .filter(|ind| ![prev, remaining[cur], next].contains(ind))
+                .any(|ind| inside_triangle(verts[*ind], tri));
+        if is_ear || failed >= num {
+            triangles.push(tri);
+            remaining.remove(cur);
+            failed = 0;
+            cur %= remaining.len();
+        } else {
+            cur = (cur + 1) % num;
+            failed += 1;
+        }
+    }
+    triangles.push([
+        verts[remaining[0]],
+        verts[remaining[1]],
+        verts[remaining[2]],
+    ]);
+    triangles
+}
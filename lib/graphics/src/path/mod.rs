@@ -0,0 +1,445 @@
+pub mod fill;
+pub mod stroke;
+
+pub use stroke::{LineCap, LineJoin, StrokeStyle};
+
+use crate::{colors::Rgba, DrawingContext, Graphics};
+use graphics::{
+    triangulation::{tx, ty},
+    Graphics as _, BACK_END_MAX_VERTEX_COUNT,
+};
+use math::vec2d::Vec2D;
+use std::f64::consts::PI;
+
+/// Curves are flattened so they differ from the line segments by at most this many pixels
+pub const DRAW_TOLERANCE: f64 = 0.25;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PathCommand {
+    MoveTo(Vec2D),
+    LineTo(Vec2D),
+    /// control point and end point
+    QuadTo(Vec2D, Vec2D),
+    /// two control points and end point
+    CubicTo(Vec2D, Vec2D, Vec2D),
+    /// line back to the start of the contour
+    Close,
+}
+
+/// Points of one contour after curves were flattened into line segments
+#[derive(Debug, Clone, PartialEq)]
+pub struct Contour {
+    pub points: Vec<Vec2D>,
+    pub closed: bool,
+}
+
+/// Outline made of lines and Bézier curves that can be filled or stroked.
+/// Every `move_to` starts a new contour, drawing commands without one start at the origin
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Path {
+    commands: Vec<PathCommand>,
+    /// start of the current contour, the target of `close`
+    start: Vec2D,
+    current: Option<Vec2D>,
+}
+
+impl Path {
+    pub fn new() -> Path {
+        Path::default()
+    }
+
+    /// Straight lines through the points
+    pub fn polyline(points: &[Vec2D], closed: bool) -> Path {
+        let mut path = Path::new();
+        for (ind, pt) in points.iter().enumerate() {
+            if ind == 0 {
+                path.move_to(*pt);
+            } else {
+                path.line_to(*pt);
+            }
+        }
+        if closed {
+            path.close();
+        }
+        path
+    }
+
+    pub fn rect(x: f64, y: f64, width: f64, height: f64) -> Path {
+        Path::polyline(
+            &[
+                Vec2D::new(x, y),
+                Vec2D::new(x + width, y),
+                Vec2D::new(x + width, y + height),
+                Vec2D::new(x, y + height),
+            ],
+            true,
+        )
+    }
+
+    pub fn circle(center: Vec2D, radius: f64) -> Path {
+        let mut path = Path::new();
+        path.arc(center, radius, 0.0, 2.0 * PI).close();
+        path
+    }
+
+    pub fn commands(&self) -> &[PathCommand] {
+        &self.commands
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
+
+    /// The end point of the last command
+    pub fn current(&self) -> Option<Vec2D> {
+        self.current
+    }
+
+    pub fn move_to(&mut self, pt: Vec2D) -> &mut Path {
+        self.commands.push(PathCommand::MoveTo(pt));
+        self.start = pt;
+        self.current = Some(pt);
+        self
+    }
+
+    fn ensure_start(&mut self) {
+        if self.current.is_none() {
+            self.move_to(Vec2D::default());
+        }
+    }
+
+    pub fn line_to(&mut self, pt: Vec2D) -> &mut Path {
+        self.ensure_start();
+        self.commands.push(PathCommand::LineTo(pt));
+        self.current = Some(pt);
+        self
+    }
+
+    pub fn quad_to(&mut self, ctrl: Vec2D, pt: Vec2D) -> &mut Path {
+        self.ensure_start();
+        self.commands.push(PathCommand::QuadTo(ctrl, pt));
+        self.current = Some(pt);
+        self
+    }
+
+    pub fn cubic_to(&mut self, ctrl1: Vec2D, ctrl2: Vec2D, pt: Vec2D) -> &mut Path {
+        self.ensure_start();
+        self.commands.push(PathCommand::CubicTo(ctrl1, ctrl2, pt));
+        self.current = Some(pt);
+        self
+    }
+
+    /// Circular arc from `start_angle` to `end_angle` (radians, clockwise on screen
+    /// for increasing angles). A line connects the current point with the start of the arc,
+    /// without a current point the arc starts a new contour
+    pub fn arc(
+        &mut self,
+        center: Vec2D,
+        radius: f64,
+        start_angle: f64,
+        end_angle: f64,
+    ) -> &mut Path {
+        let start = center + Vec2D::from_polar(radius, start_angle);
+        if self.current.is_none() {
+            self.move_to(start);
+        } else {
+            self.line_to(start);
+        }
+        // cubics approximate arcs of up to a quarter circle closely
+        let sweep = end_angle - start_angle;
+        let num = (sweep.abs() / (PI / 2.0)).ceil().max(1.0) as usize;
+        let step = sweep / num as f64;
+        let handle = 4.0 / 3.0 * (step / 4.0).tan() * radius;
+        for ind in 0..num {
+            let angle1 = start_angle + ind as f64 * step;
+            let angle2 = angle1 + step;
+            let pt1 = center + Vec2D::from_polar(radius, angle1);
+            let pt2 = center + Vec2D::from_polar(radius, angle2);
+            let ctrl1 = pt1 + Vec2D::from_polar(handle, angle1 + PI / 2.0);
+            let ctrl2 = pt2 - Vec2D::from_polar(handle, angle2 + PI / 2.0);
+            self.cubic_to(ctrl1, ctrl2, pt2);
+        }
+        self
+    }
+
+    pub fn close(&mut self) -> &mut Path {
+        if self.current.is_some() {
+            self.commands.push(PathCommand::Close);
+            self.current = Some(self.start);
+        }
+        self
+    }
+
+    /// The contours with curves replaced by line segments that are at most `tolerance` off,
+    /// contours of a single point are left out
+    pub fn flatten(&self, tolerance: f64) -> Vec<Contour> {
+        let mut contours = vec![];
+        let mut points: Vec<Vec2D> = vec![];
+        let mut start = Vec2D::default();
+        for command in self.commands.iter() {
+            // drawing on after `close` continues from the start of the closed contour
+            if points.is_empty() {
+                points.push(start);
+            }
+            let last = points[points.len() - 1];
+            match *command {
+                PathCommand::MoveTo(pt) => {
+                    if points.len() > 1 {
+                        contours.push(Contour {
+                            points: std::mem::take(&mut points),
+                            closed: false,
+                        });
+                    }
+                    points = vec![pt];
+                    start = pt;
+                }
+                PathCommand::LineTo(pt) => points.push(pt),
+                PathCommand::QuadTo(ctrl, pt) => {
+                    let num = num_segments(2.0 * (last - 2.0 * ctrl + pt).abs(), tolerance);
+                    for ind in 1..=num {
+                        let t = ind as f64 / num as f64;
+                        let s = 1.0 - t;
+                        points.push(s * s * last + 2.0 * s * t * ctrl + t * t * pt);
+                    }
+                }
+                PathCommand::CubicTo(ctrl1, ctrl2, pt) => {
+                    let bend = (last - 2.0 * ctrl1 + ctrl2)
+                        .abs()
+                        .max((ctrl1 - 2.0 * ctrl2 + pt).abs());
+                    let num = num_segments(6.0 * bend, tolerance);
+                    for ind in 1..=num {
+                        let t = ind as f64 / num as f64;
+                        let s = 1.0 - t;
+                        points.push(
+                            s * s * s * last
+                                + 3.0 * s * s * t * ctrl1
+                                + 3.0 * s * t * t * ctrl2
+                                + t * t * t * pt,
+                        );
+                    }
+                }
+                PathCommand::Close => contours.push(Contour {
+                    points: std::mem::take(&mut points),
+                    closed: true,
+                }),
+            }
+        }
+        contours.push(Contour {
+            points,
+            closed: false,
+        });
+        contours.retain(|contour| contour.points.len() > 1);
+        contours
+    }
+
+    /// Triangles covering the inside of every contour, see `fill::triangulate`
+    pub fn fill_triangles(&self, tolerance: f64) -> Vec<[Vec2D; 3]> {
+        self.flatten(tolerance)
+            .iter()
+            .flat_map(|contour| fill::triangulate(&contour.points))
+            .collect()
+    }
+
+    /// Triangles covering the outline of every contour
+    pub fn stroke_triangles(&self, style: &StrokeStyle, tolerance: f64) -> Vec<[Vec2D; 3]> {
+        self.flatten(tolerance)
+            .iter()
+            .flat_map(|contour| stroke::stroke(contour, style, tolerance))
+            .collect()
+    }
+
+    pub fn draw_fill(
+        &self,
+        color: Rgba,
+        transform: [[f64; 3]; 2],
+        ctx: &DrawingContext,
+        gl: &mut Graphics,
+    ) {
+        let triangles = self.fill_triangles(tolerance(transform, ctx.context.view));
+        draw_triangles(&triangles, color, transform, ctx, gl);
+    }
+
+    /// The stroke triangles overlap at corners, use an opaque `color`
+    pub fn draw_stroke(
+        &self,
+        style: &StrokeStyle,
+        color: Rgba,
+        transform: [[f64; 3]; 2],
+        ctx: &DrawingContext,
+        gl: &mut Graphics,
+    ) {
+        let triangles = self.stroke_triangles(style, tolerance(transform, ctx.context.view));
+        draw_triangles(&triangles, color, transform, ctx, gl);
+    }
+}
+
+/// Segments needed for a curve whose second derivative is at most `bend`
+fn num_segments(bend: f64, tolerance: f64) -> usize {
+    ((bend / (8.0 * tolerance)).sqrt().ceil() as usize).clamp(1, 500)
+}
+
+/// `DRAW_TOLERANCE` in the coordinates before `transform` is applied,
+/// `view` maps window pixels to the same coordinates as `transform`
+fn tolerance(transform: [[f64; 3]; 2], view: [[f64; 3]; 2]) -> f64 {
+    let det = |m: [[f64; 3]; 2]| m[0][0] * m[1][1] - m[0][1] * m[1][0];
+    let scale = (det(transform) / det(view)).abs().sqrt();
+    if !scale.is_normal() {
+        return DRAW_TOLERANCE;
+    }
+    DRAW_TOLERANCE / scale
+}
+
+/// Draws all triangles in one batch
+pub fn draw_triangles(
+    triangles: &[[Vec2D; 3]],
+    color: Rgba,
+    transform: [[f64; 3]; 2],
+    ctx: &DrawingContext,
+    gl: &mut Graphics,
+) {
+    let vertices: Vec<[f32; 2]> = triangles
+        .iter()
+        .flatten()
+        .map(|pt| [tx(transform, pt.x, pt.y), ty(transform, pt.x, pt.y)])
+        .collect();
+    // every chunk has to hold whole triangles
+    let chunk_size = BACK_END_MAX_VERTEX_COUNT / 3 * 3;
+    gl.tri_list(&ctx.context.draw_state, &color.into(), |draw| {
+        for chunk in vertices.chunks(chunk_size) {
+            draw(chunk);
+        }
+    });
+}
+
+#[cfg(test)]
+mod path_tests {
+    use super::{tolerance, LineCap, LineJoin, Path, PathCommand, StrokeStyle};
+    use math::vec2d::Vec2D;
+    use std::f64::consts::PI;
+
+    fn area(triangles: &[[Vec2D; 3]]) -> f64 {
+        triangles
+            .iter()
+            .map(|[a, b, c]| (*b - *a).cross(*c - *a).abs() / 2.0)
+            .sum()
+    }
+
+    fn pt(x: f64, y: f64) -> Vec2D {
+        Vec2D::new(x, y)
+    }
+
+    #[test]
+    fn builder() {
+        let mut path = Path::new();
+        path.line_to(pt(1.0, 0.0)).close().line_to(pt(0.0, 1.0));
+        assert_eq!(
+            path.commands(),
+            &[
+                PathCommand::MoveTo(pt(0.0, 0.0)),
+                PathCommand::LineTo(pt(1.0, 0.0)),
+                PathCommand::Close,
+                PathCommand::LineTo(pt(0.0, 1.0)),
+            ]
+        );
+        assert_eq!(path.current(), Some(pt(0.0, 1.0)));
+        let contours = path.flatten(0.1);
+        assert_eq!(contours.len(), 2);
+        assert!(contours[0].closed && !contours[1].closed);
+        assert_eq!(contours[1].points, vec![pt(0.0, 0.0), pt(0.0, 1.0)]);
+    }
+
+    #[test]
+    fn flatten_curves() {
+        let circle = Path::circle(pt(1.0, 2.0), 50.0);
+        let contours = circle.flatten(0.1);
+        assert_eq!(contours.len(), 1);
+        let points = &contours[0].points;
+        assert!(points.len() > 20);
+        for pair in points.windows(2) {
+            let mid = (pair[0] + pair[1]) / 2.0;
+            assert!((pair[0].dist(&pt(1.0, 2.0)) - 50.0).abs() < 0.1);
+            assert!((mid.dist(&pt(1.0, 2.0)) - 50.0).abs() < 0.2);
+        }
+
+        let mut quad = Path::new();
+        quad.move_to(pt(0.0, 0.0))
+            .quad_to(pt(1.0, 2.0), pt(2.0, 0.0));
+        let points = &quad.flatten(0.01)[0].points;
+        assert_eq!(points[points.len() - 1], pt(2.0, 0.0));
+        let top = points.iter().map(|p| p.y).fold(0.0, f64::max);
+        assert!((top - 1.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn window_tolerance() {
+        // piston's view of an 800x900 window, the transform draws around its center
+        let view = [[2.0 / 800.0, 0.0, -1.0], [0.0, -2.0 / 900.0, 1.0]];
+        let centered = [[2.0 / 800.0, 0.0, 0.0], [0.0, -2.0 / 900.0, 0.0]];
+        let zoomed = [[4.0 / 800.0, 0.0, 0.0], [0.0, -4.0 / 900.0, 0.0]];
+        assert!((tolerance(centered, view) - 0.25).abs() < 1e-12);
+        assert!((tolerance(zoomed, view) - 0.125).abs() < 1e-12);
+
+        let circle = Path::circle(pt(0.0, 0.0), 100.0);
+        let points = &circle.flatten(tolerance(centered, view))[0].points;
+        assert_eq!(points.len() - 1, 48);
+    }
+
+    #[test]
+    fn fill() {
+        let shape = [
+            pt(0.0, 0.0),
+            pt(4.0, 0.0),
+            pt(4.0, 1.0),
+            pt(1.0, 1.0),
+            pt(1.0, 3.0),
+            pt(0.0, 3.0),
+        ];
+        for closed in [true, false] {
+            let path = Path::polyline(&shape, closed);
+            assert!((area(&path.fill_triangles(0.1)) - 6.0).abs() < 1e-9);
+        }
+        let mut reversed = shape;
+        reversed.reverse();
+        assert_eq!(Path::polyline(&reversed, true).fill_triangles(0.1).len(), 4);
+        let circle = Path::circle(pt(0.0, 0.0), 10.0).fill_triangles(0.01);
+        assert!((area(&circle) - PI * 100.0).abs() < 0.5);
+        // self intersecting outlines are covered somehow
+        let bowtie = [pt(0.0, 0.0), pt(2.0, 2.0), pt(2.0, 0.0), pt(0.0, 2.0)];
+        assert_eq!(Path::polyline(&bowtie, true).fill_triangles(0.1).len(), 2);
+    }
+
+    #[test]
+    fn stroke() {
+        let line = Path::polyline(&[pt(0.0, 0.0), pt(10.0, 0.0)], false);
+        let butt = StrokeStyle::new(2.0);
+        assert!((area(&line.stroke_triangles(&butt, 0.1)) - 20.0).abs() < 1e-9);
+        let square = butt.with_cap(LineCap::Square);
+        assert!((area(&line.stroke_triangles(&square, 0.1)) - 24.0).abs() < 1e-9);
+        let round = butt.with_cap(LineCap::Round);
+        let round_area = area(&line.stroke_triangles(&round, 0.01));
+        assert!((round_area - 20.0 - PI).abs() < 0.05);
+
+        // the outer corner of a right angle is a square for miters, a triangle for bevels
+        let corner = Path::polyline(&[pt(0.0, 0.0), pt(10.0, 0.0), pt(10.0, 10.0)], false);
+        let miter = area(&corner.stroke_triangles(&butt, 0.1));
+        let bevel = area(&corner.stroke_triangles(&butt.with_join(LineJoin::Bevel), 0.1));
+        let round = area(&corner.stroke_triangles(&butt.with_join(LineJoin::Round), 0.01));
+        assert!((miter - 41.0).abs() < 1e-9);
+        assert!((bevel - 40.5).abs() < 1e-9);
+        assert!((round - 40.0 - PI / 4.0).abs() < 0.02);
+        // sharp corners fall back to bevels
+        let sharp = Path::polyline(&[pt(0.0, 0.0), pt(10.0, 0.0), pt(0.0, 1.0)], false);
+        let limited = sharp.stroke_triangles(&butt.with_miter_limit(2.0), 0.1);
+        assert_eq!(limited.len(), 5);
+        assert_eq!(
+            sharp
+                .stroke_triangles(&butt.with_miter_limit(100.0), 0.1)
+                .len(),
+            6
+        );
+
+        // closed outlines are joined at the start as well
+        let rect = Path::rect(0.0, 0.0, 10.0, 10.0);
+        assert!((area(&rect.stroke_triangles(&butt, 0.1)) - 84.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,184 @@
+use super::{fill::dedup, Contour};
+use math::vec2d::Vec2D;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LineJoin {
+    /// corners are extended to a point, unless that is longer than the miter limit
+    #[default]
+    Miter,
+    Round,
+    /// corners are cut off
+    Bevel,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LineCap {
+    /// the line ends exactly at its end point
+    #[default]
+    Butt,
+    Round,
+    /// the line goes on for half its width
+    Square,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StrokeStyle {
+    pub width: f64,
+    pub join: LineJoin,
+    pub cap: LineCap,
+    /// longest miter as a multiple of half the width, sharper corners are beveled
+    pub miter_limit: f64,
+}
+
+impl StrokeStyle {
+    pub fn new(width: f64) -> StrokeStyle {
+        StrokeStyle {
+            width,
+            join: LineJoin::default(),
+            cap: LineCap::default(),
+            miter_limit: 4.0,
+        }
+    }
+
+    pub fn with_join(mut self, join: LineJoin) -> StrokeStyle {
+        self.join = join;
+        self
+    }
+
+    pub fn with_cap(mut self, cap: LineCap) -> StrokeStyle {
+        self.cap = cap;
+        self
+    }
+
+    pub fn with_miter_limit(mut self, miter_limit: f64) -> StrokeStyle {
+        self.miter_limit = miter_limit;
+        self
+    }
+}
+
+/// Unit vector to the left of `dir` (to the right on screen)
+fn normal(dir: Vec2D) -> Vec2D {
+    let len = dir.abs();
+    Vec2D::new(-dir.y / len, dir.x / len)
+}
+
+/// Triangle fan around `center` from `from` to `to` (both relative to `center`)
+/// along the shorter way round
+fn fan(center: Vec2D, from: Vec2D, to: Vec2D, tolerance: f64, triangles: &mut Vec<[Vec2D; 3]>) {
+    let radius = from.abs();
+    let sweep = from.cross(to).atan2(from.dot(to));
+    // largest angle whose chord stays within the tolerance
+    let max_step = 2.0 * (1.0 - tolerance / radius).clamp(-1.0, 1.0).acos();
+    let num = (sweep.abs() / max_step.max(1e-3)).ceil().clamp(1.0, 128.0) as usize;
+    let start = from.arg();
+    let mut prev = center + from;
+    for ind in 1..=num {
+        let next = center + Vec2D::from_polar(radius, start + sweep * ind as f64 / num as f64);
+        triangles.push([center, prev, next]);
+        prev = next;
+    }
+}
+
+/// Fills the outer side of the corner at `pt` between the segments with directions `dir_in`
+/// and `dir_out`, the inner side is covered by the overlapping segments
+fn join(
+    pt: Vec2D,
+    dir_in: Vec2D,
+    dir_out: Vec2D,
+    style: &StrokeStyle,
+    tolerance: f64,
+    triangles: &mut Vec<[Vec2D; 3]>,
+) {
+    let turn = dir_in.cross(dir_out);
+    if turn == 0.0 && dir_in.dot(dir_out) > 0.0 {
+        return;
+    }
+    let half = style.width / 2.0;
+    // the outer side is to the right of a left turn
+    let side = if turn > 0.0 { -half } else { half };
+    let offset_in = normal(dir_in) * side;
+    let offset_out = normal(dir_out) * side;
+    match style.join {
+        LineJoin::Bevel => triangles.push([pt, pt + offset_in, pt + offset_out]),
+        LineJoin::Round => fan(pt, offset_in, offset_out, tolerance, triangles),
+        LineJoin::Miter => {
+            let bisector = offset_in + offset_out;
+            // cosine of half the angle between the offsets
+            let cos_half = bisector.abs() / (2.0 * half);
+            if cos_half == 0.0 || 1.0 / cos_half > style.miter_limit {
+                triangles.push([pt, pt + offset_in, pt + offset_out]);
+                return;
+            }
+            let tip = pt + bisector * (half / (cos_half * bisector.abs()));
+            triangles.push([pt, pt + offset_in, tip]);
+            triangles.push([pt, tip, pt + offset_out]);
+        }
+    }
+}
+
+/// Covers the end at `pt` of a line leaving in direction `dir`
+fn cap(
+    pt: Vec2D,
+    dir: Vec2D,
+    style: &StrokeStyle,
+    tolerance: f64,
+    triangles: &mut Vec<[Vec2D; 3]>,
+) {
+    let half = style.width / 2.0;
+    let offset = normal(dir) * half;
+    let back = dir * (-half / dir.abs());
+    match style.cap {
+        LineCap::Butt => (),
+        LineCap::Round => {
+            fan(pt, offset, back, tolerance, triangles);
+            fan(pt, back, -offset, tolerance, triangles);
+        }
+        LineCap::Square => {
+            triangles.push([pt + offset, pt + offset + back, pt - offset + back]);
+            triangles.push([pt + offset, pt - offset + back, pt - offset]);
+        }
+    }
+}
+
+/// Triangles covering the outline of the contour, joins and caps included.
+/// Segments and joins overlap at corners, so translucent strokes blend twice there
+pub fn stroke(contour: &Contour, style: &StrokeStyle, tolerance: f64) -> Vec<[Vec2D; 3]> {
+    let points = dedup(&contour.points, contour.closed);
+    let mut triangles = vec![];
+    if points.len() < 2 || style.width <= 0.0 {
+        return triangles;
+    }
+    let half = style.width / 2.0;
+    let num_segments = if contour.closed {
+        points.len()
+    } else {
+        points.len() - 1
+    };
+    let segment = |ind: usize| (points[ind], points[(ind + 1) % points.len()]);
+
+    for ind in 0..num_segments {
+        let (start, end) = segment(ind);
+        let offset = normal(end - start) * half;
+        triangles.push([start + offset, end + offset, end - offset]);
+        triangles.push([start + offset, end - offset, start - offset]);
+        if ind + 1 < num_segments || contour.closed {
+            let (next_start, next_end) = segment((ind + 1) % num_segments);
+            join(
+                next_start,
+                end - start,
+                next_end - next_start,
+                style,
+                tolerance,
+                &mut triangles,
+            );
+        }
+    }
+
+    if !contour.closed {
+        let (first, second) = segment(0);
+        cap(first, second - first, style, tolerance, &mut triangles);
+        let (before, last) = segment(num_segments - 1);
+        cap(last, before - last, style, tolerance, &mut triangles);
+    }
+    triangles
+}
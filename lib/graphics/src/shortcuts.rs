@@ -0,0 +1,391 @@
+use crate::{
+    colors::{rgba, Rgba},
+    fonts::Font,
+    rectangle,
+    text::{draw_lines, draw_text, layout, width, HAlign, TextStyle, VAlign},
+    DrawingContext, Graphics,
+};
+use math::vec2d::Vec2D;
+use piston::Key;
+use std::{
+    collections::BTreeMap,
+    fmt,
+    fs::File,
+    io::{self, BufRead, BufReader, Read},
+    path::Path,
+};
+
+/// Action of the help overlay, every window has it
+pub const HELP: &str = "help";
+
+const HELP_FONT_SIZE: u32 = 16;
+const HELP_MARGIN: f64 = 30.0;
+/// space between the key column and the descriptions
+const HELP_GAP: f64 = 20.0;
+const OVERLAY_COLOR: Rgba = Rgba {
+    r: 0,
+    g: 0,
+    b: 0,
+    a: 210,
+};
+
+/// Key codes piston knows names for, printable ASCII and the function, arrow and keypad keys
+fn key_codes() -> impl Iterator<Item = u32> {
+    (0..0x80).chain(0x4000_0039..=0x4000_011A)
+}
+
+/// A key with or without shift held down
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Binding {
+    pub key: Key,
+    pub shift: bool,
+}
+
+impl Binding {
+    pub fn new(key: Key) -> Binding {
+        Binding { key, shift: false }
+    }
+
+    pub fn shift(key: Key) -> Binding {
+        Binding { key, shift: true }
+    }
+
+    /// Names are case insensitive like `shift+r`, `Space`, `F1` or `+`.
+    /// Single characters are the keys that type them, longer names are piston's key names
+    pub fn parse(name: &str) -> Option<Binding> {
+        let name = name.trim();
+        let (shift, name) = match name.split_once('+') {
+            Some((modifier, key)) if modifier.trim().eq_ignore_ascii_case("shift") => {
+                (true, key.trim())
+            }
+            _ => (false, name),
+        };
+        let mut chars = name.chars();
+        let key = match (chars.next(), chars.next()) {
+            (Some(ch), None) => Key::from(ch.to_ascii_lowercase() as u32),
+            _ => key_codes()
+                .map(Key::from)
+                .find(|key| format!("{key:?}").eq_ignore_ascii_case(name))?,
+        };
+        (key != Key::Unknown).then_some(Binding { key, shift })
+    }
+}
+
+impl From<Key> for Binding {
+    fn from(key: Key) -> Self {
+        Binding::new(key)
+    }
+}
+
+impl fmt::Display for Binding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.shift {
+            write!(f, "Shift+")?;
+        }
+        match char::from_u32(self.key.code() as u32) {
+            Some(ch) if ch.is_ascii_graphic() => write!(f, "{}", ch.to_ascii_uppercase()),
+            _ => write!(f, "{:?}", self.key),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Action {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub bindings: Vec<Binding>,
+}
+
+impl Action {
+    /// The bindings separated by commas
+    pub fn keys(&self) -> String {
+        let keys: Vec<String> = self.bindings.iter().map(|b| b.to_string()).collect();
+        keys.join(", ")
+    }
+}
+
+fn invalid_data(msg: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+/// Actions of a window and the keys that trigger them, see `Runnable::shortcuts`.
+/// `InputContext::action` is the action bound to the pressed or released key
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Shortcuts {
+    actions: Vec<Action>,
+}
+
+impl Shortcuts {
+    pub fn new() -> Shortcuts {
+        Shortcuts::default()
+    }
+
+    /// Adding an action again only adds the binding, the first description is kept
+    pub fn with_action<B: Into<Binding>>(
+        mut self,
+        name: &'static str,
+        description: &'static str,
+        binding: B,
+    ) -> Shortcuts {
+        if let Some(action) = self.get_mut(name) {
+            action.bindings.push(binding.into());
+            return self;
+        }
+        self.actions.push(Action {
+            name,
+            description,
+            bindings: vec![binding.into()],
+        });
+        self
+    }
+
+    /// Another key for an action that was already added, ignored for unknown actions
+    pub fn with_binding<B: Into<Binding>>(mut self, name: &str, binding: B) -> Shortcuts {
+        let binding = binding.into();
+        match self.get_mut(name) {
+            Some(action) => action.bindings.push(binding),
+            None => println!("Ignoring {binding} for unknown action {name}"),
+        }
+        self
+    }
+
+    pub fn actions(&self) -> &[Action] {
+        &self.actions
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Action> {
+        self.actions.iter().find(|action| action.name == name)
+    }
+
+    fn get_mut(&mut self, name: &str) -> Option<&mut Action> {
+        self.actions.iter_mut().find(|action| action.name == name)
+    }
+
+    /// The action bound to exactly this binding, keys bound without shift also
+    /// match while shift is held so `+` works as shift and `=` on most layouts
+    pub fn action(&self, binding: Binding) -> Option<&'static str> {
+        let find = |binding: Binding| {
+            self.actions
+                .iter()
+                .find(|action| action.bindings.contains(&binding))
+                .map(|action| action.name)
+        };
+        find(binding).or_else(|| find(Binding::new(binding.key)).filter(|_| binding.shift))
+    }
+
+    /// Bindings of more than one action with the names of the actions, only the
+    /// first of them is ever triggered
+    pub fn conflicts(&self) -> Vec<(Binding, Vec<&'static str>)> {
+        let mut actions: BTreeMap<Binding, Vec<&'static str>> = BTreeMap::new();
+        for action in self.actions.iter() {
+            for binding in action.bindings.iter() {
+                let names = actions.entry(*binding).or_default();
+                if !names.contains(&action.name) {
+                    names.push(action.name);
+                }
+            }
+        }
+        actions
+            .into_iter()
+            .filter(|(_, names)| names.len() > 1)
+            .collect()
+    }
+
+    /// Replaces the bindings of the action, `false` if there is no action with the name
+    pub fn remap(&mut self, name: &str, bindings: Vec<Binding>) -> bool {
+        match self.get_mut(name) {
+            Some(action) => {
+                action.bindings = bindings;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Remaps actions from lines like `pause = Space, P`, everything after a `#` is a comment.
+    /// Nothing is remapped if a line names an unknown action or key
+    pub fn read_config<R: Read>(&mut self, reader: R) -> io::Result<()> {
+        let mut remapped = vec![];
+        for (ind, line) in BufReader::new(reader).lines().enumerate() {
+            let line = line?;
+            let line = line.split('#').next().unwrap_or_default().trim();
+            if line.is_empty() {
+                continue;
+            }
+            let line_num = ind + 1;
+            let (name, keys) = line
+                .split_once('=')
+                .ok_or_else(|| invalid_data(format!("line {line_num}: expected action = keys")))?;
+            let name = name.trim();
+            if self.get(name).is_none() {
+                return Err(invalid_data(format!("line {line_num}: no action {name}")));
+            }
+            let bindings = keys
+                .split(',')
+                .filter(|key| !key.trim().is_empty())
+                .map(|key| {
+                    Binding::parse(key)
+                        .ok_or_else(|| invalid_data(format!("line {line_num}: unknown key {key}")))
+                })
+                .collect::<io::Result<Vec<Binding>>>()?;
+            remapped.push((name.to_owned(), bindings));
+        }
+        for (name, bindings) in remapped {
+            self.remap(&name, bindings);
+        }
+        Ok(())
+    }
+
+    pub fn load_config<P: AsRef<Path>>(&mut self, path: P) -> io::Result<()> {
+        self.read_config(File::open(path)?)
+    }
+
+    /// Lists the actions with their keys on top of everything else
+    pub fn draw_help(&self, title: &str, ctx: &mut DrawingContext, gl: &mut Graphics) {
+        let [window_width, window_height] = ctx.args.window_size;
+        let transform = ctx.id_trans();
+        rectangle(
+            OVERLAY_COLOR.into(),
+            [0.0, 0.0, window_width, window_height],
+            transform,
+            gl,
+        );
+
+        let style = TextStyle::new(HELP_FONT_SIZE, rgba::WHITE)
+            .with_font(Font::MONOSPACE)
+            .with_align(HAlign::Left, VAlign::Top);
+        let title_style = TextStyle {
+            size: 2 * HELP_FONT_SIZE,
+            ..style.clone()
+        };
+        let mut y = HELP_MARGIN;
+        draw_text(
+            title,
+            Vec2D::new(HELP_MARGIN, y),
+            &title_style,
+            transform,
+            ctx,
+            gl,
+        );
+        y += 2.0 * title_style.line_height();
+
+        let keys: Vec<String> = self.actions.iter().map(Action::keys).collect();
+        let glyphs = ctx.glyphs_for(&style.font);
+        let key_width = keys
+            .iter()
+            .map(|keys| width(glyphs, keys, style.size))
+            .fold(0.0, f64::max);
+        let desc_x = HELP_MARGIN + key_width + HELP_GAP;
+        let desc_style = style
+            .clone()
+            .with_max_width((window_width - desc_x - HELP_MARGIN).max(0.0));
+        for (action, keys) in self.actions.iter().zip(keys.iter()) {
+            draw_text(keys, Vec2D::new(HELP_MARGIN, y), &style, transform, ctx, gl);
+            let lines = layout(
+                ctx.glyphs_for(&style.font),
+                action.description,
+                Vec2D::new(desc_x, y),
+                &desc_style,
+            );
+            y += lines.len().max(1) as f64 * style.line_height();
+            draw_lines(&lines, &desc_style, transform, ctx, gl);
+        }
+    }
+}
+
+#[cfg(test)]
+mod shortcuts_tests {
+    use super::{Binding, Shortcuts};
+    use piston::Key;
+
+    fn shortcuts() -> Shortcuts {
+        Shortcuts::new()
+            .with_action("pause", "pause and unpause", Key::Space)
+            .with_action("bigger", "zoom in", Key::Plus)
+            .with_binding("bigger", Key::Equals)
+            .with_action("reset", "start over", Binding::shift(Key::R))
+    }
+
+    #[test]
+    fn lookup() {
+        let shortcuts = shortcuts();
+        assert_eq!(shortcuts.action(Key::Space.into()), Some("pause"));
+        assert_eq!(shortcuts.action(Key::Equals.into()), Some("bigger"));
+        // shift + = types a plus
+        assert_eq!(
+            shortcuts.action(Binding::shift(Key::Equals)),
+            Some("bigger")
+        );
+        assert_eq!(shortcuts.action(Binding::shift(Key::R)), Some("reset"));
+        assert_eq!(shortcuts.action(Key::R.into()), None);
+        assert_eq!(shortcuts.get("bigger").unwrap().keys(), "+, =");
+        assert!(shortcuts.conflicts().is_empty());
+    }
+
+    #[test]
+    fn repeated_actions() {
+        let shortcuts = shortcuts()
+            .with_action("pause", "stop", Key::P)
+            .with_binding("jump", Key::J);
+        let pause = shortcuts.get("pause").unwrap();
+        assert_eq!(pause.description, "pause and unpause");
+        assert_eq!(pause.keys(), "Space, P");
+        assert_eq!(shortcuts.actions().len(), 3);
+        assert_eq!(shortcuts.action(Key::J.into()), None);
+    }
+
+    #[test]
+    fn parsing() {
+        assert_eq!(Binding::parse("Space"), Some(Key::Space.into()));
+        assert_eq!(Binding::parse(" f1 "), Some(Key::F1.into()));
+        assert_eq!(Binding::parse("+"), Some(Key::Plus.into()));
+        assert_eq!(Binding::parse("R"), Some(Key::R.into()));
+        assert_eq!(Binding::parse("1"), Some(Key::D1.into()));
+        assert_eq!(
+            Binding::parse("Shift + /"),
+            Some(Binding::shift(Key::Slash))
+        );
+        assert_eq!(Binding::parse("Left"), Some(Key::Left.into()));
+        assert_eq!(Binding::parse("NoSuchKey"), None);
+        assert_eq!(Binding::parse(""), None);
+        for binding in [Binding::shift(Key::Slash), Key::F12.into(), Key::A.into()] {
+            assert_eq!(Binding::parse(&binding.to_string()), Some(binding));
+        }
+        assert_eq!(Binding::shift(Key::Slash).to_string(), "Shift+/");
+    }
+
+    #[test]
+    fn conflicts() {
+        let shortcuts = shortcuts()
+            .with_action("next", "next system", Key::N)
+            .with_binding("reset", Key::N)
+            .with_binding("pause", Key::N);
+        let conflicts = shortcuts.conflicts();
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].0, Key::N.into());
+        assert_eq!(conflicts[0].1, vec!["pause", "reset", "next"]);
+        assert_eq!(shortcuts.action(Key::N.into()), Some("pause"));
+    }
+
+    #[test]
+    fn config() {
+        let mut shortcuts = shortcuts();
+        let config = "# my keys\npause = P, Space\n\nreset = shift+x # was shift+r\n";
+        shortcuts.read_config(config.as_bytes()).unwrap();
+        assert_eq!(shortcuts.action(Key::P.into()), Some("pause"));
+        assert_eq!(shortcuts.action(Binding::shift(Key::X)), Some("reset"));
+        assert_eq!(shortcuts.action(Binding::shift(Key::R)), None);
+
+        for broken in ["pause", "jump = J", "pause = Space, NoSuchKey"] {
+            let mut unchanged = shortcuts.clone();
+            assert!(
+                unchanged.read_config(broken.as_bytes()).is_err(),
+                "{broken}"
+            );
+            assert_eq!(unchanged, shortcuts);
+        }
+        // an action without keys
+        shortcuts.read_config("pause =".as_bytes()).unwrap();
+        assert!(shortcuts.get("pause").unwrap().bindings.is_empty());
+    }
+}
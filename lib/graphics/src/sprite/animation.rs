@@ -0,0 +1,106 @@
+use crate::{Updatable, UpdateContext};
+
+/// Frames of an atlas that are shown one after another for the same time each
+#[derive(Debug, Clone, PartialEq)]
+pub struct Animation {
+    frames: Vec<usize>,
+    frame_time: f64,
+    looping: bool,
+    time: f64,
+}
+
+impl Animation {
+    /// Shows `fps` frames per second and starts over after the last one
+    pub fn new(frames: Vec<usize>, fps: f64) -> Animation {
+        assert!(!frames.is_empty(), "animation without frames");
+        assert!(fps > 0.0, "frames per second have to be positive");
+        Animation {
+            frames,
+            frame_time: 1.0 / fps,
+            looping: true,
+            time: 0.0,
+        }
+    }
+
+    /// Always shows the same frame
+    pub fn still(frame: usize) -> Animation {
+        Animation::new(vec![frame], 1.0)
+    }
+
+    /// Animations that don't loop stay at their last frame
+    pub fn with_looping(mut self, looping: bool) -> Animation {
+        self.looping = looping;
+        self
+    }
+
+    pub fn duration(&self) -> f64 {
+        self.frames.len() as f64 * self.frame_time
+    }
+
+    pub fn step(&mut self, dt: f64) {
+        self.time += dt;
+        if self.looping {
+            self.time %= self.duration();
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.time = 0.0;
+    }
+
+    /// Index of the atlas frame to draw
+    pub fn frame(&self) -> usize {
+        let ind = (self.time / self.frame_time) as usize;
+        if self.looping {
+            self.frames[ind % self.frames.len()]
+        } else {
+            self.frames[ind.min(self.frames.len() - 1)]
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        !self.looping && self.time >= self.duration()
+    }
+}
+
+impl Updatable for Animation {
+    fn update(&mut self, ctx: &mut UpdateContext) {
+        self.step(ctx.args.dt);
+    }
+}
+
+#[cfg(test)]
+mod animation_tests {
+    use super::Animation;
+
+    #[test]
+    fn looping() {
+        let mut anim = Animation::new(vec![3, 5, 4], 4.0);
+        assert_eq!(anim.duration(), 0.75);
+        assert_eq!(anim.frame(), 3);
+        anim.step(0.3);
+        assert_eq!(anim.frame(), 5);
+        anim.step(0.25);
+        assert_eq!(anim.frame(), 4);
+        anim.step(0.25);
+        assert_eq!(anim.frame(), 3);
+        assert!(!anim.is_finished());
+        // many loops at once
+        anim.step(7.5);
+        assert_eq!(anim.frame(), 3);
+        anim.reset();
+        assert_eq!(anim.frame(), 3);
+    }
+
+    #[test]
+    fn once() {
+        let mut anim = Animation::new(vec![0, 1], 10.0).with_looping(false);
+        anim.step(0.15);
+        assert_eq!(anim.frame(), 1);
+        assert!(!anim.is_finished());
+        anim.step(1.0);
+        assert_eq!(anim.frame(), 1);
+        assert!(anim.is_finished());
+        assert_eq!(Animation::still(7).frame(), 7);
+    }
+}
@@ -0,0 +1,229 @@
+use serde::Deserialize;
+use std::{
+    collections::HashMap,
+    io::{self, Read},
+};
+
+/// Rectangle of a sprite sheet in pixels, `x` and `y` are the top left corner
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub struct Frame {
+    pub x: u32,
+    pub y: u32,
+    pub w: u32,
+    pub h: u32,
+}
+
+impl Frame {
+    pub fn new(x: u32, y: u32, w: u32, h: u32) -> Frame {
+        Frame { x, y, w, h }
+    }
+
+    /// `[x, y, w, h]` as used for the source rectangle of piston images
+    pub fn src_rect(&self) -> [f64; 4] {
+        [self.x as f64, self.y as f64, self.w as f64, self.h as f64]
+    }
+
+    pub fn right(&self) -> u32 {
+        self.x + self.w
+    }
+
+    pub fn bottom(&self) -> u32 {
+        self.y + self.h
+    }
+}
+
+#[derive(Deserialize)]
+struct JsonFrame {
+    frame: Frame,
+}
+
+#[derive(Deserialize)]
+struct JsonNamedFrame {
+    filename: String,
+    frame: Frame,
+}
+
+/// The frames of TexturePacker and Aseprite exports are either an object
+/// keyed by name or an array
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum JsonFrames {
+    Hash(HashMap<String, JsonFrame>),
+    Array(Vec<JsonNamedFrame>),
+}
+
+#[derive(Deserialize)]
+struct JsonAtlas {
+    frames: JsonFrames,
+}
+
+/// Where the frames are on a sprite sheet, frames are looked up by index or by name
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Atlas {
+    frames: Vec<Frame>,
+    names: Vec<Option<String>>,
+    indices: HashMap<String, usize>,
+}
+
+impl Atlas {
+    pub fn new() -> Atlas {
+        Atlas::default()
+    }
+
+    /// Cells of `frame_size` from left to right and top to bottom, with `spacing`
+    /// pixels between neighboring cells. Cells that don't fit on the sheet are left out
+    pub fn grid(sheet_size: [u32; 2], frame_size: [u32; 2], spacing: u32) -> Atlas {
+        let [sheet_w, sheet_h] = sheet_size;
+        let [frame_w, frame_h] = frame_size;
+        let mut atlas = Atlas::new();
+        if frame_w == 0 || frame_h == 0 {
+            return atlas;
+        }
+        let mut y = 0;
+        while y + frame_h <= sheet_h {
+            let mut x = 0;
+            while x + frame_w <= sheet_w {
+                atlas.add(Frame::new(x, y, frame_w, frame_h));
+                x += frame_w + spacing;
+            }
+            y += frame_h + spacing;
+        }
+        atlas
+    }
+
+    /// Reads the `frames` of a JSON atlas as written by TexturePacker or Aseprite.
+    /// Frames given as an object are sorted by name, arrays keep their order
+    pub fn from_json<R: Read>(reader: R) -> io::Result<Atlas> {
+        let json: JsonAtlas = serde_json::from_reader(reader)?;
+        let mut atlas = Atlas::new();
+        match json.frames {
+            JsonFrames::Hash(frames) => {
+                let mut frames: Vec<_> = frames.into_iter().collect();
+                frames.sort_by(|(name1, _), (name2, _)| name1.cmp(name2));
+                for (name, json) in frames {
+                    atlas.add_named(&name, json.frame);
+                }
+            }
+            JsonFrames::Array(frames) => {
+                for json in frames {
+                    atlas.add_named(&json.filename, json.frame);
+                }
+            }
+        }
+        Ok(atlas)
+    }
+
+    /// Index of the new frame
+    pub fn add(&mut self, frame: Frame) -> usize {
+        self.frames.push(frame);
+        self.names.push(None);
+        self.frames.len() - 1
+    }
+
+    /// A frame that already has the name loses it to the new one
+    pub fn add_named(&mut self, name: &str, frame: Frame) -> usize {
+        let ind = self.add(frame);
+        self.names[ind] = Some(name.to_owned());
+        if let Some(old) = self.indices.insert(name.to_owned(), ind) {
+            self.names[old] = None;
+        }
+        ind
+    }
+
+    pub fn frame(&self, ind: usize) -> Frame {
+        self.frames[ind]
+    }
+
+    pub fn frames(&self) -> &[Frame] {
+        &self.frames
+    }
+
+    pub fn index(&self, name: &str) -> Option<usize> {
+        self.indices.get(name).copied()
+    }
+
+    pub fn name(&self, ind: usize) -> Option<&str> {
+        self.names[ind].as_deref()
+    }
+
+    /// Indices of the frames whose names start with `prefix` in atlas order,
+    /// like the frames `walk_0`, `walk_1` and `walk_2` of an animation
+    pub fn sequence(&self, prefix: &str) -> Vec<usize> {
+        (0..self.len())
+            .filter(|ind| self.name(*ind).is_some_and(|name| name.starts_with(prefix)))
+            .collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Smallest size a sheet needs to contain every frame
+    pub fn extent(&self) -> [u32; 2] {
+        self.frames.iter().fold([0, 0], |[w, h], frame| {
+            [w.max(frame.right()), h.max(frame.bottom())]
+        })
+    }
+}
+
+#[cfg(test)]
+mod atlas_tests {
+    use super::{Atlas, Frame};
+
+    #[test]
+    fn grid() {
+        let atlas = Atlas::grid([35, 20], [10, 8], 2);
+        // the third column ends at 34, the third row would need 28 pixels
+        assert_eq!(atlas.len(), 6);
+        assert_eq!(atlas.frame(1), Frame::new(12, 0, 10, 8));
+        assert_eq!(atlas.frame(5), Frame::new(24, 10, 10, 8));
+        assert_eq!(atlas.extent(), [34, 18]);
+        assert!(Atlas::grid([35, 20], [0, 8], 0).is_empty());
+        assert_eq!(Atlas::grid([20, 8], [10, 8], 0).len(), 2);
+    }
+
+    #[test]
+    fn names() {
+        let mut atlas = Atlas::new();
+        atlas.add_named("walk_0", Frame::new(0, 0, 4, 4));
+        atlas.add(Frame::new(4, 0, 4, 4));
+        atlas.add_named("jump", Frame::new(8, 0, 4, 4));
+        atlas.add_named("walk_1", Frame::new(12, 0, 4, 4));
+        assert_eq!(atlas.index("jump"), Some(2));
+        assert_eq!(atlas.index("run"), None);
+        assert_eq!(atlas.name(1), None);
+        assert_eq!(atlas.sequence("walk"), vec![0, 3]);
+
+        let ind = atlas.add_named("jump", Frame::new(16, 0, 4, 4));
+        assert_eq!(atlas.index("jump"), Some(ind));
+        assert_eq!(atlas.name(2), None);
+    }
+
+    #[test]
+    fn json() {
+        let hash = r#"{
+            "frames": {
+                "bird_1": {"frame": {"x": 16, "y": 0, "w": 16, "h": 12}, "rotated": false},
+                "bird_0": {"frame": {"x": 0, "y": 0, "w": 16, "h": 12}}
+            },
+            "meta": {"image": "bird.png"}
+        }"#;
+        let atlas = Atlas::from_json(hash.as_bytes()).unwrap();
+        assert_eq!(atlas.sequence("bird_"), vec![0, 1]);
+        assert_eq!(atlas.frame(1), Frame::new(16, 0, 16, 12));
+
+        let array = r#"{"frames": [
+            {"filename": "b", "frame": {"x": 0, "y": 0, "w": 2, "h": 2}, "duration": 100},
+            {"filename": "a", "frame": {"x": 2, "y": 0, "w": 2, "h": 2}}
+        ]}"#;
+        let atlas = Atlas::from_json(array.as_bytes()).unwrap();
+        assert_eq!(atlas.index("a"), Some(1));
+
+        assert!(Atlas::from_json("{}".as_bytes()).is_err());
+        assert!(Atlas::from_json(r#"{"frames": {"a": {"frame": {"x": 0}}}}"#.as_bytes()).is_err());
+    }
+}
@@ -0,0 +1,7 @@
+pub mod animation;
+pub mod atlas;
+pub mod sheet;
+
+pub use animation::Animation;
+pub use atlas::{Atlas, Frame};
+pub use sheet::{Sprite, SpriteSheet};
@@ -0,0 +1,168 @@
+use super::atlas::{Atlas, Frame};
+use crate::{
+    colors::{rgba, Rgba},
+    DrawingContext, Graphics, Transformed,
+};
+use image::RgbaImage;
+use math::vec2d::Vec2D;
+use piston_window::{Filter, G2dTexture, G2dTextureContext, TextureSettings};
+use std::{
+    fs::File,
+    io::{self, BufReader},
+    path::Path,
+};
+
+/// Where and how a frame of a sprite sheet is drawn
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Sprite {
+    pub pos: Vec2D,
+    /// in radians around `pos`
+    pub rotation: f64,
+    /// negative factors mirror the frame
+    pub scale: Vec2D,
+    /// multiplied with the colors of the sheet, white keeps them
+    pub tint: Rgba,
+    /// point of the frame that ends up at `pos`,
+    /// `(0, 0)` is the top left and `(1, 1)` the bottom right corner
+    pub anchor: Vec2D,
+}
+
+impl Sprite {
+    /// Centered on `pos` at the size of the frame
+    pub fn new(pos: Vec2D) -> Sprite {
+        Sprite {
+            pos,
+            rotation: 0.0,
+            scale: Vec2D::new(1.0, 1.0),
+            tint: rgba::WHITE,
+            anchor: Vec2D::new(0.5, 0.5),
+        }
+    }
+
+    pub fn with_rotation(mut self, rotation: f64) -> Sprite {
+        self.rotation = rotation;
+        self
+    }
+
+    pub fn with_scale(mut self, scale: f64) -> Sprite {
+        self.scale = Vec2D::new(scale, scale);
+        self
+    }
+
+    pub fn with_tint(mut self, tint: Rgba) -> Sprite {
+        self.tint = tint;
+        self
+    }
+
+    pub fn with_anchor(mut self, anchor: Vec2D) -> Sprite {
+        self.anchor = anchor;
+        self
+    }
+}
+
+/// An image with many frames that is uploaded once as a single texture
+pub struct SpriteSheet {
+    texture: G2dTexture,
+    size: [u32; 2],
+    atlas: Atlas,
+}
+
+impl SpriteSheet {
+    /// Fails if a frame of the atlas reaches past the image
+    pub fn new(
+        image: &RgbaImage,
+        atlas: Atlas,
+        texture_context: &mut G2dTextureContext,
+    ) -> io::Result<SpriteSheet> {
+        let size = [image.width(), image.height()];
+        let [width, height] = atlas.extent();
+        if width > size[0] || height > size[1] {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "frames reach to {width}x{height} on a sheet of {}x{}",
+                    size[0], size[1]
+                ),
+            ));
+        }
+        // sprites are usually pixel art that would get blurry when scaled up
+        let settings = TextureSettings::new().filter(Filter::Nearest);
+        let texture = G2dTexture::from_image(texture_context, image, &settings)
+            .map_err(|err| io::Error::other(format!("{err:?}")))?;
+        Ok(SpriteSheet {
+            texture,
+            size,
+            atlas,
+        })
+    }
+
+    /// Loads the image with the frames given by `atlas`
+    pub fn load<P: AsRef<Path>>(
+        path: P,
+        atlas: Atlas,
+        texture_context: &mut G2dTextureContext,
+    ) -> io::Result<SpriteSheet> {
+        let image = image::open(path).map_err(io::Error::other)?;
+        SpriteSheet::new(&image.into_rgba8(), atlas, texture_context)
+    }
+
+    /// Loads an image that is split into frames of the same size, see `Atlas::grid`
+    pub fn load_grid<P: AsRef<Path>>(
+        path: P,
+        frame_size: [u32; 2],
+        spacing: u32,
+        texture_context: &mut G2dTextureContext,
+    ) -> io::Result<SpriteSheet> {
+        let image = image::open(path).map_err(io::Error::other)?.into_rgba8();
+        let atlas = Atlas::grid([image.width(), image.height()], frame_size, spacing);
+        SpriteSheet::new(&image, atlas, texture_context)
+    }
+
+    /// Loads an image and the JSON atlas describing its frames, see `Atlas::from_json`
+    pub fn load_json<P: AsRef<Path>, Q: AsRef<Path>>(
+        image_path: P,
+        atlas_path: Q,
+        texture_context: &mut G2dTextureContext,
+    ) -> io::Result<SpriteSheet> {
+        let atlas = Atlas::from_json(BufReader::new(File::open(atlas_path)?))?;
+        SpriteSheet::load(image_path, atlas, texture_context)
+    }
+
+    pub fn atlas(&self) -> &Atlas {
+        &self.atlas
+    }
+
+    pub fn size(&self) -> [u32; 2] {
+        self.size
+    }
+
+    pub fn frame(&self, ind: usize) -> Frame {
+        self.atlas.frame(ind)
+    }
+
+    /// Draws the frame with index `frame` as given by the sprite
+    pub fn draw(
+        &self,
+        frame: usize,
+        sprite: &Sprite,
+        transform: [[f64; 3]; 2],
+        ctx: &DrawingContext,
+        gl: &mut Graphics,
+    ) {
+        let frame = self.atlas.frame(frame);
+        let (width, height) = (frame.w as f64, frame.h as f64);
+        let transform = transform
+            .trans(sprite.pos.x, sprite.pos.y)
+            .rot_rad(sprite.rotation)
+            .scale(sprite.scale.x, sprite.scale.y);
+        graphics::Image::new_color(sprite.tint.into())
+            .src_rect(frame.src_rect())
+            .rect([
+                -sprite.anchor.x * width,
+                -sprite.anchor.y * height,
+                width,
+                height,
+            ])
+            .draw(&self.texture, &ctx.context.draw_state, transform, gl);
+    }
+}
@@ -0,0 +1,387 @@
+use crate::{colors::Rgba, fonts::Font, DrawingContext, Graphics};
+use math::vec2d::Vec2D;
+use piston_window::{text::Text, Glyphs};
+
+/// Height of capital letters above the baseline relative to the font size
+const ASCENT: f64 = 0.75;
+
+/// Widths of characters in pixels, implemented by the glyph cache of the window
+pub trait Measure {
+    fn advance(&mut self, ch: char, size: u32) -> f64;
+
+    fn ascent(&mut self, size: u32) -> f64 {
+        ASCENT * size as f64
+    }
+}
+
+impl Measure for Glyphs {
+    fn advance(&mut self, ch: char, size: u32) -> f64 {
+        self.opt_character(size, ch)
+            .map(|ch| ch.advance_width())
+            .unwrap_or(size as f64 / 2.0)
+    }
+}
+
+/// Every character is `0.6` (or the given fraction) of the font size wide,
+/// for layouts without a window
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Monospace(pub f64);
+
+impl Default for Monospace {
+    fn default() -> Self {
+        Monospace(0.6)
+    }
+}
+
+impl Measure for Monospace {
+    fn advance(&mut self, _: char, size: u32) -> f64 {
+        self.0 * size as f64
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HAlign {
+    #[default]
+    Left,
+    Center,
+    Right,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum VAlign {
+    Top,
+    Middle,
+    /// the baseline of the first line, like piston's `Text::draw_pos`
+    #[default]
+    Baseline,
+    Bottom,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextStyle {
+    pub size: u32,
+    pub color: Rgba,
+    pub font: Font,
+    pub h_align: HAlign,
+    pub v_align: VAlign,
+    /// distance between baselines relative to the font size
+    pub line_spacing: f64,
+    /// lines are wrapped at spaces to stay below this width
+    pub max_width: Option<f64>,
+    /// wrapping keeps runs of spaces and trailing spaces, for text that is being typed
+    pub keep_spaces: bool,
+}
+
+impl TextStyle {
+    pub fn new(size: u32, color: Rgba) -> TextStyle {
+        TextStyle {
+            size,
+            color,
+            font: Font::default(),
+            h_align: HAlign::default(),
+            v_align: VAlign::default(),
+            line_spacing: 1.2,
+            max_width: None,
+            keep_spaces: false,
+        }
+    }
+
+    pub fn with_font(mut self, font: Font) -> TextStyle {
+        self.font = font;
+        self
+    }
+
+    pub fn with_align(mut self, h_align: HAlign, v_align: VAlign) -> TextStyle {
+        self.h_align = h_align;
+        self.v_align = v_align;
+        self
+    }
+
+    pub fn with_line_spacing(mut self, line_spacing: f64) -> TextStyle {
+        self.line_spacing = line_spacing;
+        self
+    }
+
+    pub fn with_max_width(mut self, max_width: f64) -> TextStyle {
+        self.max_width = Some(max_width);
+        self
+    }
+
+    pub fn with_keep_spaces(mut self) -> TextStyle {
+        self.keep_spaces = true;
+        self
+    }
+
+    pub fn line_height(&self) -> f64 {
+        self.line_spacing * self.size as f64
+    }
+}
+
+/// A line of a text block, `pos` is the start of its baseline
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextLine {
+    pub text: String,
+    pub pos: Vec2D,
+    pub width: f64,
+}
+
+pub fn width<M: Measure + ?Sized>(measure: &mut M, text: &str, size: u32) -> f64 {
+    text.chars().map(|ch| measure.advance(ch, size)).sum()
+}
+
+/// Splits the text at line breaks and at spaces where a line would get wider than
+/// `max_width`, words that are wider on their own are split between characters
+pub fn wrap<M: Measure + ?Sized>(
+    measure: &mut M,
+    text: &str,
+    size: u32,
+    max_width: f64,
+) -> Vec<String> {
+    wrap_words(measure, text, size, max_width, false)
+}
+
+/// Like `wrap` but runs of spaces and trailing spaces stay in the lines,
+/// only the space a line is broken at is dropped
+pub fn wrap_keeping_spaces<M: Measure + ?Sized>(
+    measure: &mut M,
+    text: &str,
+    size: u32,
+    max_width: f64,
+) -> Vec<String> {
+    wrap_words(measure, text, size, max_width, true)
+}
+
+fn wrap_words<M: Measure + ?Sized>(
+    measure: &mut M,
+    text: &str,
+    size: u32,
+    max_width: f64,
+    keep_spaces: bool,
+) -> Vec<String> {
+    let space = measure.advance(' ', size);
+    let mut lines = vec![];
+    for paragraph in text.split('\n') {
+        let mut line = String::new();
+        let mut line_width = 0.0;
+        let words: Vec<&str> = if keep_spaces {
+            paragraph.split(' ').collect()
+        } else {
+            paragraph.split_whitespace().collect()
+        };
+        for (ind, word) in words.into_iter().enumerate() {
+            let word_width = width(measure, word, size);
+            let separated = ind > 0 && (keep_spaces || !line.is_empty());
+            if separated && !line.is_empty() && line_width + space + word_width > max_width {
+                lines.push(std::mem::take(&mut line));
+                line_width = 0.0;
+            } else if separated {
+                line.push(' ');
+                line_width += space;
+            }
+            if word_width <= max_width {
+                line.push_str(word);
+                line_width += word_width;
+                continue;
+            }
+            for ch in word.chars() {
+                let advance = measure.advance(ch, size);
+                if !line.is_empty() && line_width + advance > max_width {
+                    lines.push(std::mem::take(&mut line));
+                    line_width = 0.0;
+                }
+                line.push(ch);
+                line_width += advance;
+            }
+        }
+        lines.push(line);
+    }
+    lines
+}
+
+/// Positions the lines of the text so the block is aligned to `pos` as given by the style
+pub fn layout<M: Measure + ?Sized>(
+    measure: &mut M,
+    text: &str,
+    pos: Vec2D,
+    style: &TextStyle,
+) -> Vec<TextLine> {
+    let lines = match style.max_width {
+        Some(max_width) if style.keep_spaces => {
+            wrap_keeping_spaces(measure, text, style.size, max_width)
+        }
+        Some(max_width) => wrap(measure, text, style.size, max_width),
+        None => text.split('\n').map(str::to_owned).collect(),
+    };
+    let line_height = style.line_height();
+    let ascent = measure.ascent(style.size);
+    let block_height = lines.len() as f64 * line_height;
+    let top = match style.v_align {
+        VAlign::Top => pos.y,
+        VAlign::Middle => pos.y - block_height / 2.0,
+        VAlign::Baseline => pos.y - ascent,
+        VAlign::Bottom => pos.y - block_height,
+    };
+
+    lines
+        .into_iter()
+        .enumerate()
+        .map(|(ind, text)| {
+            let width = width(measure, &text, style.size);
+            let x = match style.h_align {
+                HAlign::Left => pos.x,
+                HAlign::Center => pos.x - width / 2.0,
+                HAlign::Right => pos.x - width,
+            };
+            let y = top + ind as f64 * line_height + ascent;
+            TextLine {
+                text,
+                pos: Vec2D::new(x, y),
+                width,
+            }
+        })
+        .collect()
+}
+
+/// Width and height of the laid out text, every line takes up the line height
+pub fn block_size<M: Measure + ?Sized>(measure: &mut M, text: &str, style: &TextStyle) -> [f64; 2] {
+    let lines = layout(measure, text, Vec2D::default(), style);
+    let width = lines.iter().map(|line| line.width).fold(0.0, f64::max);
+    [width, lines.len() as f64 * style.line_height()]
+}
+
+/// The style with the largest font size up to `style.size` at which the text,
+/// wrapped to `width`, fits into a box of the given size. The size does not go below 1
+pub fn fitted<M: Measure + ?Sized>(
+    measure: &mut M,
+    text: &str,
+    width: f64,
+    height: f64,
+    style: &TextStyle,
+) -> TextStyle {
+    let mut fitted = style.clone().with_max_width(width);
+    while fitted.size > 1 {
+        let [block_width, block_height] = block_size(measure, text, &fitted);
+        if block_width <= width && block_height <= height {
+            break;
+        }
+        fitted.size -= 1;
+    }
+    fitted
+}
+
+pub fn draw_lines(
+    lines: &[TextLine],
+    style: &TextStyle,
+    transform: [[f64; 3]; 2],
+    ctx: &mut DrawingContext,
+    gl: &mut Graphics,
+) {
+    let text = Text::new_color(style.color.into(), style.size);
+    let context = ctx.context;
+    let glyphs = ctx.glyphs_for(&style.font);
+    for line in lines {
+        text.draw_pos(
+            &line.text,
+            [line.pos.x, line.pos.y],
+            glyphs,
+            &context.draw_state,
+            transform,
+            gl,
+        )
+        .unwrap();
+    }
+}
+
+/// Draws the text aligned to `pos`, measured with the glyphs of the font in the style
+pub fn draw_text(
+    text: &str,
+    pos: Vec2D,
+    style: &TextStyle,
+    transform: [[f64; 3]; 2],
+    ctx: &mut DrawingContext,
+    gl: &mut Graphics,
+) {
+    let lines = layout(ctx.glyphs_for(&style.font), text, pos, style);
+    draw_lines(&lines, style, transform, ctx, gl);
+}
+
+#[cfg(test)]
+mod text_tests {
+    use super::{
+        block_size, fitted, layout, width, wrap, wrap_keeping_spaces, HAlign, Monospace, TextStyle,
+        VAlign,
+    };
+    use crate::colors::rgba;
+    use math::vec2d::Vec2D;
+
+    /// characters are 5 pixels wide at size 10
+    fn mono() -> Monospace {
+        Monospace(0.5)
+    }
+
+    #[test]
+    fn wrapping() {
+        assert_eq!(width(&mut mono(), "abc", 10), 15.0);
+        let lines = wrap(&mut mono(), "the quick brown fox\n\njumps", 10, 50.0);
+        assert_eq!(lines, vec!["the quick", "brown fox", "", "jumps"]);
+        let lines = wrap(&mut mono(), "a abcdefghijkl b", 10, 25.0);
+        assert_eq!(lines, vec!["a", "abcde", "fghij", "kl b"]);
+        assert_eq!(wrap(&mut mono(), "", 10, 25.0), vec![""]);
+    }
+
+    #[test]
+    fn wrapping_keeps_spaces() {
+        assert_eq!(wrap(&mut mono(), "a  b ", 10, 50.0), vec!["a b"]);
+        assert_eq!(
+            wrap_keeping_spaces(&mut mono(), "a  b ", 10, 50.0),
+            vec!["a  b "]
+        );
+        assert_eq!(wrap_keeping_spaces(&mut mono(), " a", 10, 50.0), vec![" a"]);
+        // the space at the break is dropped, the others stay
+        let lines = wrap_keeping_spaces(&mut mono(), "abc  de f", 10, 25.0);
+        assert_eq!(lines, vec!["abc ", "de f"]);
+
+        let style = TextStyle::new(10, rgba::WHITE)
+            .with_max_width(50.0)
+            .with_keep_spaces();
+        let lines = layout(&mut mono(), "a  b ", Vec2D::default(), &style);
+        assert_eq!(lines[0].text, "a  b ");
+        assert_eq!(lines[0].width, 25.0);
+    }
+
+    #[test]
+    fn alignment() {
+        let style = TextStyle::new(10, rgba::WHITE).with_line_spacing(2.0);
+        let pos = Vec2D::new(100.0, 100.0);
+        let lines = layout(&mut mono(), "ab\nabcd", pos, &style);
+        assert_eq!(lines[0].pos, Vec2D::new(100.0, 100.0));
+        assert_eq!(lines[1].pos, Vec2D::new(100.0, 120.0));
+        assert_eq!(lines[1].width, 20.0);
+
+        let centered = style.clone().with_align(HAlign::Center, VAlign::Middle);
+        let lines = layout(&mut mono(), "ab\nabcd", pos, &centered);
+        assert_eq!(lines[0].pos, Vec2D::new(95.0, 87.5));
+        assert_eq!(lines[1].pos, Vec2D::new(90.0, 107.5));
+
+        let bottom_right = style.clone().with_align(HAlign::Right, VAlign::Bottom);
+        let lines = layout(&mut mono(), "ab\nabcd", pos, &bottom_right);
+        assert_eq!(lines[1].pos, Vec2D::new(80.0, 87.5));
+        let top = style.with_align(HAlign::Left, VAlign::Top);
+        assert_eq!(layout(&mut mono(), "ab", pos, &top)[0].pos.y, 107.5);
+    }
+
+    #[test]
+    fn fitting() {
+        let style = TextStyle::new(20, rgba::WHITE).with_line_spacing(1.0);
+        let text = "aaaa bbbb";
+        assert_eq!(block_size(&mut mono(), text, &style), [90.0, 20.0]);
+        // fits on one line at full size
+        assert_eq!(fitted(&mut mono(), text, 100.0, 20.0, &style).size, 20);
+        // two lines of 4 characters
+        let two_lines = fitted(&mut mono(), text, 40.0, 40.0, &style);
+        assert_eq!(two_lines.size, 20);
+        assert_eq!(two_lines.max_width, Some(40.0));
+        assert_eq!(fitted(&mut mono(), text, 40.0, 30.0, &style).size, 15);
+        assert_eq!(fitted(&mut mono(), text, 0.0, 0.0, &style).size, 1);
+    }
+}
@@ -1,5 +1,9 @@
-use crate::Graphics;
-use graphics::{Context, Transformed};
+use crate::{
+    fonts::{Font, Fonts},
+    Graphics,
+};
+use graphics::Context;
+use math::{affine2::Affine2, vec2d::Vec2D};
 use piston::RenderArgs;
 use piston_window::{G2dTextureContext, Glyphs};
 
@@ -7,19 +11,33 @@ pub struct DrawingContext<'a> {
     pub context: &'a Context,
     pub args: &'a RenderArgs,
     pub glyphs: &'a mut Glyphs,
+    pub fonts: &'a mut Fonts,
     pub texture_context: &'a mut G2dTextureContext,
 }
 
 impl<'a> DrawingContext<'a> {
+    pub fn id_affine(&self) -> Affine2 {
+        Affine2::from(self.context.transform)
+    }
+
+    pub fn center_affine(&self) -> Affine2 {
+        self.id_affine() * Affine2::translation(Vec2D::from(self.args.window_size) / 2.0)
+    }
+
     pub fn id_trans(&self) -> [[f64; 3]; 2] {
-        self.context.transform.scale(1.0, 1.0)
+        self.id_affine().into()
     }
 
     pub fn center_trans(&self) -> [[f64; 3]; 2] {
-        self.context.transform.trans(
-            self.args.window_size[0] / 2.0,
-            self.args.window_size[1] / 2.0,
-        )
+        self.center_affine().into()
+    }
+
+    /// Glyphs of the font, the default font stands in for fonts that were not loaded
+    pub fn glyphs_for(&mut self, font: &Font) -> &mut Glyphs {
+        match self.fonts.get_mut(font) {
+            Some(glyphs) => glyphs,
+            None => self.glyphs,
+        }
     }
 }
 
@@ -5,6 +5,8 @@ pub struct InputContext<'a> {
     pub window_width: f64,
     pub mouse_pos: [f64; 2],
     pub args: &'a ButtonArgs,
+    /// the action of `Runnable::shortcuts` bound to the key, if any
+    pub action: Option<&'static str>,
 }
 pub trait EventHandler {
     fn handle_input(&mut self, _: &InputContext) {}
@@ -1,5 +1,5 @@
 use super::{drawable::Drawable, eventhandler::EventHandler, updatable::Updatable};
-use crate::SCREENSHOT_DIR;
+use crate::{fonts::FontFile, shortcuts::Shortcuts, SCREENSHOT_DIR};
 use piston_window::G2dTextureContext;
 use std::process::Command;
 
@@ -18,6 +18,15 @@ pub struct WindowConfig {
 pub trait Runnable: Drawable + Updatable + EventHandler {
     fn config(&self) -> WindowConfig;
     fn setup(&mut self, _: &mut SetupContext) {}
+    /// Fonts to load besides the default and monospace ones when the window opens
+    fn fonts(&self) -> Vec<FontFile> {
+        vec![]
+    }
+    /// Actions triggered by keys, listed by the help overlay and remappable
+    /// in `shortcuts/<title>.txt` with the spaces of the title removed
+    fn shortcuts(&self) -> Shortcuts {
+        Shortcuts::new()
+    }
     fn screenshot(&self) {
         let title = self.config().title.replace(" ", "");
         let date_str = chrono::Local::now().format("%Y%m%d_%H%M%s");
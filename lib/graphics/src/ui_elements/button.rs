@@ -1,5 +1,5 @@
 use crate::{rectangle, Color, Drawable, DrawingContext, Graphics};
-use math::vec2d::Vec2D;
+use math::{geometry::Rect, vec2d::Vec2D};
 use piston::{Button as PisButton, ButtonArgs, ButtonState, MouseButton};
 use piston_window::text;
 
@@ -40,10 +40,7 @@ impl Button {
         {
             return false;
         }
-        mouse_pos[0] >= self.pos.x
-            && mouse_pos[0] <= self.pos.x + self.width
-            && mouse_pos[1] >= self.pos.y
-            && mouse_pos[1] <= self.pos.y + self.height
+        Rect::new(self.pos.x, self.pos.y, self.width, self.height).contains(mouse_pos.into())
     }
 }
 
@@ -1,10 +1,12 @@
 use crate::{
     colors::{rgba, Rgba},
-    rectangle, Drawable, DrawingContext, EventHandler, Graphics, InputContext,
+    fonts::Font,
+    rectangle,
+    text::{draw_lines, layout, HAlign, TextStyle, VAlign},
+    Drawable, DrawingContext, EventHandler, Graphics, InputContext,
 };
-use math::vec2d::Vec2D;
+use math::{geometry::Rect, vec2d::Vec2D};
 use piston::{Button, ButtonState, Key, MouseButton};
-use piston_window::text::Text;
 
 pub struct TextField {
     pos: Vec2D,
@@ -16,6 +18,7 @@ pub struct TextField {
     text: String,
     text_color: Rgba,
     font_size: u32,
+    font: Font,
 }
 impl TextField {
     pub fn new(x: f64, y: f64, w: f64, h: f64, font_size: u32) -> TextField {
@@ -29,6 +32,7 @@ impl TextField {
             text: "".to_owned(),
             text_color: rgba::BLACK,
             font_size,
+            font: Font::default(),
         }
     }
 
@@ -36,6 +40,10 @@ impl TextField {
         self.text_color = col;
     }
 
+    pub fn set_font(&mut self, font: Font) {
+        self.font = font;
+    }
+
     pub fn set_bg_color(&mut self, col: Rgba) {
         self.background = col;
     }
@@ -69,10 +77,7 @@ impl TextField {
     }
 
     fn inside(&self, pos: &Vec2D) -> bool {
-        pos.x >= self.pos.x
-            && pos.x <= self.pos.x + self.width
-            && pos.y >= self.pos.y
-            && pos.y <= self.pos.y + self.height
+        Rect::new(self.pos.x, self.pos.y, self.width, self.height).contains(*pos)
     }
 
     fn check_shift(&mut self, st: &ButtonState, bt: &Button) {
@@ -125,49 +130,15 @@ impl Drawable for TextField {
             gl,
         );
 
-        let text = Text::new_color(self.text_color.into(), self.font_size);
-
-        let mut next_y = self.pos.y + self.font_size as f64;
-        let mut next_line = "".to_owned();
-        let mut current_size = 0.0;
-        for ch in self.text.chars() {
-            next_line.push(ch);
-            let next_size = ctx
-                .glyphs
-                .opt_character(self.font_size, ch)
-                .map(|ch| ch.advance_width())
-                .unwrap_or(self.font_size as f64);
-            current_size += next_size;
-            if current_size + next_size > self.width {
-                text.draw_pos(
-                    &next_line,
-                    [self.pos.x, next_y],
-                    ctx.glyphs,
-                    &ctx.context.draw_state,
-                    transform,
-                    gl,
-                )
-                .unwrap();
-                next_line.clear();
-                next_y += self.font_size as f64;
-                current_size = 0.0;
-                if next_y > self.pos.y + self.height {
-                    break;
-                }
-            }
-        }
-
-        if !next_line.is_empty() {
-            text.draw_pos(
-                &next_line,
-                [self.pos.x, next_y],
-                ctx.glyphs,
-                &ctx.context.draw_state,
-                transform,
-                gl,
-            )
-            .unwrap();
-        }
+        let style = TextStyle::new(self.font_size, self.text_color)
+            .with_font(self.font.clone())
+            .with_align(HAlign::Left, VAlign::Top)
+            .with_line_spacing(1.0)
+            .with_max_width(self.width)
+            .with_keep_spaces();
+        let mut lines = layout(ctx.glyphs_for(&style.font), &self.text, self.pos, &style);
+        lines.retain(|line| line.pos.y <= self.pos.y + self.height);
+        draw_lines(&lines, &style, transform, ctx, gl);
     }
 }
 
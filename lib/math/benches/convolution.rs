@@ -0,0 +1,113 @@
+//! Timings of `Grid::convolve`, run with `cargo bench -p math --bench convolution`
+use math::{
+    convolution::{ConvolutionMethod, Kernel},
+    grid::{Boundary, Grid},
+};
+use std::time::{Duration, Instant};
+
+const SIZE: usize = 300;
+/// Simulation steps per frame in the reaction diffusion challenge
+const STEPS_PER_FRAME: usize = 10;
+const FRAME_BUDGET: Duration = Duration::from_micros(16_667);
+
+const LAPLACE_WEIGHTS: [[f64; 3]; 3] = [[0.05, 0.2, 0.05], [0.2, -1.0, 0.2], [0.05, 0.2, 0.05]];
+
+/// Average duration of `fun` over `runs` runs after one warm up run
+fn time<T>(runs: u32, mut fun: impl FnMut() -> T) -> Duration {
+    std::hint::black_box(fun());
+    let start = Instant::now();
+    for _ in 0..runs {
+        std::hint::black_box(fun());
+    }
+    start.elapsed() / runs
+}
+
+fn grid() -> Grid<f64> {
+    Grid::from_fn(|x, y| ((x * 7 + y * 13) % 11) as f64 / 10.0, SIZE, SIZE)
+}
+
+/// One frame of the Gray-Scott model as run by the reaction diffusion challenge
+fn reaction_diffusion_frame(a: &mut Grid<f64>, b: &mut Grid<f64>, laplace: &Kernel) {
+    for _ in 0..STEPS_PER_FRAME {
+        let laplace_a = a.convolve(laplace, Boundary::Wrap);
+        let laplace_b = b.convolve(laplace, Boundary::Wrap);
+        let cells = a.iter_mut().zip(b.iter_mut());
+        for ((a, b), (lap_a, lap_b)) in cells.zip(laplace_a.iter().zip(laplace_b.iter())) {
+            let reaction = *a * *b * *b;
+            *a += lap_a - reaction + 0.055 * (1.0 - *a);
+            *b += 0.5 * lap_b + reaction - (0.062 + 0.055) * *b;
+        }
+    }
+}
+
+fn main() {
+    let laplace = Kernel::from_rows(LAPLACE_WEIGHTS);
+    let (mut a, mut b) = (Grid::from_fn(|_, _| 1.0, SIZE, SIZE), grid());
+    let frame = time(20, || reaction_diffusion_frame(&mut a, &mut b, &laplace));
+    println!(
+        "reaction diffusion {SIZE}x{SIZE}, {STEPS_PER_FRAME} steps: {frame:?} per frame ({:.0}% of {FRAME_BUDGET:?})",
+        100.0 * frame.as_secs_f64() / FRAME_BUDGET.as_secs_f64()
+    );
+
+    let grid = grid();
+    let naive = time(5, || naive_convolve(&grid, &laplace, Boundary::Wrap));
+    let fast = time(20, || grid.convolve(&laplace, Boundary::Wrap));
+    println!("laplace 3x3: naive {naive:?}, convolve {fast:?}");
+
+    let methods = [
+        ConvolutionMethod::Direct,
+        ConvolutionMethod::Separable,
+        ConvolutionMethod::Fft,
+    ];
+    for sigma in [1.0, 3.0, 8.0] {
+        let kernel = Kernel::gaussian(sigma);
+        // the same weights without the factorization
+        let dense = Kernel::new(
+            kernel.width(),
+            kernel.height(),
+            (0..kernel.height())
+                .flat_map(|y| (0..kernel.width()).map(move |x| (x, y)))
+                .map(|(x, y)| kernel.get(x, y))
+                .collect(),
+        );
+        let timings: Vec<String> = methods
+            .iter()
+            .map(|method| {
+                let kernel = if *method == ConvolutionMethod::Separable {
+                    &kernel
+                } else {
+                    &dense
+                };
+                let took = time(3, || grid.convolve_using(kernel, Boundary::Clamp, *method));
+                format!("{method:?} {took:?}")
+            })
+            .collect();
+        println!(
+            "gaussian {}x{}: {}",
+            kernel.width(),
+            kernel.height(),
+            timings.join(", ")
+        );
+    }
+}
+
+/// Convolution straight from the definition with a boundary lookup per weight
+fn naive_convolve(grid: &Grid<f64>, kernel: &Kernel, boundary: Boundary) -> Grid<f64> {
+    let (anchor_x, anchor_y) = ((kernel.width() - 1) / 2, (kernel.height() - 1) / 2);
+    Grid::from_fn(
+        |x, y| {
+            let mut sum = 0.0;
+            for ky in 0..kernel.height() {
+                for kx in 0..kernel.width() {
+                    let src_x = (x + kx) as i64 - anchor_x as i64;
+                    let src_y = (y + ky) as i64 - anchor_y as i64;
+                    sum += kernel.get(kx, ky)
+                        * grid.get_bounded(src_x, src_y, boundary).unwrap_or(&0.0);
+                }
+            }
+            sum
+        },
+        grid.num_cols(),
+        grid.num_rows(),
+    )
+}
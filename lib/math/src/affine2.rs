@@ -0,0 +1,178 @@
+use super::{mat22::Mat22, vec2d::Vec2D};
+use std::ops::Mul;
+
+/// Affine map p -> linear * p + translation,
+/// chaining methods append transforms the same way piston's `Transformed` does
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Affine2 {
+    pub linear: Mat22,
+    pub translation: Vec2D,
+}
+
+impl Affine2 {
+    pub fn new(linear: Mat22, translation: Vec2D) -> Affine2 {
+        Affine2 {
+            linear,
+            translation,
+        }
+    }
+
+    pub fn identity() -> Affine2 {
+        Affine2::new(Mat22::identity(), Vec2D::default())
+    }
+
+    pub fn translation(offset: Vec2D) -> Affine2 {
+        Affine2::new(Mat22::identity(), offset)
+    }
+
+    pub fn rotation(angle: f64) -> Affine2 {
+        Affine2::new(Mat22::rotation(angle), Vec2D::default())
+    }
+
+    pub fn scaling(factors: Vec2D) -> Affine2 {
+        Affine2::new(Mat22::scale(factors), Vec2D::default())
+    }
+
+    pub fn trans(self, x: f64, y: f64) -> Affine2 {
+        self * Affine2::translation(Vec2D::new(x, y))
+    }
+
+    pub fn rot_rad(self, angle: f64) -> Affine2 {
+        self * Affine2::rotation(angle)
+    }
+
+    pub fn scale(self, sx: f64, sy: f64) -> Affine2 {
+        self * Affine2::scaling(Vec2D::new(sx, sy))
+    }
+
+    pub fn apply_point(&self, pt: Vec2D) -> Vec2D {
+        self.linear * pt + self.translation
+    }
+
+    /// Applies only the linear part, for directions and offsets
+    pub fn apply_vector(&self, vec: Vec2D) -> Vec2D {
+        self.linear * vec
+    }
+
+    pub fn det(&self) -> f64 {
+        self.linear.det()
+    }
+
+    pub fn inv(self) -> Option<Affine2> {
+        let linear = self.linear.inv()?;
+        Some(Affine2::new(linear, -(linear * self.translation)))
+    }
+}
+
+impl Default for Affine2 {
+    fn default() -> Affine2 {
+        Affine2::identity()
+    }
+}
+
+impl From<Mat22> for Affine2 {
+    fn from(linear: Mat22) -> Affine2 {
+        Affine2::new(linear, Vec2D::default())
+    }
+}
+
+impl From<[[f64; 3]; 2]> for Affine2 {
+    fn from(mat: [[f64; 3]; 2]) -> Affine2 {
+        Affine2::new(
+            Mat22::new([[mat[0][0], mat[0][1]], [mat[1][0], mat[1][1]]]),
+            Vec2D::new(mat[0][2], mat[1][2]),
+        )
+    }
+}
+
+impl From<Affine2> for [[f64; 3]; 2] {
+    fn from(aff: Affine2) -> [[f64; 3]; 2] {
+        let m = aff.linear.rows();
+        [
+            [m[0][0], m[0][1], aff.translation.x],
+            [m[1][0], m[1][1], aff.translation.y],
+        ]
+    }
+}
+
+impl Mul<Affine2> for Affine2 {
+    type Output = Affine2;
+    fn mul(self, other: Affine2) -> Affine2 {
+        Affine2::new(
+            self.linear * other.linear,
+            self.apply_point(other.translation),
+        )
+    }
+}
+
+impl Mul<Vec2D> for Affine2 {
+    type Output = Vec2D;
+    fn mul(self, other: Vec2D) -> Vec2D {
+        self.apply_point(other)
+    }
+}
+
+#[cfg(test)]
+mod affine2_tests {
+    use super::Affine2;
+    use crate::{mat22::Mat22, vec2d::Vec2D};
+    use std::f64::consts::PI;
+
+    fn assert_close_vec(result: Vec2D, expected: Vec2D) {
+        assert!(result.dist(&expected) < 1e-9, "{result} != {expected}")
+    }
+
+    #[test]
+    fn apply_translation() {
+        let aff = Affine2::translation(Vec2D::new(1.0, 2.0));
+        assert_eq!(aff * Vec2D::new(1.0, 1.0), Vec2D::new(2.0, 3.0));
+        assert_eq!(aff.apply_vector(Vec2D::new(1.0, 1.0)), Vec2D::new(1.0, 1.0))
+    }
+
+    #[test]
+    fn compose_order() {
+        let aff = Affine2::identity().trans(10.0, 0.0).rot_rad(PI / 2.0);
+        // rotate first, then translate
+        assert_close_vec(aff * Vec2D::new(1.0, 0.0), Vec2D::new(10.0, 1.0));
+        let result = (Affine2::translation(Vec2D::new(10.0, 0.0)) * Affine2::rotation(PI / 2.0))
+            * Vec2D::new(1.0, 0.0);
+        assert_close_vec(result, Vec2D::new(10.0, 1.0))
+    }
+
+    #[test]
+    fn scale() {
+        let aff = Affine2::translation(Vec2D::new(1.0, 1.0)).scale(2.0, 3.0);
+        assert_eq!(aff * Vec2D::new(1.0, 1.0), Vec2D::new(3.0, 4.0));
+        assert_eq!(aff.det(), 6.0)
+    }
+
+    #[test]
+    fn inverse() {
+        let aff = Affine2::identity()
+            .trans(3.0, -2.0)
+            .rot_rad(0.4)
+            .scale(2.0, 0.5);
+        let inv = aff.inv().unwrap();
+        let pt = Vec2D::new(-1.5, 4.0);
+        assert_close_vec(inv * (aff * pt), pt);
+        assert_close_vec((aff * inv) * pt, pt);
+        assert_eq!(Affine2::scaling(Vec2D::new(0.0, 1.0)).inv(), None)
+    }
+
+    #[test]
+    fn piston_matrix_roundtrip() {
+        let mat = [[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]];
+        let aff = Affine2::from(mat);
+        assert_eq!(aff.linear, Mat22::new([[1.0, 2.0], [4.0, 5.0]]));
+        assert_eq!(aff.translation, Vec2D::new(3.0, 6.0));
+        assert_eq!(<[[f64; 3]; 2]>::from(aff), mat)
+    }
+
+    #[test]
+    fn piston_conventions() {
+        // piston applies [[a, b, tx], [c, d, ty]] as (a*x + b*y + tx, c*x + d*y + ty)
+        let mat = [[0.0, -1.0, 5.0], [1.0, 0.0, 7.0]];
+        let result = Affine2::from(mat) * Vec2D::new(2.0, 3.0);
+        assert_eq!(result, Vec2D::new(2.0, 9.0))
+    }
+}
@@ -0,0 +1,402 @@
+use super::vec2d::Vec2D;
+use num_traits::Float;
+use std::{
+    fmt,
+    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+};
+
+/// Complex number over `f64` or `f32`
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub struct Complex<T = f64> {
+    pub re: T,
+    pub im: T,
+}
+
+impl<T: Float> Complex<T> {
+    pub fn new(re: T, im: T) -> Complex<T> {
+        Complex { re, im }
+    }
+
+    pub fn i() -> Complex<T> {
+        Complex::new(T::zero(), T::one())
+    }
+
+    pub fn from_polar(r: T, phi: T) -> Complex<T> {
+        Complex::new(r * phi.cos(), r * phi.sin())
+    }
+
+    pub fn polar(&self) -> (T, T) {
+        (self.abs(), self.arg())
+    }
+
+    pub fn norm_sqr(&self) -> T {
+        self.re * self.re + self.im * self.im
+    }
+
+    pub fn abs(&self) -> T {
+        self.re.hypot(self.im)
+    }
+
+    pub fn arg(&self) -> T {
+        self.im.atan2(self.re)
+    }
+
+    pub fn conj(self) -> Complex<T> {
+        Complex::new(self.re, -self.im)
+    }
+
+    fn is_zero(&self) -> bool {
+        self.re.is_zero() && self.im.is_zero()
+    }
+
+    /// Multiplicative inverse, infinite or NaN for zero like float division
+    pub fn recip(self) -> Complex<T> {
+        self.conj() / self.norm_sqr()
+    }
+
+    /// Integer power by repeated squaring, exact for small exponents
+    pub fn powi(self, n: i32) -> Complex<T> {
+        let power = self.powu(n.unsigned_abs());
+        if n < 0 {
+            power.recip()
+        } else {
+            power
+        }
+    }
+
+    /// Natural power by repeated squaring
+    pub fn powu(self, n: u32) -> Complex<T> {
+        let mut result = Complex::new(T::one(), T::zero());
+        let mut base = self;
+        let mut exp = n;
+        while exp > 0 {
+            if exp % 2 == 1 {
+                result *= base;
+            }
+            base *= base;
+            exp /= 2;
+        }
+        result
+    }
+
+    /// Principal value of self^n
+    pub fn powf(self, n: T) -> Complex<T> {
+        if self.is_zero() {
+            return self;
+        }
+        let (r, phi) = self.polar();
+        Complex::from_polar(r.powf(n), phi * n)
+    }
+
+    /// Principal value of self^other
+    pub fn powc(self, other: Complex<T>) -> Complex<T> {
+        if self.is_zero() {
+            return self;
+        }
+        (self.ln() * other).exp()
+    }
+
+    pub fn sqrt(self) -> Complex<T> {
+        self.powf(T::one() / (T::one() + T::one()))
+    }
+
+    pub fn exp(self) -> Complex<T> {
+        Complex::from_polar(self.re.exp(), self.im)
+    }
+
+    /// Principal branch of the logarithm, the imaginary part lies in (-pi, pi]
+    pub fn ln(self) -> Complex<T> {
+        Complex::new(self.abs().ln(), self.arg())
+    }
+
+    pub fn sin(self) -> Complex<T> {
+        Complex::new(
+            self.re.sin() * self.im.cosh(),
+            self.re.cos() * self.im.sinh(),
+        )
+    }
+
+    pub fn cos(self) -> Complex<T> {
+        Complex::new(
+            self.re.cos() * self.im.cosh(),
+            -self.re.sin() * self.im.sinh(),
+        )
+    }
+}
+
+impl<T: Float> From<T> for Complex<T> {
+    fn from(re: T) -> Complex<T> {
+        Complex::new(re, T::zero())
+    }
+}
+
+impl From<Vec2D> for Complex {
+    fn from(vec: Vec2D) -> Complex {
+        Complex::new(vec.x, vec.y)
+    }
+}
+
+impl From<Complex> for Vec2D {
+    fn from(z: Complex) -> Vec2D {
+        Vec2D::new(z.re, z.im)
+    }
+}
+
+impl<T: Float> Add for Complex<T> {
+    type Output = Complex<T>;
+    fn add(self, other: Complex<T>) -> Complex<T> {
+        Complex::new(self.re + other.re, self.im + other.im)
+    }
+}
+
+impl<T: Float> Add<T> for Complex<T> {
+    type Output = Complex<T>;
+    fn add(self, other: T) -> Complex<T> {
+        Complex::new(self.re + other, self.im)
+    }
+}
+
+impl<T: Float> AddAssign for Complex<T> {
+    fn add_assign(&mut self, other: Complex<T>) {
+        *self = *self + other;
+    }
+}
+
+impl<T: Float> Sub for Complex<T> {
+    type Output = Complex<T>;
+    fn sub(self, other: Complex<T>) -> Complex<T> {
+        Complex::new(self.re - other.re, self.im - other.im)
+    }
+}
+
+impl<T: Float> Sub<T> for Complex<T> {
+    type Output = Complex<T>;
+    fn sub(self, other: T) -> Complex<T> {
+        Complex::new(self.re - other, self.im)
+    }
+}
+
+impl<T: Float> SubAssign for Complex<T> {
+    fn sub_assign(&mut self, other: Complex<T>) {
+        *self = *self - other;
+    }
+}
+
+impl<T: Float> Mul for Complex<T> {
+    type Output = Complex<T>;
+    fn mul(self, other: Complex<T>) -> Complex<T> {
+        Complex::new(
+            self.re * other.re - self.im * other.im,
+            self.re * other.im + self.im * other.re,
+        )
+    }
+}
+
+impl<T: Float> Mul<T> for Complex<T> {
+    type Output = Complex<T>;
+    fn mul(self, other: T) -> Complex<T> {
+        Complex::new(self.re * other, self.im * other)
+    }
+}
+
+impl<T: Float> MulAssign for Complex<T> {
+    fn mul_assign(&mut self, other: Complex<T>) {
+        *self = *self * other;
+    }
+}
+
+impl<T: Float> MulAssign<T> for Complex<T> {
+    fn mul_assign(&mut self, other: T) {
+        *self = *self * other;
+    }
+}
+
+impl<T: Float> Div for Complex<T> {
+    type Output = Complex<T>;
+    fn div(self, other: Complex<T>) -> Complex<T> {
+        (self * other.conj()) / other.norm_sqr()
+    }
+}
+
+impl<T: Float> Div<T> for Complex<T> {
+    type Output = Complex<T>;
+    fn div(self, other: T) -> Complex<T> {
+        Complex::new(self.re / other, self.im / other)
+    }
+}
+
+impl<T: Float> DivAssign for Complex<T> {
+    fn div_assign(&mut self, other: Complex<T>) {
+        *self = *self / other;
+    }
+}
+
+impl<T: Float> DivAssign<T> for Complex<T> {
+    fn div_assign(&mut self, other: T) {
+        *self = *self / other;
+    }
+}
+
+impl<T: Float> Neg for Complex<T> {
+    type Output = Complex<T>;
+    fn neg(self) -> Complex<T> {
+        Complex::new(-self.re, -self.im)
+    }
+}
+
+/// Operators with the float on the left, the orphan rule rules out a generic impl
+macro_rules! scalar_lhs_ops {
+    ($($float:ty),*) => {$(
+        impl Add<Complex<$float>> for $float {
+            type Output = Complex<$float>;
+            fn add(self, other: Complex<$float>) -> Complex<$float> {
+                other + self
+            }
+        }
+
+        impl Sub<Complex<$float>> for $float {
+            type Output = Complex<$float>;
+            fn sub(self, other: Complex<$float>) -> Complex<$float> {
+                -other + self
+            }
+        }
+
+        impl Mul<Complex<$float>> for $float {
+            type Output = Complex<$float>;
+            fn mul(self, other: Complex<$float>) -> Complex<$float> {
+                other * self
+            }
+        }
+
+        impl Div<Complex<$float>> for $float {
+            type Output = Complex<$float>;
+            fn div(self, other: Complex<$float>) -> Complex<$float> {
+                Complex::from(self) / other
+            }
+        }
+    )*};
+}
+
+scalar_lhs_ops!(f32, f64);
+
+impl<T: Float + fmt::Display> fmt::Display for Complex<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.im < T::zero() {
+            write!(f, "{}-{}i", self.re, -self.im)
+        } else {
+            write!(f, "{}+{}i", self.re, self.im)
+        }
+    }
+}
+
+#[cfg(test)]
+mod complex_tests {
+    use super::Complex;
+    use crate::vec2d::Vec2D;
+    use std::f64::consts::PI;
+
+    fn assert_close(result: Complex, expected: Complex) {
+        assert!((result - expected).abs() < 1e-9, "{result} != {expected}")
+    }
+
+    #[test]
+    fn arithmetic() {
+        let z1 = Complex::new(1.0, 2.0);
+        let z2 = Complex::new(3.0, -1.0);
+        assert_eq!(z1 + z2, Complex::new(4.0, 1.0));
+        assert_eq!(z1 - z2, Complex::new(-2.0, 3.0));
+        assert_eq!(z1 * z2, Complex::new(5.0, 5.0));
+        assert_close(z1 / z2, Complex::new(0.1, 0.7));
+        assert_close((z1 / z2) * z2, z1)
+    }
+
+    #[test]
+    fn scalar_ops() {
+        let z = Complex::new(1.0, -2.0);
+        assert_eq!(2.0 * z, Complex::new(2.0, -4.0));
+        assert_eq!(z + 1.0, Complex::new(2.0, -2.0));
+        assert_eq!(1.0 - z, Complex::new(0.0, 2.0));
+        assert_close(1.0 / z, z.recip());
+        assert_close(z.recip(), Complex::new(0.2, 0.4))
+    }
+
+    #[test]
+    fn assign_ops() {
+        let mut result = Complex::new(1.0, 1.0);
+        result *= Complex::i();
+        result += Complex::new(1.0, 0.0);
+        result /= 2.0;
+        assert_eq!(result, Complex::new(0.0, 0.5))
+    }
+
+    #[test]
+    fn i_squared() {
+        assert_eq!(Complex::i() * Complex::i(), Complex::new(-1.0, 0.0))
+    }
+
+    #[test]
+    fn conj_norm() {
+        let z = Complex::new(3.0, 4.0);
+        assert_eq!(z.conj(), Complex::new(3.0, -4.0));
+        assert_eq!(z.norm_sqr(), 25.0);
+        assert_eq!(z.abs(), 5.0);
+        assert_eq!(z * z.conj(), Complex::new(25.0, 0.0))
+    }
+
+    #[test]
+    fn powers() {
+        let z = Complex::new(1.0, 1.0);
+        assert_eq!(z.powi(2), Complex::new(0.0, 2.0));
+        assert_eq!(z.powi(0), Complex::new(1.0, 0.0));
+        assert_close(z.powi(-2), Complex::new(0.0, -0.5));
+        assert_close(z.powf(3.0), z * z * z);
+        assert_close(z.sqrt() * z.sqrt(), z);
+        assert_close(z.powc(Complex::new(2.0, 0.0)), z.powi(2))
+    }
+
+    #[test]
+    fn extreme_powers() {
+        let i = Complex::i();
+        assert_eq!(i.powi(i32::MIN), Complex::new(1.0, 0.0));
+        assert_eq!(i.powi(i32::MAX), Complex::new(0.0, -1.0));
+        assert_eq!(i.powu(u32::MAX), Complex::new(0.0, -1.0));
+    }
+
+    #[test]
+    fn single_precision() {
+        let z = Complex::<f32>::new(1.0, 1.0);
+        assert_eq!(z.powi(2), Complex::new(0.0, 2.0));
+        assert_eq!(2.0f32 * z, Complex::new(2.0, 2.0));
+        assert!((z.recip() - Complex::new(0.5, -0.5)).abs() < 1e-6)
+    }
+
+    #[test]
+    fn exp_ln() {
+        assert_close((Complex::i() * PI).exp(), Complex::new(-1.0, 0.0));
+        let z = Complex::new(0.5, -1.5);
+        assert_close(z.ln().exp(), z);
+        assert_close(Complex::new(-1.0, 0.0).ln(), Complex::new(0.0, PI))
+    }
+
+    #[test]
+    fn trig() {
+        let z = Complex::new(0.3, 0.7);
+        let one = z.sin() * z.sin() + z.cos() * z.cos();
+        assert_close(one, Complex::new(1.0, 0.0));
+        let euler = z.cos() + Complex::i() * z.sin();
+        assert_close(euler, (Complex::i() * z).exp())
+    }
+
+    #[test]
+    fn vec_conversion() {
+        let z = Complex::from(Vec2D::new(1.0, 2.0));
+        assert_eq!(z, Complex::new(1.0, 2.0));
+        assert_eq!(Vec2D::from(z), Vec2D::new(1.0, 2.0))
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(format!("{}", Complex::new(1.0, 2.0)), "1+2i");
+        assert_eq!(format!("{}", Complex::new(1.0, -2.0)), "1-2i")
+    }
+}
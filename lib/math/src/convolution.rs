@@ -0,0 +1,501 @@
+use super::{
+    complex::Complex,
+    grid::{Boundary, Grid},
+};
+use std::f64::consts::PI;
+
+/// Below this many multiplications a convolution runs on a single thread
+const PARALLEL_THRESHOLD: usize = 1 << 18;
+/// Non separable kernels with more weights than this use the FFT
+const FFT_THRESHOLD: usize = 31 * 31;
+
+/// Weights of a convolution, anchored at `((width - 1) / 2, (height - 1) / 2)`.
+/// Cell `(x, y)` of the result is the sum of `weight(kx, ky) * grid(x + kx - ax, y + ky - ay)`
+/// over the kernel, so the kernel is not mirrored
+#[derive(Debug, Clone)]
+pub struct Kernel {
+    width: usize,
+    height: usize,
+    /// row major
+    weights: Vec<f64>,
+    /// row and column with `weight(x, y) = row[x] * column[y]` if there are such
+    factors: Option<(Vec<f64>, Vec<f64>)>,
+}
+
+impl Kernel {
+    pub fn new(width: usize, height: usize, weights: Vec<f64>) -> Kernel {
+        assert!(width > 0 && height > 0, "empty kernel");
+        assert_eq!(weights.len(), width * height, "kernel size mismatch");
+        assert!(
+            weights.iter().all(|w| w.is_finite()),
+            "kernel weights not finite"
+        );
+        let factors = Self::factorize(width, height, &weights);
+        Kernel {
+            width,
+            height,
+            weights,
+            factors,
+        }
+    }
+
+    /// `rows[y][x]` is the weight at `(x, y)`
+    pub fn from_rows<const W: usize, const H: usize>(rows: [[f64; W]; H]) -> Kernel {
+        Kernel::new(W, H, rows.iter().flatten().copied().collect())
+    }
+
+    /// `columns[x][y]` is the weight at `(x, y)`, the layout of `Grid::convolute`
+    pub fn from_columns<const W: usize, const H: usize>(columns: [[f64; H]; W]) -> Kernel {
+        let weights = (0..H)
+            .flat_map(|y| columns.iter().map(move |column| column[y]))
+            .collect();
+        Kernel::new(W, H, weights)
+    }
+
+    /// Kernel with `weight(x, y) = row[x] * column[y]`
+    pub fn separable(row: Vec<f64>, column: Vec<f64>) -> Kernel {
+        assert!(!row.is_empty() && !column.is_empty(), "empty kernel");
+        assert!(
+            row.iter().chain(column.iter()).all(|w| w.is_finite()),
+            "kernel weights not finite"
+        );
+        let weights = column
+            .iter()
+            .flat_map(|c| row.iter().map(move |r| r * c))
+            .collect();
+        Kernel {
+            width: row.len(),
+            height: column.len(),
+            weights,
+            factors: Some((row, column)),
+        }
+    }
+
+    /// Normalized gaussian blur cut off at three standard deviations
+    pub fn gaussian(sigma: f64) -> Kernel {
+        assert!(sigma > 0.0 && sigma.is_finite(), "sigma has to be positive");
+        let radius = (3.0 * sigma).ceil().max(0.0) as i64;
+        let weights: Vec<f64> = (-radius..=radius)
+            .map(|x| (-(x * x) as f64 / (2.0 * sigma * sigma)).exp())
+            .collect();
+        let sum: f64 = weights.iter().sum();
+        let weights: Vec<f64> = weights.into_iter().map(|w| w / sum).collect();
+        Kernel::separable(weights.clone(), weights)
+    }
+
+    /// Average over a square of `2 * radius + 1` cells
+    pub fn box_blur(radius: usize) -> Kernel {
+        let size = 2 * radius + 1;
+        let weights = vec![1.0 / size as f64; size];
+        Kernel::separable(weights.clone(), weights)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> f64 {
+        self.weights[y * self.width + x]
+    }
+
+    pub fn is_separable(&self) -> bool {
+        self.factors.is_some()
+    }
+
+    fn anchor(&self) -> (usize, usize) {
+        ((self.width - 1) / 2, (self.height - 1) / 2)
+    }
+
+    /// Splits rank one kernels into a row and a column
+    fn factorize(width: usize, height: usize, weights: &[f64]) -> Option<(Vec<f64>, Vec<f64>)> {
+        let (pivot, max) = weights
+            .iter()
+            .enumerate()
+            .max_by(|(_, w1), (_, w2)| w1.abs().total_cmp(&w2.abs()))?;
+        if *max == 0.0 {
+            return None;
+        }
+        let (pivot_x, pivot_y) = (pivot % width, pivot / width);
+        let row: Vec<f64> = weights[pivot_y * width..(pivot_y + 1) * width].to_vec();
+        let column: Vec<f64> = (0..height)
+            .map(|y| weights[y * width + pivot_x] / max)
+            .collect();
+
+        let tolerance = 1e-12 * max.abs();
+        let separable = (0..height).all(|y| {
+            (0..width).all(|x| (weights[y * width + x] - row[x] * column[y]).abs() <= tolerance)
+        });
+        separable.then_some((row, column))
+    }
+}
+
+impl PartialEq for Kernel {
+    /// Kernels with the same weights are equal however they were factorized
+    fn eq(&self, other: &Kernel) -> bool {
+        self.width == other.width && self.height == other.height && self.weights == other.weights
+    }
+}
+
+/// How `Grid::convolve_using` computes a convolution, all of them give the same result
+/// up to rounding
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ConvolutionMethod {
+    /// Separable kernels run separately, large kernels use the FFT, everything else direct
+    #[default]
+    Auto,
+    Direct,
+    /// A horizontal and a vertical pass, direct for kernels that are not separable
+    Separable,
+    Fft,
+}
+
+impl Grid<f64> {
+    pub fn convolve(&self, kernel: &Kernel, boundary: Boundary) -> Grid<f64> {
+        self.convolve_using(kernel, boundary, ConvolutionMethod::Auto)
+    }
+
+    pub fn convolve_using(
+        &self,
+        kernel: &Kernel,
+        boundary: Boundary,
+        method: ConvolutionMethod,
+    ) -> Grid<f64> {
+        if self.num_cols() == 0 || self.num_rows() == 0 {
+            return self.clone();
+        }
+        let method = match method {
+            ConvolutionMethod::Auto if kernel.is_separable() => ConvolutionMethod::Separable,
+            ConvolutionMethod::Auto if kernel.weights.len() > FFT_THRESHOLD => {
+                ConvolutionMethod::Fft
+            }
+            ConvolutionMethod::Auto => ConvolutionMethod::Direct,
+            method => method,
+        };
+        match (method, &kernel.factors) {
+            (ConvolutionMethod::Separable, Some((row, column))) => {
+                let horizontal = Kernel::new(row.len(), 1, row.clone());
+                let vertical = Kernel::new(1, column.len(), column.clone());
+                direct(&direct(self, &horizontal, boundary), &vertical, boundary)
+            }
+            (ConvolutionMethod::Fft, _) => fft_convolve(self, kernel, boundary),
+            _ => direct(self, kernel, boundary),
+        }
+    }
+}
+
+/// Source index for every position from `-anchor` to `len + size - 1 - anchor`
+fn boundary_map(len: usize, size: usize, anchor: usize, boundary: Boundary) -> Vec<Option<usize>> {
+    (0..len + size - 1)
+        .map(|i| boundary.resolve(i as i64 - anchor as i64, len))
+        .collect()
+}
+
+fn direct(grid: &Grid<f64>, kernel: &Kernel, boundary: Boundary) -> Grid<f64> {
+    let (cols, rows) = (grid.num_cols(), grid.num_rows());
+    let (anchor_x, anchor_y) = kernel.anchor();
+    let col_map = boundary_map(cols, kernel.width, anchor_x, boundary);
+    let row_map = boundary_map(rows, kernel.height, anchor_y, boundary);
+
+    let mut result = vec![0.0; cols * rows];
+    let threads = if cols * rows * kernel.weights.len() < PARALLEL_THRESHOLD {
+        1
+    } else {
+        std::thread::available_parallelism().map_or(1, |n| n.get())
+    };
+    let rows_per_thread = rows.div_ceil(threads);
+    std::thread::scope(|scope| {
+        for (chunk_ind, chunk) in result.chunks_mut(rows_per_thread * cols).enumerate() {
+            let (col_map, row_map) = (&col_map, &row_map);
+            scope.spawn(move || {
+                direct_rows(
+                    grid,
+                    kernel,
+                    col_map,
+                    row_map,
+                    chunk_ind * rows_per_thread,
+                    chunk,
+                )
+            });
+        }
+    });
+    Grid::from_vec(result, cols, rows)
+}
+
+/// Fills `out` with the rows of the result starting at `first_row`
+fn direct_rows(
+    grid: &Grid<f64>,
+    kernel: &Kernel,
+    col_map: &[Option<usize>],
+    row_map: &[Option<usize>],
+    first_row: usize,
+    out: &mut [f64],
+) {
+    let cols = grid.num_cols();
+    let width = kernel.width;
+    let (anchor_x, _) = kernel.anchor();
+    // the kernel fits into the grid for these columns, no boundary lookups needed
+    let interior = anchor_x..(cols + anchor_x + 1).saturating_sub(width).max(anchor_x);
+    let edges: Vec<usize> = (0..cols).filter(|x| !interior.contains(x)).collect();
+
+    for (row_offset, out_row) in out.chunks_mut(cols).enumerate() {
+        let y = first_row + row_offset;
+        for (kernel_y, weights) in kernel.weights.chunks(width).enumerate() {
+            let Some(src_y) = row_map[y + kernel_y] else {
+                continue;
+            };
+            let src_row = grid.row(src_y);
+            // one pass over the row per weight, simple enough to be vectorized
+            for (kernel_x, weight) in weights.iter().enumerate() {
+                if *weight == 0.0 || interior.is_empty() {
+                    continue;
+                }
+                let src = &src_row[interior.start + kernel_x - anchor_x..];
+                for (out, val) in out_row[interior.clone()].iter_mut().zip(src) {
+                    *out += weight * val;
+                }
+            }
+            for x in edges.iter() {
+                out_row[*x] += weights
+                    .iter()
+                    .zip(&col_map[*x..*x + width])
+                    .filter_map(|(w, src_x)| src_x.map(|src_x| w * src_row[src_x]))
+                    .sum::<f64>();
+            }
+        }
+    }
+}
+
+/// In place radix 2 FFT, the length of `data` has to be a power of two.
+/// The inverse transform is scaled by `1 / data.len()`
+fn fft(data: &mut [Complex], inverse: bool) {
+    let len = data.len();
+    if len <= 1 {
+        return;
+    }
+    let bits = len.trailing_zeros();
+    for i in 0..len {
+        let j = i.reverse_bits() >> (usize::BITS - bits);
+        if i < j {
+            data.swap(i, j);
+        }
+    }
+
+    let sign = if inverse { 1.0 } else { -1.0 };
+    let mut size = 2;
+    while size <= len {
+        let step = Complex::from_polar(1.0, sign * 2.0 * PI / size as f64);
+        for chunk in data.chunks_mut(size) {
+            let (lower, upper) = chunk.split_at_mut(size / 2);
+            let mut twiddle = Complex::new(1.0, 0.0);
+            for (a, b) in lower.iter_mut().zip(upper.iter_mut()) {
+                let t = twiddle * *b;
+                *b = *a - t;
+                *a += t;
+                twiddle *= step;
+            }
+        }
+        size *= 2;
+    }
+
+    if inverse {
+        for val in data.iter_mut() {
+            *val /= len as f64;
+        }
+    }
+}
+
+/// FFT of every row then of every column of a row major `width` by `height` array
+fn fft_2d(data: &mut [Complex], width: usize, height: usize, inverse: bool) {
+    for row in data.chunks_mut(width) {
+        fft(row, inverse);
+    }
+    let mut column = vec![Complex::default(); height];
+    for x in 0..width {
+        for (y, val) in column.iter_mut().enumerate() {
+            *val = data[y * width + x];
+        }
+        fft(&mut column, inverse);
+        for (y, val) in column.iter().enumerate() {
+            data[y * width + x] = *val;
+        }
+    }
+}
+
+/// Correlates the grid padded according to `boundary` with the kernel in frequency space,
+/// the padding is large enough that the cyclic convolution never wraps
+fn fft_convolve(grid: &Grid<f64>, kernel: &Kernel, boundary: Boundary) -> Grid<f64> {
+    let (cols, rows) = (grid.num_cols(), grid.num_rows());
+    let (anchor_x, anchor_y) = kernel.anchor();
+    let col_map = boundary_map(cols, kernel.width, anchor_x, boundary);
+    let row_map = boundary_map(rows, kernel.height, anchor_y, boundary);
+    let width = col_map.len().next_power_of_two();
+    let height = row_map.len().next_power_of_two();
+
+    let mut padded = vec![Complex::default(); width * height];
+    for (y, src_y) in row_map.iter().enumerate() {
+        for (x, src_x) in col_map.iter().enumerate() {
+            if let (Some(src_x), Some(src_y)) = (src_x, src_y) {
+                padded[y * width + x] = Complex::from(grid[(*src_x, *src_y)]);
+            }
+        }
+    }
+    let mut weights = vec![Complex::default(); width * height];
+    for (y, row) in kernel.weights.chunks(kernel.width).enumerate() {
+        for (x, w) in row.iter().enumerate() {
+            weights[y * width + x] = Complex::from(*w);
+        }
+    }
+
+    fft_2d(&mut padded, width, height, false);
+    fft_2d(&mut weights, width, height, false);
+    for (val, w) in padded.iter_mut().zip(weights) {
+        *val *= w.conj();
+    }
+    fft_2d(&mut padded, width, height, true);
+
+    Grid::from_fn(|x, y| padded[y * width + x].re, cols, rows)
+}
+
+#[cfg(test)]
+mod convolution_tests {
+    use super::{ConvolutionMethod, Kernel};
+    use crate::grid::{Boundary, Grid};
+
+    const BOUNDARIES: [Boundary; 4] = [
+        Boundary::Zero,
+        Boundary::Clamp,
+        Boundary::Wrap,
+        Boundary::Reflect,
+    ];
+    const METHODS: [ConvolutionMethod; 4] = [
+        ConvolutionMethod::Auto,
+        ConvolutionMethod::Direct,
+        ConvolutionMethod::Separable,
+        ConvolutionMethod::Fft,
+    ];
+
+    fn grid(cols: usize, rows: usize) -> Grid<f64> {
+        Grid::from_fn(
+            |x, y| ((x * 7 + y * 13) % 11) as f64 - 5.0 + 0.1 * x as f64,
+            cols,
+            rows,
+        )
+    }
+
+    /// Straight from the definition, one boundary lookup per weight
+    fn reference(grid: &Grid<f64>, kernel: &Kernel, boundary: Boundary) -> Grid<f64> {
+        let (anchor_x, anchor_y) = kernel.anchor();
+        Grid::from_fn(
+            |x, y| {
+                let mut sum = 0.0;
+                for ky in 0..kernel.height() {
+                    for kx in 0..kernel.width() {
+                        let src_x = (x + kx) as i64 - anchor_x as i64;
+                        let src_y = (y + ky) as i64 - anchor_y as i64;
+                        let val = grid.get_bounded(src_x, src_y, boundary).unwrap_or(&0.0);
+                        sum += kernel.get(kx, ky) * val;
+                    }
+                }
+                sum
+            },
+            grid.num_cols(),
+            grid.num_rows(),
+        )
+    }
+
+    fn assert_close(result: &Grid<f64>, expected: &Grid<f64>) {
+        assert_eq!(result.num_cols(), expected.num_cols());
+        for (r, e) in result.iter().zip(expected.iter()) {
+            assert!((r - e).abs() < 1e-9, "{r} != {e}")
+        }
+    }
+
+    #[test]
+    fn kernel_layouts() {
+        let rows = Kernel::from_rows([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+        assert_eq!((rows.width(), rows.height()), (3, 2));
+        assert_eq!(rows.get(2, 0), 3.0);
+        let columns = Kernel::from_columns([[1.0, 4.0], [2.0, 5.0], [3.0, 6.0]]);
+        assert_eq!(rows, columns);
+    }
+
+    #[test]
+    fn separable_detection() {
+        let outer = Kernel::from_rows([[1.0, 2.0, 1.0], [2.0, 4.0, 2.0], [1.0, 2.0, 1.0]]);
+        assert!(outer.is_separable());
+        assert_eq!(
+            outer,
+            Kernel::separable(vec![1.0, 2.0, 1.0], vec![1.0, 2.0, 1.0])
+        );
+        let laplace = Kernel::from_rows([[0.0, 1.0, 0.0], [1.0, -4.0, 1.0], [0.0, 1.0, 0.0]]);
+        assert!(!laplace.is_separable());
+        assert!(!Kernel::from_rows([[0.0, 0.0]]).is_separable());
+
+        let gaussian = Kernel::gaussian(1.5);
+        assert_eq!(gaussian.width(), 11);
+        assert!(gaussian.is_separable());
+        let sum: f64 = (0..11)
+            .flat_map(|y| (0..11).map(move |x| (x, y)))
+            .map(|(x, y)| gaussian.get(x, y))
+            .sum();
+        assert!((sum - 1.0).abs() < 1e-12)
+    }
+
+    #[test]
+    fn methods_match_reference() {
+        let kernels = [
+            Kernel::from_rows([[0.05, 0.2, 0.05], [0.2, -1.0, 0.2], [0.05, 0.2, 0.05]]),
+            Kernel::from_rows([[1.0, -2.0], [0.5, 3.0], [2.0, 1.0]]),
+            Kernel::gaussian(1.0),
+            Kernel::box_blur(2),
+            // length one transforms along the grid
+            Kernel::from_rows([[1.0], [-2.0], [0.5]]),
+            Kernel::from_rows([[0.5, 2.0, -1.0]]),
+        ];
+        for (cols, rows) in [(9, 7), (2, 3), (1, 1), (1, 4), (5, 1)] {
+            let grid = grid(cols, rows);
+            for kernel in kernels.iter() {
+                for boundary in BOUNDARIES {
+                    let expected = reference(&grid, kernel, boundary);
+                    for method in METHODS {
+                        assert_close(&grid.convolve_using(kernel, boundary, method), &expected)
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn large_kernel_fft() {
+        let grid = grid(40, 30);
+        let weights = (0..33 * 33).map(|i| ((i * 31) % 7) as f64 - 3.0).collect();
+        let kernel = Kernel::new(33, 33, weights);
+        for boundary in BOUNDARIES {
+            let expected = reference(&grid, &kernel, boundary);
+            assert_close(&grid.convolve(&kernel, boundary), &expected)
+        }
+    }
+
+    #[test]
+    fn parallel_rows() {
+        // large enough to be split across threads
+        let grid = grid(301, 299);
+        let kernel = Kernel::from_rows([[0.05, 0.2, 0.05], [0.2, -1.0, 0.2], [0.05, 0.2, 0.05]]);
+        let result = grid.convolve_using(&kernel, Boundary::Wrap, ConvolutionMethod::Direct);
+        assert_close(&result, &reference(&grid, &kernel, Boundary::Wrap))
+    }
+
+    #[test]
+    fn legacy_convolute() {
+        let grid = grid(6, 5);
+        let kernel = [[1.0, 2.0], [3.0, 4.0], [5.0, 6.0]];
+        assert_close(
+            &grid.convolute_with(kernel, Boundary::Clamp),
+            &reference(&grid, &Kernel::from_columns(kernel), Boundary::Clamp),
+        )
+    }
+}
@@ -0,0 +1,340 @@
+use super::{
+    geometry::{Polygon, Rect},
+    vec2d::Vec2D,
+};
+
+/// Delaunay triangulation of a point set built with the Bowyer-Watson algorithm,
+/// no point lies inside the circumcircle of any triangle.
+/// Duplicate points are only triangulated once, collinear point sets have no triangles
+#[derive(Debug, Clone)]
+pub struct Triangulation {
+    pub points: Vec<Vec2D>,
+    /// indices into `points`, counter-clockwise
+    pub triangles: Vec<[usize; 3]>,
+}
+
+/// Center and squared radius of the circle through all three points
+fn circumcircle([a, b, c]: [Vec2D; 3]) -> (Vec2D, f64) {
+    let (ab, ac) = (b - a, c - a);
+    let denom = 2.0 * ab.cross(ac);
+    let (ab_sq, ac_sq) = (ab.dot(ab), ac.dot(ac));
+    let offset = Vec2D::new(ac.y * ab_sq - ab.y * ac_sq, ab.x * ac_sq - ac.x * ab_sq) / denom;
+    (a + offset, offset.dot(offset))
+}
+
+/// Vertices of `tri` in counter-clockwise order
+fn ccw(points: &[Vec2D], [a, b, c]: [usize; 3]) -> [usize; 3] {
+    if (points[b] - points[a]).cross(points[c] - points[a]) < 0.0 {
+        [a, c, b]
+    } else {
+        [a, b, c]
+    }
+}
+
+/// Keeps the side of `poly` closer to `pt` than to `other`
+fn clip_bisector(poly: &Polygon, pt: Vec2D, other: Vec2D) -> Polygon {
+    let normal = other - pt;
+    let offset = normal.dot((pt + other) / 2.0);
+    let side = |v: Vec2D| offset - normal.dot(v);
+
+    let mut result = vec![];
+    for edge in poly.edges() {
+        let (start_side, end_side) = (side(edge.start), side(edge.end));
+        if (start_side >= 0.0) != (end_side >= 0.0) {
+            result.push(edge.at(start_side / (start_side - end_side)));
+        }
+        if end_side >= 0.0 {
+            result.push(edge.end);
+        }
+    }
+    Polygon::new(result)
+}
+
+impl Triangulation {
+    pub fn new(points: &[Vec2D]) -> Triangulation {
+        let mut pts = points.to_vec();
+        let num_points = pts.len();
+        if num_points < 3 {
+            return Triangulation {
+                points: pts,
+                triangles: vec![],
+            };
+        }
+
+        // super triangle containing every point, removed again at the end
+        let (min, max) = points
+            .iter()
+            .fold((points[0], points[0]), |(min, max), pt| {
+                (
+                    Vec2D::new(min.x.min(pt.x), min.y.min(pt.y)),
+                    Vec2D::new(max.x.max(pt.x), max.y.max(pt.y)),
+                )
+            });
+        let bounds = Rect::from_corners(min, max);
+        let center = bounds.center();
+        let size = 20.0 * bounds.width.max(bounds.height).max(1.0);
+        pts.push(center + Vec2D::new(-size, -size));
+        pts.push(center + Vec2D::new(size, -size));
+        pts.push(center + Vec2D::new(0.0, size));
+
+        let super_tri = [num_points, num_points + 1, num_points + 2];
+        let mut triangles: Vec<([usize; 3], (Vec2D, f64))> =
+            vec![(super_tri, circumcircle(super_tri.map(|ind| pts[ind])))];
+
+        for ind in 0..num_points {
+            let pt = pts[ind];
+            if pts[..ind].contains(&pt) {
+                continue;
+            }
+
+            let (bad, good): (Vec<_>, Vec<_>) = triangles
+                .into_iter()
+                .partition(|(_, (center, radius_sq))| pt.dist(center).powi(2) < *radius_sq);
+            triangles = good;
+
+            // edges of the cavity are the ones belonging to a single bad triangle
+            let bad_edges: Vec<[usize; 2]> = bad
+                .iter()
+                .flat_map(|([a, b, c], _)| [[*a, *b], [*b, *c], [*c, *a]])
+                .collect();
+            for [a, b] in bad_edges.iter() {
+                let shared = bad_edges.iter().filter(|[c, d]| c == b && d == a).count();
+                if shared == 0 {
+                    let tri = ccw(&pts, [*a, *b, ind]);
+                    triangles.push((tri, circumcircle(tri.map(|ind| pts[ind]))));
+                }
+            }
+        }
+
+        pts.truncate(num_points);
+        Triangulation {
+            points: pts,
+            triangles: triangles
+                .into_iter()
+                .map(|(tri, _)| tri)
+                .filter(|tri| tri.iter().all(|ind| *ind < num_points))
+                .collect(),
+        }
+    }
+
+    /// Undirected edges, each listed once with the smaller index first
+    pub fn edges(&self) -> Vec<[usize; 2]> {
+        let mut edges: Vec<[usize; 2]> = self
+            .triangles
+            .iter()
+            .flat_map(|[a, b, c]| [[*a, *b], [*b, *c], [*c, *a]])
+            .map(|[a, b]| [a.min(b), a.max(b)])
+            .collect();
+        edges.sort();
+        edges.dedup();
+        edges
+    }
+
+    /// For every triangle the triangles across its edges,
+    /// entry `i` is across the edge opposite of vertex `i`
+    pub fn adjacency(&self) -> Vec<[Option<usize>; 3]> {
+        let mut edge_owner = std::collections::HashMap::new();
+        for (tri_ind, tri) in self.triangles.iter().enumerate() {
+            for i in 0..3 {
+                edge_owner.insert([tri[(i + 1) % 3], tri[(i + 2) % 3]], tri_ind);
+            }
+        }
+        self.triangles
+            .iter()
+            .map(|tri| {
+                [0, 1, 2].map(|i| {
+                    edge_owner
+                        .get(&[tri[(i + 2) % 3], tri[(i + 1) % 3]])
+                        .copied()
+                })
+            })
+            .collect()
+    }
+
+    /// Indices of the points connected to each point by an edge
+    pub fn point_neighbors(&self) -> Vec<Vec<usize>> {
+        let mut neighbors = vec![vec![]; self.points.len()];
+        for [a, b] in self.edges() {
+            neighbors[a].push(b);
+            neighbors[b].push(a);
+        }
+        neighbors
+    }
+
+    pub fn circumcenters(&self) -> Vec<Vec2D> {
+        self.triangles
+            .iter()
+            .map(|tri| circumcircle(tri.map(|ind| self.points[ind])).0)
+            .collect()
+    }
+
+    /// Voronoi cell of every point clipped to `bounds`, the dual of the triangulation.
+    /// Cells of points outside `bounds` or of duplicates may be empty
+    pub fn voronoi(&self, bounds: Rect) -> Vec<Polygon> {
+        let neighbors = self.point_neighbors();
+        let mut seen = vec![];
+        let mut cells = vec![];
+        for (ind, pt) in self.points.iter().enumerate() {
+            if seen.contains(pt) {
+                cells.push(Polygon::new(vec![]));
+                continue;
+            }
+            seen.push(*pt);
+
+            let mut cell = bounds.to_polygon();
+            if !bounds.contains(*pt) {
+                cell.verts.clear();
+            }
+            // without triangles every other point may bound the cell
+            let others: Vec<usize> = if self.triangles.is_empty() {
+                (0..self.points.len())
+                    .filter(|other| *other != ind)
+                    .collect()
+            } else {
+                neighbors[ind].clone()
+            };
+            for other in others {
+                if self.points[other] != *pt {
+                    cell = clip_bisector(&cell, *pt, self.points[other]);
+                }
+            }
+            cells.push(cell);
+        }
+        cells
+    }
+}
+
+/// Moves every point to the centroid of its Voronoi cell `iterations` times,
+/// spreading the points out evenly within `bounds`
+pub fn lloyd_relax(points: &[Vec2D], bounds: Rect, iterations: usize) -> Vec<Vec2D> {
+    let mut points = points.to_vec();
+    for _ in 0..iterations {
+        let cells = Triangulation::new(&points).voronoi(bounds);
+        for (pt, cell) in points.iter_mut().zip(cells) {
+            if cell.area() > 0.0 {
+                *pt = cell.centroid();
+            }
+        }
+    }
+    points
+}
+
+#[cfg(test)]
+mod delaunay_tests {
+    use super::{lloyd_relax, Triangulation};
+    use crate::{geometry::Rect, vec2d::Vec2D};
+
+    fn points(num: usize) -> Vec<Vec2D> {
+        let mut state: u64 = 987;
+        let mut next = || {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (state >> 11) as f64 / (1u64 << 53) as f64
+        };
+        (0..num)
+            .map(|_| Vec2D::new(100.0 * next(), 100.0 * next()))
+            .collect()
+    }
+
+    fn bounds() -> Rect {
+        Rect::new(0.0, 0.0, 100.0, 100.0)
+    }
+
+    #[test]
+    fn square() {
+        let pts = vec![
+            Vec2D::new(0.0, 0.0),
+            Vec2D::new(2.0, 0.0),
+            Vec2D::new(2.0, 1.0),
+            Vec2D::new(0.0, 1.1),
+        ];
+        let tri = Triangulation::new(&pts);
+        assert_eq!(tri.triangles.len(), 2);
+        assert_eq!(tri.edges().len(), 5);
+        let adjacency = tri.adjacency();
+        assert_eq!(adjacency[0].iter().flatten().collect::<Vec<_>>(), vec![&1]);
+        assert_eq!(adjacency[1].iter().flatten().collect::<Vec<_>>(), vec![&0])
+    }
+
+    #[test]
+    fn degenerate() {
+        assert!(Triangulation::new(&[Vec2D::new(1.0, 1.0)])
+            .triangles
+            .is_empty());
+        let line: Vec<Vec2D> = (0..5).map(|i| Vec2D::new(i as f64, 0.0)).collect();
+        assert!(Triangulation::new(&line).triangles.is_empty());
+        let mut pts = points(20);
+        pts.push(pts[0]);
+        assert_eq!(
+            Triangulation::new(&pts).triangles.len(),
+            Triangulation::new(&pts[..20]).triangles.len()
+        )
+    }
+
+    #[test]
+    fn empty_circumcircles() {
+        let pts = points(200);
+        let tri = Triangulation::new(&pts);
+        for (tri_pts, center) in tri.triangles.iter().zip(tri.circumcenters()) {
+            let a = pts[tri_pts[0]];
+            assert!((pts[tri_pts[1]] - a).cross(pts[tri_pts[2]] - a) > 0.0);
+            let radius = center.dist(&a);
+            for pt in pts.iter() {
+                assert!(pt.dist(&center) >= radius - 1e-9)
+            }
+        }
+    }
+
+    #[test]
+    fn euler_characteristic() {
+        let pts = points(200);
+        let tri = Triangulation::new(&pts);
+        let hull = crate::geometry::Polygon::convex_hull(&pts).verts.len();
+        assert_eq!(tri.triangles.len(), 2 * pts.len() - 2 - hull);
+        assert_eq!(tri.edges().len(), 3 * pts.len() - 3 - hull);
+
+        // every interior edge is shared by exactly two triangles
+        let shared: usize = tri
+            .adjacency()
+            .iter()
+            .map(|adj| adj.iter().flatten().count())
+            .sum();
+        assert_eq!(shared, 2 * (tri.edges().len() - hull))
+    }
+
+    #[test]
+    fn voronoi_cells() {
+        let pts = points(100);
+        let cells = Triangulation::new(&pts).voronoi(bounds());
+        let total: f64 = cells.iter().map(|cell| cell.area()).sum();
+        assert!((total - bounds().area()).abs() < 1e-6);
+
+        for (pt, cell) in pts.iter().zip(cells.iter()) {
+            assert!(cell.contains(*pt));
+            // every cell vertex is at least as close to its own point as to any other
+            for vert in cell.verts.iter() {
+                let own = vert.dist(pt);
+                assert!(pts.iter().all(|other| vert.dist(other) >= own - 1e-9))
+            }
+        }
+    }
+
+    #[test]
+    fn lloyd() {
+        let pts = points(50);
+        let spread = |pts: &[Vec2D]| {
+            let areas: Vec<f64> = Triangulation::new(pts)
+                .voronoi(bounds())
+                .iter()
+                .map(|cell| cell.area())
+                .collect();
+            let mean = areas.iter().sum::<f64>() / areas.len() as f64;
+            areas.iter().map(|area| (area - mean).powi(2)).sum::<f64>()
+        };
+        let relaxed = lloyd_relax(&pts, bounds(), 10);
+        assert_eq!(relaxed.len(), pts.len());
+        assert!(relaxed.iter().all(|pt| bounds().contains(*pt)));
+        assert!(spread(&relaxed) < spread(&pts) / 4.0)
+    }
+}
@@ -0,0 +1,79 @@
+use super::Segment;
+use crate::vec2d::Vec2D;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Circle {
+    pub center: Vec2D,
+    pub radius: f64,
+}
+
+impl Circle {
+    pub fn new(center: Vec2D, radius: f64) -> Circle {
+        Circle { center, radius }
+    }
+
+    pub fn area(&self) -> f64 {
+        std::f64::consts::PI * self.radius * self.radius
+    }
+
+    pub fn contains(&self, pt: Vec2D) -> bool {
+        self.center.dist(&pt) <= self.radius
+    }
+
+    pub fn intersects(&self, other: &Circle) -> bool {
+        self.center.dist(&other.center) <= self.radius + other.radius
+    }
+
+    /// True if any part of the segment lies in the circle
+    pub fn intersects_segment(&self, seg: &Segment) -> bool {
+        seg.dist_to_point(self.center) <= self.radius
+    }
+
+    /// Points where the segment crosses the border, ordered from `seg.start` to `seg.end`
+    pub fn segment_intersections(&self, seg: &Segment) -> Vec<Vec2D> {
+        let dir = seg.direction();
+        let diff = seg.start - self.center;
+        let a = dir.dot(dir);
+        let b = 2.0 * diff.dot(dir);
+        let c = diff.dot(diff) - self.radius * self.radius;
+        let discr = b * b - 4.0 * a * c;
+        if a == 0.0 || discr < 0.0 {
+            return vec![];
+        }
+
+        let sqrt = discr.sqrt();
+        let mut ts = vec![(-b - sqrt) / (2.0 * a)];
+        if discr > 0.0 {
+            ts.push((-b + sqrt) / (2.0 * a));
+        }
+        ts.into_iter()
+            .filter(|t| (0.0..=1.0).contains(t))
+            .map(|t| seg.at(t))
+            .collect()
+    }
+
+    /// Points where the borders of both circles meet,
+    /// empty if one circle lies within the other or they are apart
+    pub fn circle_intersections(&self, other: &Circle) -> Vec<Vec2D> {
+        let diff = other.center - self.center;
+        let dist = diff.abs();
+        if dist == 0.0
+            || dist > self.radius + other.radius
+            || dist < (self.radius - other.radius).abs()
+        {
+            return vec![];
+        }
+
+        // distance from self.center to the chord through both intersections
+        let along =
+            (dist * dist + self.radius * self.radius - other.radius * other.radius) / (2.0 * dist);
+        let half_chord = (self.radius * self.radius - along * along).max(0.0).sqrt();
+        let mid = self.center + (along / dist) * diff;
+        let offset = (half_chord / dist) * Vec2D::new(-diff.y, diff.x);
+        if half_chord == 0.0 {
+            vec![mid]
+        } else {
+            vec![mid + offset, mid - offset]
+        }
+    }
+}
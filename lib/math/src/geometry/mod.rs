@@ -0,0 +1,259 @@
+pub mod circle;
+pub mod polygon;
+pub mod rect;
+pub mod segment;
+pub use circle::Circle;
+pub use polygon::Polygon;
+pub use rect::Rect;
+pub use segment::Segment;
+
+use super::vec2d::Vec2D;
+
+/// Smallest and largest projection of `pts` onto `axis`
+fn project<'a, I: IntoIterator<Item = &'a Vec2D>>(pts: I, axis: Vec2D) -> [f64; 2] {
+    pts.into_iter()
+        .map(|pt| pt.dot(axis))
+        .fold([f64::INFINITY, f64::NEG_INFINITY], |[min, max], proj| {
+            [min.min(proj), max.max(proj)]
+        })
+}
+
+/// Signed overlap of two projections, the sign says which way to push the first one
+/// out of the second, `None` if they are separated or only touch
+fn overlap([min1, max1]: [f64; 2], [min2, max2]: [f64; 2]) -> Option<f64> {
+    if max1 <= min2 || max2 <= min1 {
+        return None;
+    }
+    let push_back = max1 - min2;
+    let push_forward = max2 - min1;
+    if push_back < push_forward {
+        Some(-push_back)
+    } else {
+        Some(push_forward)
+    }
+}
+
+#[cfg(test)]
+mod geometry_tests {
+    use super::{Circle, Polygon, Rect, Segment};
+    use crate::vec2d::Vec2D;
+
+    fn assert_close(v1: Vec2D, v2: Vec2D) {
+        assert!(v1.dist(&v2) < 1e-9, "{v1} != {v2}")
+    }
+
+    fn square(x: f64, y: f64, size: f64) -> Polygon {
+        Rect::new(x, y, size, size).to_polygon()
+    }
+
+    /// U shaped polygon opening upwards
+    fn concave() -> Polygon {
+        Polygon::new(vec![
+            Vec2D::new(0.0, 0.0),
+            Vec2D::new(3.0, 0.0),
+            Vec2D::new(3.0, 3.0),
+            Vec2D::new(2.0, 3.0),
+            Vec2D::new(2.0, 1.0),
+            Vec2D::new(1.0, 1.0),
+            Vec2D::new(1.0, 3.0),
+            Vec2D::new(0.0, 3.0),
+        ])
+    }
+
+    #[test]
+    fn segment_intersection() {
+        let seg1 = Segment::new(Vec2D::new(0.0, 0.0), Vec2D::new(2.0, 2.0));
+        let seg2 = Segment::new(Vec2D::new(0.0, 2.0), Vec2D::new(2.0, 0.0));
+        assert_close(seg1.intersection(&seg2).unwrap(), Vec2D::new(1.0, 1.0));
+        let short = Segment::new(Vec2D::new(0.0, 2.0), Vec2D::new(0.5, 1.5));
+        assert_eq!(seg1.intersection(&short), None);
+        assert!(!seg1.intersects(&short));
+        // touching at an end point
+        let touching = Segment::new(Vec2D::new(2.0, 2.0), Vec2D::new(3.0, 0.0));
+        assert_close(seg1.intersection(&touching).unwrap(), Vec2D::new(2.0, 2.0));
+    }
+
+    #[test]
+    fn segment_parallel() {
+        let seg1 = Segment::new(Vec2D::new(0.0, 0.0), Vec2D::new(2.0, 0.0));
+        let parallel = Segment::new(Vec2D::new(0.0, 1.0), Vec2D::new(2.0, 1.0));
+        assert_eq!(seg1.intersection(&parallel), None);
+        let collinear = Segment::new(Vec2D::new(3.0, 0.0), Vec2D::new(1.0, 0.0));
+        assert_close(seg1.intersection(&collinear).unwrap(), Vec2D::new(1.0, 0.0));
+        let disjoint = Segment::new(Vec2D::new(3.0, 0.0), Vec2D::new(4.0, 0.0));
+        assert!(!seg1.intersects(&disjoint))
+    }
+
+    #[test]
+    fn segment_closest_point() {
+        let seg = Segment::new(Vec2D::new(0.0, 0.0), Vec2D::new(4.0, 0.0));
+        assert_close(
+            seg.closest_point(Vec2D::new(1.0, 3.0)),
+            Vec2D::new(1.0, 0.0),
+        );
+        assert_close(
+            seg.closest_point(Vec2D::new(-2.0, 1.0)),
+            Vec2D::new(0.0, 0.0),
+        );
+        assert_eq!(seg.dist_to_point(Vec2D::new(7.0, 4.0)), 5.0);
+        assert_eq!(seg.length(), 4.0)
+    }
+
+    #[test]
+    fn rect() {
+        let rect = Rect::new(1.0, 2.0, 3.0, 4.0);
+        assert!(rect.contains(Vec2D::new(1.0, 2.0)));
+        assert!(rect.contains(Vec2D::new(4.0, 6.0)));
+        assert!(!rect.contains(Vec2D::new(4.1, 3.0)));
+        assert_eq!(rect.center(), Vec2D::new(2.5, 4.0));
+        assert_eq!(rect.area(), 12.0);
+
+        let other = Rect::from_corners(Vec2D::new(5.0, 5.0), Vec2D::new(3.0, 3.0));
+        assert_eq!(other, Rect::new(3.0, 3.0, 2.0, 2.0));
+        assert!(rect.intersects(&other));
+        assert_eq!(
+            rect.intersection(&other),
+            Some(Rect::new(3.0, 3.0, 1.0, 2.0))
+        );
+        assert_eq!(rect.intersection(&Rect::new(10.0, 0.0, 1.0, 1.0)), None)
+    }
+
+    #[test]
+    fn circle() {
+        let circle = Circle::new(Vec2D::new(0.0, 0.0), 2.0);
+        assert!(circle.contains(Vec2D::new(1.0, 1.0)));
+        assert!(!circle.contains(Vec2D::new(2.0, 1.0)));
+        assert!(circle.intersects(&Circle::new(Vec2D::new(3.0, 0.0), 1.5)));
+        assert!(!circle.intersects(&Circle::new(Vec2D::new(3.0, 0.0), 0.5)));
+
+        let hits = circle.circle_intersections(&Circle::new(Vec2D::new(2.0, 0.0), 2.0));
+        assert_eq!(hits.len(), 2);
+        for hit in hits {
+            assert!((hit.x - 1.0).abs() < 1e-9);
+            assert!((hit.y.abs() - 3.0f64.sqrt()).abs() < 1e-9)
+        }
+        assert!(circle
+            .circle_intersections(&Circle::new(Vec2D::new(0.5, 0.0), 0.5))
+            .is_empty());
+    }
+
+    #[test]
+    fn circle_segment() {
+        let circle = Circle::new(Vec2D::new(0.0, 0.0), 1.0);
+        let through = Segment::new(Vec2D::new(-2.0, 0.0), Vec2D::new(2.0, 0.0));
+        let hits = circle.segment_intersections(&through);
+        assert_eq!(hits.len(), 2);
+        assert_close(hits[0], Vec2D::new(-1.0, 0.0));
+        assert_close(hits[1], Vec2D::new(1.0, 0.0));
+        assert!(circle.intersects_segment(&through));
+
+        let half = Segment::new(Vec2D::new(0.0, 0.0), Vec2D::new(2.0, 0.0));
+        assert_eq!(circle.segment_intersections(&half).len(), 1);
+        // fully inside, no boundary crossing but still intersecting
+        let inside = Segment::new(Vec2D::new(-0.5, 0.0), Vec2D::new(0.5, 0.0));
+        assert!(circle.segment_intersections(&inside).is_empty());
+        assert!(circle.intersects_segment(&inside));
+        let outside = Segment::new(Vec2D::new(-2.0, 1.5), Vec2D::new(2.0, 1.5));
+        assert!(!circle.intersects_segment(&outside))
+    }
+
+    #[test]
+    fn area_and_centroid() {
+        let sq = square(1.0, 1.0, 2.0);
+        assert_eq!(sq.area(), 4.0);
+        assert_close(sq.centroid(), Vec2D::new(2.0, 2.0));
+        let reversed = Polygon::new(sq.verts.iter().rev().copied().collect());
+        assert_eq!(reversed.signed_area(), -sq.signed_area());
+        assert_close(reversed.centroid(), Vec2D::new(2.0, 2.0));
+
+        let u = concave();
+        assert_eq!(u.area(), 7.0);
+        // symmetric around x = 1.5, mass pulled to the bottom bar
+        let centroid = u.centroid();
+        assert!((centroid.x - 1.5).abs() < 1e-9);
+        assert!((centroid.y - 9.5 / 7.0).abs() < 1e-9)
+    }
+
+    #[test]
+    fn contains_concave() {
+        let u = concave();
+        assert!(u.contains(Vec2D::new(0.5, 2.5)));
+        assert!(u.contains(Vec2D::new(1.5, 0.5)));
+        assert!(!u.contains(Vec2D::new(1.5, 2.0)));
+        assert!(!u.contains(Vec2D::new(-1.0, 0.5)));
+        assert!(!u.is_convex());
+        assert!(square(0.0, 0.0, 1.0).is_convex())
+    }
+
+    #[test]
+    fn convex_hull() {
+        let mut pts = concave().verts;
+        pts.push(Vec2D::new(1.5, 1.5));
+        pts.push(Vec2D::new(0.0, 1.5));
+        let hull = Polygon::convex_hull(&pts);
+        assert_eq!(
+            hull.verts,
+            vec![
+                Vec2D::new(0.0, 0.0),
+                Vec2D::new(3.0, 0.0),
+                Vec2D::new(3.0, 3.0),
+                Vec2D::new(0.0, 3.0)
+            ]
+        );
+        assert!(hull.signed_area() > 0.0);
+        assert!(Polygon::convex_hull(&[Vec2D::new(1.0, 1.0)]).verts.len() == 1)
+    }
+
+    #[test]
+    fn sat() {
+        let sq1 = square(0.0, 0.0, 2.0);
+        let sq2 = square(1.5, 0.5, 2.0);
+        let mtv = sq1.sat(&sq2).unwrap();
+        assert_close(mtv, Vec2D::new(-0.5, 0.0));
+        assert!(sq1.translated(mtv).sat(&sq2).is_none());
+        assert_close(sq2.sat(&sq1).unwrap(), Vec2D::new(0.5, 0.0));
+
+        assert!(sq1.sat(&square(2.0, 0.0, 1.0)).is_none());
+        let triangle = Polygon::new(vec![
+            Vec2D::new(3.0, 0.0),
+            Vec2D::new(5.0, 0.0),
+            Vec2D::new(3.0, 2.0),
+        ]);
+        assert!(sq1.sat(&triangle).is_none());
+        assert!(sq1.translated(Vec2D::new(1.5, 0.0)).intersects(&triangle))
+    }
+
+    #[test]
+    fn sat_circle() {
+        let sq = square(0.0, 0.0, 2.0);
+        let mtv = sq
+            .sat_circle(&Circle::new(Vec2D::new(2.5, 1.0), 1.0))
+            .unwrap();
+        assert_close(mtv, Vec2D::new(-0.5, 0.0));
+        // near a corner the axis through the closest vertex separates them
+        assert!(sq
+            .sat_circle(&Circle::new(Vec2D::new(2.8, 2.8), 1.0))
+            .is_none());
+        assert!(sq
+            .sat_circle(&Circle::new(Vec2D::new(1.0, 1.0), 0.1))
+            .is_some())
+    }
+
+    #[test]
+    fn clip() {
+        let clipped = square(0.0, 0.0, 2.0).clip(&square(1.0, 1.0, 2.0));
+        assert_eq!(clipped.area(), 1.0);
+        assert_close(clipped.centroid(), Vec2D::new(1.5, 1.5));
+
+        // concave subjects work against a convex clip region
+        let clipped = concave().clip(&Rect::new(0.0, 0.5, 3.0, 2.0).to_polygon());
+        assert!((clipped.area() - (3.0 * 0.5 + 2.0 * 1.5)).abs() < 1e-9);
+
+        let reversed = Polygon::new(square(1.0, 1.0, 2.0).verts.into_iter().rev().collect());
+        assert_eq!(square(0.0, 0.0, 2.0).clip(&reversed).area(), 1.0);
+        assert!(square(0.0, 0.0, 1.0)
+            .clip(&square(5.0, 5.0, 1.0))
+            .verts
+            .is_empty())
+    }
+}
@@ -0,0 +1,215 @@
+use super::{overlap, project, Circle, Segment};
+use crate::vec2d::Vec2D;
+
+/// Closed polygon through `verts`, the last vertex connects back to the first.
+/// Both orientations and concave shapes are allowed unless a method says otherwise
+#[derive(Debug, Clone, PartialEq)]
+pub struct Polygon {
+    pub verts: Vec<Vec2D>,
+}
+
+impl Polygon {
+    pub fn new(verts: Vec<Vec2D>) -> Polygon {
+        Polygon { verts }
+    }
+
+    /// Smallest convex polygon containing all `pts`, counter-clockwise
+    /// without collinear vertices
+    pub fn convex_hull(pts: &[Vec2D]) -> Polygon {
+        let mut pts = pts.to_vec();
+        pts.sort_by(|p1, p2| {
+            (p1.x, p1.y)
+                .partial_cmp(&(p2.x, p2.y))
+                .expect("convex hull of NaN")
+        });
+        pts.dedup();
+        if pts.len() < 3 {
+            return Polygon::new(pts);
+        }
+
+        // Andrew's monotone chain, lower hull then upper hull
+        let mut hull: Vec<Vec2D> = vec![];
+        for pass in [pts.clone(), pts.into_iter().rev().collect()] {
+            let start = hull.len();
+            for pt in pass {
+                while hull.len() >= start + 2 {
+                    let [a, b] = [hull[hull.len() - 2], hull[hull.len() - 1]];
+                    if (b - a).cross(pt - a) > 0.0 {
+                        break;
+                    }
+                    hull.pop();
+                }
+                hull.push(pt);
+            }
+            // the last point starts the next pass
+            hull.pop();
+        }
+        Polygon::new(hull)
+    }
+
+    pub fn edges(&self) -> Vec<Segment> {
+        let next = self.verts.iter().cycle().skip(1);
+        self.verts
+            .iter()
+            .zip(next)
+            .map(|(start, end)| Segment::new(*start, *end))
+            .collect()
+    }
+
+    /// Unit normals of all edges with nonzero length,
+    /// pointing outwards for counter-clockwise polygons
+    pub fn normals(&self) -> Vec<Vec2D> {
+        self.edges()
+            .into_iter()
+            .map(|edge| edge.direction())
+            .filter(|dir| *dir != Vec2D::default())
+            .map(|dir| Vec2D::new(dir.y, -dir.x) / dir.abs())
+            .collect()
+    }
+
+    pub fn translated(&self, offset: Vec2D) -> Polygon {
+        Polygon::new(self.verts.iter().map(|vert| *vert + offset).collect())
+    }
+
+    /// Positive for counter-clockwise vertices, negative otherwise
+    pub fn signed_area(&self) -> f64 {
+        self.edges()
+            .iter()
+            .map(|edge| edge.start.cross(edge.end))
+            .sum::<f64>()
+            / 2.0
+    }
+
+    pub fn area(&self) -> f64 {
+        self.signed_area().abs()
+    }
+
+    /// Center of mass, the average vertex for polygons without area
+    pub fn centroid(&self) -> Vec2D {
+        let area = self.signed_area();
+        if area == 0.0 {
+            let sum = self.verts.iter().fold(Vec2D::default(), |sum, v| sum + *v);
+            return sum / self.verts.len().max(1) as f64;
+        }
+        let weighted = self.edges().iter().fold(Vec2D::default(), |sum, edge| {
+            sum + edge.start.cross(edge.end) * (edge.start + edge.end)
+        });
+        weighted / (6.0 * area)
+    }
+
+    pub fn is_convex(&self) -> bool {
+        let edges = self.edges();
+        let turns = edges
+            .iter()
+            .zip(edges.iter().cycle().skip(1))
+            .map(|(edge, next)| edge.direction().cross(next.direction()));
+        let mut sign = 0.0;
+        for turn in turns {
+            if turn == 0.0 {
+                continue;
+            }
+            if sign * turn < 0.0 {
+                return false;
+            }
+            sign = turn;
+        }
+        true
+    }
+
+    /// Even-odd rule, works for concave polygons as well
+    pub fn contains(&self, pt: Vec2D) -> bool {
+        let mut inside = false;
+        for edge in self.edges() {
+            let (a, b) = (edge.start, edge.end);
+            if (a.y > pt.y) != (b.y > pt.y) {
+                let cross_x = a.x + (pt.y - a.y) / (b.y - a.y) * (b.x - a.x);
+                if pt.x < cross_x {
+                    inside = !inside;
+                }
+            }
+        }
+        inside
+    }
+
+    pub fn project(&self, axis: Vec2D) -> [f64; 2] {
+        project(&self.verts, axis)
+    }
+
+    /// Smallest translation moving `self` out of `other` along one of the `axes`
+    fn min_translation(
+        axes: Vec<Vec2D>,
+        proj1: impl Fn(Vec2D) -> [f64; 2],
+        proj2: impl Fn(Vec2D) -> [f64; 2],
+    ) -> Option<Vec2D> {
+        let mut mtv: Option<(f64, Vec2D)> = None;
+        for axis in axes {
+            let depth = overlap(proj1(axis), proj2(axis))?;
+            if mtv.is_none_or(|(min, _)| depth.abs() < min) {
+                mtv = Some((depth.abs(), depth * axis));
+            }
+        }
+        mtv.map(|(_, translation)| translation)
+    }
+
+    /// Separating axis test for convex polygons, returns the minimum translation
+    /// vector that moves `self` out of `other` if they overlap
+    pub fn sat(&self, other: &Polygon) -> Option<Vec2D> {
+        let mut axes = self.normals();
+        axes.extend(other.normals());
+        Self::min_translation(axes, |axis| self.project(axis), |axis| other.project(axis))
+    }
+
+    pub fn intersects(&self, other: &Polygon) -> bool {
+        self.sat(other).is_some()
+    }
+
+    /// Same as `sat` with a circle instead of a second polygon
+    pub fn sat_circle(&self, circle: &Circle) -> Option<Vec2D> {
+        let mut axes = self.normals();
+        let closest = self.verts.iter().min_by(|v1, v2| {
+            let (d1, d2) = (v1.dist(&circle.center), v2.dist(&circle.center));
+            d1.partial_cmp(&d2).unwrap()
+        });
+        if let Some(closest) = closest {
+            let to_center = circle.center - *closest;
+            if to_center != Vec2D::default() {
+                axes.push(to_center / to_center.abs());
+            }
+        }
+        Self::min_translation(
+            axes,
+            |axis| self.project(axis),
+            |axis| {
+                let center = circle.center.dot(axis);
+                [center - circle.radius, center + circle.radius]
+            },
+        )
+    }
+
+    /// Part of `self` inside the convex polygon `clip` (Sutherland-Hodgman),
+    /// concave subjects may end up with edges running along the border of `clip`
+    pub fn clip(&self, clip: &Polygon) -> Polygon {
+        let orientation = clip.signed_area().signum();
+        let mut result = self.verts.clone();
+        for edge in clip.edges() {
+            if result.is_empty() {
+                break;
+            }
+            let inside = |pt: Vec2D| orientation * edge.direction().cross(pt - edge.start) >= 0.0;
+            let input = Polygon::new(std::mem::take(&mut result));
+            for seg in input.edges() {
+                let (start_in, end_in) = (inside(seg.start), inside(seg.end));
+                if start_in != end_in {
+                    // the lines can not be parallel if the points are on different sides
+                    let dir = edge.direction();
+                    let t = (edge.start - seg.start).cross(dir) / seg.direction().cross(dir);
+                    result.push(seg.at(t));
+                }
+                if end_in {
+                    result.push(seg.end);
+                }
+            }
+        }
+        Polygon::new(result)
+    }
+}
@@ -0,0 +1,75 @@
+use super::Polygon;
+use crate::vec2d::Vec2D;
+
+/// Axis aligned rectangle with `pos` as its corner with the smallest coordinates
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rect {
+    pub pos: Vec2D,
+    pub width: f64,
+    pub height: f64,
+}
+
+impl Rect {
+    pub fn new(x: f64, y: f64, width: f64, height: f64) -> Rect {
+        Rect {
+            pos: Vec2D::new(x, y),
+            width,
+            height,
+        }
+    }
+
+    /// Rectangle spanned by two opposite corners in any order
+    pub fn from_corners(corner1: Vec2D, corner2: Vec2D) -> Rect {
+        let min = Vec2D::new(corner1.x.min(corner2.x), corner1.y.min(corner2.y));
+        Rect {
+            pos: min,
+            width: (corner1.x - corner2.x).abs(),
+            height: (corner1.y - corner2.y).abs(),
+        }
+    }
+
+    pub fn min(&self) -> Vec2D {
+        self.pos
+    }
+
+    pub fn max(&self) -> Vec2D {
+        self.pos + Vec2D::new(self.width, self.height)
+    }
+
+    pub fn center(&self) -> Vec2D {
+        self.pos + Vec2D::new(self.width / 2.0, self.height / 2.0)
+    }
+
+    pub fn area(&self) -> f64 {
+        self.width * self.height
+    }
+
+    /// Points on the border count as inside
+    pub fn contains(&self, pt: Vec2D) -> bool {
+        let max = self.max();
+        pt.x >= self.pos.x && pt.x <= max.x && pt.y >= self.pos.y && pt.y <= max.y
+    }
+
+    pub fn intersects(&self, other: &Rect) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    /// Overlapping region, `None` if the rectangles only touch or are apart
+    pub fn intersection(&self, other: &Rect) -> Option<Rect> {
+        let (max1, max2) = (self.max(), other.max());
+        let min = Vec2D::new(self.pos.x.max(other.pos.x), self.pos.y.max(other.pos.y));
+        let max = Vec2D::new(max1.x.min(max2.x), max1.y.min(max2.y));
+        (min.x < max.x && min.y < max.y).then(|| Rect::from_corners(min, max))
+    }
+
+    /// Corners in counter-clockwise order starting at `pos`
+    pub fn to_polygon(&self) -> Polygon {
+        let max = self.max();
+        Polygon::new(vec![
+            self.pos,
+            Vec2D::new(max.x, self.pos.y),
+            max,
+            Vec2D::new(self.pos.x, max.y),
+        ])
+    }
+}
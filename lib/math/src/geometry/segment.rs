@@ -0,0 +1,76 @@
+use crate::vec2d::Vec2D;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Segment {
+    pub start: Vec2D,
+    pub end: Vec2D,
+}
+
+impl Segment {
+    pub fn new(start: Vec2D, end: Vec2D) -> Segment {
+        Segment { start, end }
+    }
+
+    /// Vector from start to end
+    pub fn direction(&self) -> Vec2D {
+        self.end - self.start
+    }
+
+    pub fn length(&self) -> f64 {
+        self.direction().abs()
+    }
+
+    pub fn midpoint(&self) -> Vec2D {
+        (self.start + self.end) / 2.0
+    }
+
+    /// Point at `t` along the segment, 0 is the start and 1 the end
+    pub fn at(&self, t: f64) -> Vec2D {
+        self.start + t * self.direction()
+    }
+
+    pub fn closest_point(&self, pt: Vec2D) -> Vec2D {
+        let dir = self.direction();
+        let len_sq = dir.dot(dir);
+        if len_sq == 0.0 {
+            return self.start;
+        }
+        self.at(((pt - self.start).dot(dir) / len_sq).clamp(0.0, 1.0))
+    }
+
+    pub fn dist_to_point(&self, pt: Vec2D) -> f64 {
+        self.closest_point(pt).dist(&pt)
+    }
+
+    /// Point where both segments meet, end points included.
+    /// Overlapping collinear segments return the first shared point along `self`,
+    /// parallel ones return `None`
+    pub fn intersection(&self, other: &Segment) -> Option<Vec2D> {
+        let dir = self.direction();
+        let other_dir = other.direction();
+        let denom = dir.cross(other_dir);
+        let diff = other.start - self.start;
+
+        if denom == 0.0 {
+            if diff.cross(dir) != 0.0 {
+                return None;
+            }
+            let len_sq = dir.dot(dir);
+            if len_sq == 0.0 {
+                return (other.dist_to_point(self.start) == 0.0).then_some(self.start);
+            }
+            let t0 = diff.dot(dir) / len_sq;
+            let t1 = (other.end - self.start).dot(dir) / len_sq;
+            let (t_min, t_max) = (t0.min(t1).max(0.0), t0.max(t1).min(1.0));
+            return (t_min <= t_max).then(|| self.at(t_min));
+        }
+
+        let t = diff.cross(other_dir) / denom;
+        let u = diff.cross(dir) / denom;
+        ((0.0..=1.0).contains(&t) && (0.0..=1.0).contains(&u)).then(|| self.at(t))
+    }
+
+    pub fn intersects(&self, other: &Segment) -> bool {
+        self.intersection(other).is_some()
+    }
+}
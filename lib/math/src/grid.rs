@@ -1,8 +1,100 @@
+use super::convolution::Kernel;
 use std::{
+    collections::VecDeque,
     fmt,
     ops::{Index, IndexMut},
 };
 
+/// How coordinates outside of a grid are mapped back into it
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Boundary {
+    /// Outside cells do not exist, lookups return nothing and convolutions read zero
+    #[default]
+    Zero,
+    /// Coordinates are clamped to the nearest edge cell
+    Clamp,
+    /// The grid repeats in every direction (toroidal)
+    Wrap,
+    /// The grid is mirrored at its edges without repeating the edge cell,
+    /// so -1 maps to 1
+    Reflect,
+}
+
+/// Signed grid coordinates that wrap around the edges when indexing,
+/// `grid[Wrapped(-1, 0)]` is the last cell of the first row
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Wrapped(pub i64, pub i64);
+
+impl Boundary {
+    /// Index within `0..len` that `coord` maps to, `None` for cells that do not exist
+    pub fn resolve(&self, coord: i64, len: usize) -> Option<usize> {
+        let len = len as i64;
+        if (0..len).contains(&coord) {
+            return Some(coord as usize);
+        }
+        if len == 0 {
+            return None;
+        }
+        let resolved = match self {
+            Boundary::Zero => return None,
+            Boundary::Clamp => coord.clamp(0, len - 1),
+            Boundary::Wrap => coord.rem_euclid(len),
+            Boundary::Reflect if len == 1 => 0,
+            Boundary::Reflect => {
+                let period = 2 * (len - 1);
+                let folded = coord.rem_euclid(period);
+                if folded < len {
+                    folded
+                } else {
+                    period - folded
+                }
+            }
+        };
+        Some(resolved as usize)
+    }
+}
+
+/// Which cells count as neighbors of a cell
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Neighborhood {
+    /// The 4 cells sharing an edge
+    VonNeumann,
+    /// The 8 cells sharing an edge or a corner
+    Moore,
+    /// The 6 cells of a hexagonal grid where odd rows are shifted right by half a cell
+    Hex,
+}
+
+impl Neighborhood {
+    /// Offsets from a cell in row `y` to its neighbors
+    pub fn offsets(&self, y: i64) -> Vec<(i64, i64)> {
+        match self {
+            Neighborhood::VonNeumann => vec![(0, -1), (-1, 0), (1, 0), (0, 1)],
+            Neighborhood::Moore => vec![
+                (-1, -1),
+                (0, -1),
+                (1, -1),
+                (-1, 0),
+                (1, 0),
+                (-1, 1),
+                (0, 1),
+                (1, 1),
+            ],
+            Neighborhood::Hex => {
+                let shift = y.rem_euclid(2);
+                vec![
+                    (shift - 1, -1),
+                    (shift, -1),
+                    (-1, 0),
+                    (1, 0),
+                    (shift - 1, 1),
+                    (shift, 1),
+                ]
+            }
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct Grid<T> {
     num_rows: usize,
@@ -29,6 +121,16 @@ impl<T> Grid<T> {
         grid
     }
 
+    /// Grid over row major `elements`
+    pub fn from_vec(elements: Vec<T>, num_cols: usize, num_rows: usize) -> Grid<T> {
+        assert_eq!(elements.len(), num_cols * num_rows, "grid size mismatch");
+        Grid {
+            num_rows,
+            num_cols,
+            elements,
+        }
+    }
+
     pub fn insert(&mut self, x: usize, y: usize, elem: T)
     where
         T: Default,
@@ -47,10 +149,31 @@ impl<T> Grid<T> {
         Some(y * self.num_cols + x)
     }
 
+    pub fn num_cols(&self) -> usize {
+        self.num_cols
+    }
+
+    pub fn num_rows(&self) -> usize {
+        self.num_rows
+    }
+
     pub fn get(&self, x: usize, y: usize) -> Option<&T> {
         self.elements.get(self.ind(x, y)?)
     }
 
+    /// Maps `(x, y)` into the grid according to `boundary`
+    pub fn resolve(&self, x: i64, y: i64, boundary: Boundary) -> Option<(usize, usize)> {
+        Some((
+            boundary.resolve(x, self.num_cols)?,
+            boundary.resolve(y, self.num_rows)?,
+        ))
+    }
+
+    pub fn get_bounded(&self, x: i64, y: i64, boundary: Boundary) -> Option<&T> {
+        let (x, y) = self.resolve(x, y, boundary)?;
+        self.get(x, y)
+    }
+
     pub fn get_or_default(&self, x: i64, y: i64) -> T
     where
         T: Default + Clone,
@@ -68,6 +191,21 @@ impl<T> Grid<T> {
         self.elements.get_mut(ind)
     }
 
+    /// Signed coordinates wrapping around the edges.
+    ///
+    /// Panics if the grid is empty
+    pub fn get_wrapped(&self, x: i64, y: i64) -> &T {
+        self.get_bounded(x, y, Boundary::Wrap).unwrap()
+    }
+
+    /// Mutable access with signed coordinates wrapping around the edges.
+    ///
+    /// Panics if the grid is empty
+    pub fn wrapped_mut(&mut self, x: i64, y: i64) -> &mut T {
+        let (x, y) = self.resolve(x, y, Boundary::Wrap).unwrap();
+        self.get_mut(x, y).unwrap()
+    }
+
     pub fn iter_mut(&mut self) -> std::slice::IterMut<T> {
         self.elements.iter_mut()
     }
@@ -81,6 +219,145 @@ impl<T> Grid<T> {
         &self.elements[start_ind..start_ind + self.num_cols]
     }
 
+    /// `None` if `x` is outside the grid
+    pub fn column(&self, x: usize) -> Option<impl Iterator<Item = &T>> {
+        (x < self.num_cols).then(|| self.elements.iter().skip(x).step_by(self.num_cols))
+    }
+
+    /// Positions and values of the neighbors of `(x, y)`, positions outside the grid
+    /// are mapped back with `boundary` so small grids may yield a cell more than once
+    pub fn neighbors(
+        &self,
+        x: usize,
+        y: usize,
+        neighborhood: Neighborhood,
+        boundary: Boundary,
+    ) -> impl Iterator<Item = ((usize, usize), &T)> {
+        neighborhood
+            .offsets(y as i64)
+            .into_iter()
+            .filter_map(move |(dx, dy)| {
+                let pos = self.resolve(x as i64 + dx, y as i64 + dy, boundary)?;
+                Some((pos, self.get(pos.0, pos.1)?))
+            })
+    }
+
+    /// Rectangle of `num_cols` by `num_rows` cells starting at `(x, y)`,
+    /// `None` if it does not fit into the grid
+    pub fn view(
+        &self,
+        x: usize,
+        y: usize,
+        num_cols: usize,
+        num_rows: usize,
+    ) -> Option<GridView<'_, T>> {
+        if x + num_cols > self.num_cols || y + num_rows > self.num_rows {
+            return None;
+        }
+        Some(GridView {
+            grid: self,
+            x,
+            y,
+            num_cols,
+            num_rows,
+        })
+    }
+
+    /// Changes the size keeping the cells in the top left corner, new cells are default
+    pub fn resize(&mut self, num_cols: usize, num_rows: usize)
+    where
+        T: Default + Clone,
+    {
+        *self = Grid::from_fn(
+            |x, y| self.get(x, y).cloned().unwrap_or_default(),
+            num_cols,
+            num_rows,
+        );
+    }
+
+    /// Scales the grid to a new size picking the nearest cell for each new cell,
+    /// `None` if the grid has no cells to pick from
+    pub fn resample(&self, num_cols: usize, num_rows: usize) -> Option<Grid<T>>
+    where
+        T: Clone,
+    {
+        if self.elements.is_empty() {
+            return None;
+        }
+        let source = |coord: usize, new_len: usize, len: usize| {
+            (((coord as f64 + 0.5) * len as f64 / new_len as f64) as usize).min(len - 1)
+        };
+        Some(Grid::from_fn(
+            |x, y| {
+                let src_x = source(x, num_cols, self.num_cols);
+                let src_y = source(y, num_rows, self.num_rows);
+                self[(src_x, src_y)].clone()
+            },
+            num_cols,
+            num_rows,
+        ))
+    }
+
+    /// Positions of all cells connected to `(x, y)` through cells for which `include` holds,
+    /// in breadth first order. Empty if `(x, y)` itself is not included
+    pub fn region<F>(
+        &self,
+        x: usize,
+        y: usize,
+        neighborhood: Neighborhood,
+        boundary: Boundary,
+        include: F,
+    ) -> Vec<(usize, usize)>
+    where
+        F: Fn(&T) -> bool,
+    {
+        let mut region = vec![];
+        if !self.get(x, y).is_some_and(&include) {
+            return region;
+        }
+
+        let mut visited = vec![false; self.num_cols * self.num_rows];
+        let mut queue = VecDeque::from([(x, y)]);
+        visited[y * self.num_cols + x] = true;
+        while let Some((x, y)) = queue.pop_front() {
+            region.push((x, y));
+            for (pos, elem) in self.neighbors(x, y, neighborhood, boundary) {
+                let ind = pos.1 * self.num_cols + pos.0;
+                if !visited[ind] && include(elem) {
+                    visited[ind] = true;
+                    queue.push_back(pos);
+                }
+            }
+        }
+        region
+    }
+
+    /// Sets every cell connected to `(x, y)` with the same value as `(x, y)` to `value`,
+    /// returns the number of changed cells
+    pub fn flood_fill(
+        &mut self,
+        x: usize,
+        y: usize,
+        value: T,
+        neighborhood: Neighborhood,
+        boundary: Boundary,
+    ) -> usize
+    where
+        T: PartialEq + Clone,
+    {
+        let Some(start) = self.get(x, y).cloned() else {
+            return 0;
+        };
+        if start == value {
+            return 0;
+        }
+        let region = self.region(x, y, neighborhood, boundary, |elem| *elem == start);
+        for pos in region.iter() {
+            self[*pos] = value.clone();
+        }
+        region.len()
+    }
+
     pub fn map<F, U>(self, fun: F) -> Grid<U>
     where
         F: Fn(T) -> U,
@@ -92,6 +369,17 @@ impl<T> Grid<T> {
         new_grid
     }
 
+    pub fn map_ref<F, U>(&self, fun: F) -> Grid<U>
+    where
+        F: Fn(&T) -> U,
+    {
+        Grid::from_vec(
+            self.elements.iter().map(fun).collect(),
+            self.num_cols,
+            self.num_rows,
+        )
+    }
+
     pub fn map_mut<F>(&mut self, fun: F)
     where
         F: Fn(&mut T),
@@ -104,22 +392,101 @@ impl<T> Grid<T> {
 
 impl Grid<f64> {
     pub fn convolute<const N: usize, const M: usize>(&self, kernel: [[f64; N]; M]) -> Grid<f64> {
-        let mut new_grid = Grid::from_fn(|_, _| 0.0, self.num_cols, self.num_rows);
-        for center_y in 0..self.num_rows {
-            for center_x in 0..self.num_cols {
-                let start_x = center_x as i64 - (M as i64 - 1) / 2;
-                let start_y = center_y as i64 - (N as i64 - 1) / 2;
-                for kernel_y in 0..N {
-                    let y = start_y + kernel_y as i64;
-                    for kernel_x in 0..M {
-                        let x = start_x + kernel_x as i64;
-                        new_grid[(center_x, center_y)] +=
-                            kernel[kernel_x][kernel_y] * self.get_or_default(x, y);
-                    }
-                }
-            }
+        self.convolute_with(kernel, Boundary::Zero)
+    }
+
+    /// `kernel[x][y]` is the weight at `(x, y)`, see `Grid::convolve`
+    pub fn convolute_with<const N: usize, const M: usize>(
+        &self,
+        kernel: [[f64; N]; M],
+        boundary: Boundary,
+    ) -> Grid<f64> {
+        self.convolve(&Kernel::from_columns(kernel), boundary)
+    }
+
+    /// Scales the grid to a new size interpolating linearly between the four closest cells,
+    /// all zero if the grid is empty
+    pub fn resample_bilinear(&self, num_cols: usize, num_rows: usize) -> Grid<f64> {
+        if self.elements.is_empty() {
+            return Grid::from_fn(|_, _| 0.0, num_cols, num_rows);
         }
-        new_grid
+        let source = |coord: usize, new_len: usize, len: usize| {
+            ((coord as f64 + 0.5) * len as f64 / new_len as f64 - 0.5).clamp(0.0, (len - 1) as f64)
+        };
+        Grid::from_fn(
+            |x, y| {
+                let (src_x, src_y) = (
+                    source(x, num_cols, self.num_cols),
+                    source(y, num_rows, self.num_rows),
+                );
+                let (x0, y0) = (src_x.floor() as i64, src_y.floor() as i64);
+                let (tx, ty) = (src_x - x0 as f64, src_y - y0 as f64);
+                let at = |x, y| *self.get_bounded(x, y, Boundary::Clamp).unwrap();
+                let top = (1.0 - tx) * at(x0, y0) + tx * at(x0 + 1, y0);
+                let bottom = (1.0 - tx) * at(x0, y0 + 1) + tx * at(x0 + 1, y0 + 1);
+                (1.0 - ty) * top + ty * bottom
+            },
+            num_cols,
+            num_rows,
+        )
+    }
+}
+
+/// Borrowed rectangular part of a grid, see `Grid::view`
+#[derive(Debug)]
+pub struct GridView<'a, T> {
+    grid: &'a Grid<T>,
+    x: usize,
+    y: usize,
+    num_cols: usize,
+    num_rows: usize,
+}
+
+impl<T> Clone for GridView<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for GridView<'_, T> {}
+
+impl<'a, T> GridView<'a, T> {
+    pub fn num_cols(&self) -> usize {
+        self.num_cols
+    }
+
+    pub fn num_rows(&self) -> usize {
+        self.num_rows
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&'a T> {
+        if x >= self.num_cols || y >= self.num_rows {
+            return None;
+        }
+        self.grid.get(self.x + x, self.y + y)
+    }
+
+    pub fn row(&self, y: usize) -> &'a [T] {
+        &self.grid.row(self.y + y)[self.x..self.x + self.num_cols]
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &'a T> {
+        let view = *self;
+        (0..self.num_rows).flat_map(move |y| view.row(y).iter())
+    }
+
+    pub fn to_grid(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid::from_fn(|x, y| self[(x, y)].clone(), self.num_cols, self.num_rows)
+    }
+}
+
+impl<T> Index<(usize, usize)> for GridView<'_, T> {
+    type Output = T;
+    fn index(&self, (x, y): (usize, usize)) -> &Self::Output {
+        self.get(x, y).unwrap()
     }
 }
 
@@ -148,6 +515,19 @@ impl<T> IndexMut<(usize, usize)> for Grid<T> {
     }
 }
 
+impl<T> Index<Wrapped> for Grid<T> {
+    type Output = T;
+    fn index(&self, Wrapped(x, y): Wrapped) -> &Self::Output {
+        self.get_wrapped(x, y)
+    }
+}
+
+impl<T> IndexMut<Wrapped> for Grid<T> {
+    fn index_mut(&mut self, Wrapped(x, y): Wrapped) -> &mut Self::Output {
+        self.wrapped_mut(x, y)
+    }
+}
+
 impl<'a, T: 'a> IntoIterator for &'a Grid<T> {
     type Item = &'a T;
     type IntoIter = std::slice::Iter<'a, T>;
@@ -166,7 +546,7 @@ impl<T> IntoIterator for Grid<T> {
 
 #[cfg(test)]
 mod grid_tests {
-    use super::Grid;
+    use super::{Boundary, Grid, Neighborhood, Wrapped};
     use crate::vec2d::Vec2D;
 
     #[test]
@@ -235,4 +615,184 @@ mod grid_tests {
         };
         assert_eq!(result, expected)
     }
+
+    /// 0 1 2
+    /// 3 4 5
+    fn numbered() -> Grid<i64> {
+        Grid::from_fn(|x, y| (x + 3 * y) as i64, 3, 2)
+    }
+
+    fn row_lookup(boundary: Boundary) -> Vec<Option<i64>> {
+        let grid = numbered();
+        (-4..7)
+            .map(|x| grid.get_bounded(x, 0, boundary).copied())
+            .collect()
+    }
+
+    #[test]
+    fn boundary_zero() {
+        let mut expected = vec![None; 4];
+        expected.extend([Some(0), Some(1), Some(2)]);
+        expected.extend([None; 4]);
+        assert_eq!(row_lookup(Boundary::Zero), expected);
+        assert_eq!(numbered().get_bounded(1, -1, Boundary::Zero), None)
+    }
+
+    #[test]
+    fn boundary_clamp() {
+        let expected = [0, 0, 0, 0, 0, 1, 2, 2, 2, 2, 2].map(Some);
+        assert_eq!(row_lookup(Boundary::Clamp), expected);
+        assert_eq!(numbered().get_bounded(-1, 5, Boundary::Clamp), Some(&3))
+    }
+
+    #[test]
+    fn boundary_wrap() {
+        let expected = [2, 0, 1, 2, 0, 1, 2, 0, 1, 2, 0].map(Some);
+        assert_eq!(row_lookup(Boundary::Wrap), expected);
+        assert_eq!(numbered().get_bounded(4, -1, Boundary::Wrap), Some(&4))
+    }
+
+    #[test]
+    fn boundary_reflect() {
+        let expected = [0, 1, 2, 1, 0, 1, 2, 1, 0, 1, 2].map(Some);
+        assert_eq!(row_lookup(Boundary::Reflect), expected);
+        assert_eq!(numbered().get_bounded(0, 2, Boundary::Reflect), Some(&0));
+        let single = Grid::from_fn(|_, _| 7, 1, 1);
+        assert_eq!(single.get_bounded(-3, 5, Boundary::Reflect), Some(&7))
+    }
+
+    #[test]
+    fn wrapped_access() {
+        let mut grid = numbered();
+        assert_eq!(grid.get_wrapped(-1, 0), &2);
+        assert_eq!(grid.get_wrapped(4, 3), &4);
+        *grid.wrapped_mut(-3, -1) = 10;
+        assert_eq!(grid[(0, 1)], 10);
+        assert_eq!(grid[Wrapped(-1, 0)], 2);
+        grid[Wrapped(3, 5)] = 11;
+        assert_eq!(grid[(0, 1)], 11);
+    }
+
+    #[test]
+    fn convolution_boundaries() {
+        let grid = numbered();
+        let kernel = [[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 0.0, 0.0]];
+        // the kernel picks the cell above, see `convolute` for the kernel layout
+        let grid = grid.map(|v| v as f64);
+        let shifted =
+            |boundary| -> Vec<f64> { grid.convolute_with(kernel, boundary).into_iter().collect() };
+        assert_eq!(shifted(Boundary::Zero), vec![0.0, 0.0, 0.0, 0.0, 1.0, 2.0]);
+        assert_eq!(shifted(Boundary::Clamp), vec![0.0, 1.0, 2.0, 0.0, 1.0, 2.0]);
+        assert_eq!(shifted(Boundary::Wrap), vec![3.0, 4.0, 5.0, 0.0, 1.0, 2.0]);
+        assert_eq!(
+            shifted(Boundary::Reflect),
+            vec![3.0, 4.0, 5.0, 0.0, 1.0, 2.0]
+        );
+        assert_eq!(
+            grid.convolute_with(kernel, Boundary::Zero),
+            grid.convolute(kernel)
+        )
+    }
+
+    #[test]
+    fn neighbors() {
+        let grid = Grid::from_fn(|x, y| (x, y), 4, 4);
+        let positions = |x, y, neighborhood, boundary| -> Vec<(usize, usize)> {
+            let mut result: Vec<(usize, usize)> = grid
+                .neighbors(x, y, neighborhood, boundary)
+                .map(|(pos, val)| {
+                    assert_eq!(pos, *val);
+                    pos
+                })
+                .collect();
+            result.sort();
+            result
+        };
+
+        let corner = positions(0, 0, Neighborhood::VonNeumann, Boundary::Zero);
+        assert_eq!(corner, vec![(0, 1), (1, 0)]);
+        assert_eq!(
+            positions(0, 0, Neighborhood::Moore, Boundary::Zero).len(),
+            3
+        );
+        let wrapped = positions(0, 0, Neighborhood::Moore, Boundary::Wrap);
+        assert_eq!(wrapped.len(), 8);
+        assert!(wrapped.contains(&(3, 3)));
+        assert_eq!(
+            positions(2, 2, Neighborhood::Moore, Boundary::Zero).len(),
+            8
+        );
+
+        // odd rows are shifted right
+        let even = positions(1, 2, Neighborhood::Hex, Boundary::Zero);
+        assert_eq!(even, vec![(0, 1), (0, 2), (0, 3), (1, 1), (1, 3), (2, 2)]);
+        let odd = positions(1, 1, Neighborhood::Hex, Boundary::Zero);
+        assert_eq!(odd, vec![(0, 1), (1, 0), (1, 2), (2, 0), (2, 1), (2, 2)])
+    }
+
+    #[test]
+    fn column_and_view() {
+        let grid = Grid::from_fn(|x, y| (x, y), 4, 3);
+        let column: Vec<&(usize, usize)> = grid.column(2).unwrap().collect();
+        assert_eq!(column, vec![&(2, 0), &(2, 1), &(2, 2)]);
+        assert!(grid.column(4).is_none());
+
+        let view = grid.view(1, 1, 2, 2).unwrap();
+        assert_eq!(view[(0, 0)], (1, 1));
+        assert_eq!(view.get(2, 0), None);
+        assert_eq!(view.row(1), &[(1, 2), (2, 2)]);
+        let elems: Vec<&(usize, usize)> = view.iter().collect();
+        assert_eq!(elems, vec![&(1, 1), &(2, 1), &(1, 2), &(2, 2)]);
+        assert_eq!(view.to_grid(), Grid::from_fn(|x, y| (x + 1, y + 1), 2, 2));
+        assert!(grid.view(3, 0, 2, 1).is_none())
+    }
+
+    #[test]
+    fn resize_and_resample() {
+        let mut grid = numbered();
+        grid.resize(2, 3);
+        assert_eq!(
+            grid.iter().copied().collect::<Vec<i64>>(),
+            vec![0, 1, 3, 4, 0, 0]
+        );
+
+        let small = Grid::from_fn(|x, y| (x, y), 2, 2);
+        let large = small.resample(4, 4).unwrap();
+        assert_eq!(large[(3, 0)], (1, 0));
+        assert_eq!(large[(1, 2)], (0, 1));
+        assert_eq!(large.resample(2, 2), Some(small));
+        let empty: Grid<f64> = Grid::from_fn(|_, _| 1.0, 0, 0);
+        assert!(empty.resample(2, 2).is_none());
+        assert_eq!(empty.resample_bilinear(2, 1).iter().sum::<f64>(), 0.0);
+
+        let ramp = Grid::from_fn(|x, _| x as f64, 2, 1);
+        let smooth = ramp.resample_bilinear(4, 1);
+        assert_eq!(
+            smooth.iter().copied().collect::<Vec<f64>>(),
+            vec![0.0, 0.25, 0.75, 1.0]
+        )
+    }
+
+    #[test]
+    fn flood_fill() {
+        // a wall in column 2 splits the grid
+        let mut grid = Grid::from_fn(|x, _| if x == 2 { 1 } else { 0 }, 5, 3);
+        let region = grid.region(0, 0, Neighborhood::VonNeumann, Boundary::Zero, |v| *v == 0);
+        assert_eq!(region.len(), 6);
+        assert_eq!(region[0], (0, 0));
+
+        let filled = grid.flood_fill(0, 0, 2, Neighborhood::VonNeumann, Boundary::Zero);
+        assert_eq!(filled, 6);
+        assert_eq!(grid[(4, 2)], 0);
+        // around the edges the wall no longer splits the grid
+        let filled = grid.flood_fill(4, 0, 2, Neighborhood::VonNeumann, Boundary::Wrap);
+        assert_eq!(filled, 6);
+        assert_eq!(
+            grid.flood_fill(0, 0, 2, Neighborhood::Moore, Boundary::Zero),
+            0
+        );
+        assert!(grid
+            .region(2, 0, Neighborhood::Moore, Boundary::Zero, |v| *v == 0)
+            .is_empty())
+    }
 }
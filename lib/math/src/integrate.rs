@@ -0,0 +1,210 @@
+use super::{vec2d::Vec2D, vec3d::Vec3D};
+
+/// Anything that can be moved along a derivative,
+/// e.g. a position, a velocity or the positions of a whole system of bodies
+pub trait State: Clone {
+    /// `self + scale * deriv`
+    fn add_scaled(&self, deriv: &Self, scale: f64) -> Self;
+}
+
+impl State for f64 {
+    fn add_scaled(&self, deriv: &f64, scale: f64) -> f64 {
+        self + scale * deriv
+    }
+}
+
+impl State for Vec2D {
+    fn add_scaled(&self, deriv: &Vec2D, scale: f64) -> Vec2D {
+        *self + scale * *deriv
+    }
+}
+
+impl State for Vec3D {
+    fn add_scaled(&self, deriv: &Vec3D, scale: f64) -> Vec3D {
+        *self + scale * *deriv
+    }
+}
+
+impl<S: State> State for Vec<S> {
+    fn add_scaled(&self, deriv: &Vec<S>, scale: f64) -> Vec<S> {
+        self.iter()
+            .zip(deriv.iter())
+            .map(|(st, d)| st.add_scaled(d, scale))
+            .collect()
+    }
+}
+
+impl<S1: State, S2: State> State for (S1, S2) {
+    fn add_scaled(&self, deriv: &(S1, S2), scale: f64) -> (S1, S2) {
+        (
+            self.0.add_scaled(&deriv.0, scale),
+            self.1.add_scaled(&deriv.1, scale),
+        )
+    }
+}
+
+/// One explicit Euler step of `state' = deriv(state)`
+pub fn euler<S: State>(state: &S, dt: f64, deriv: impl Fn(&S) -> S) -> S {
+    state.add_scaled(&deriv(state), dt)
+}
+
+/// One classic fourth order Runge-Kutta step of `state' = deriv(state)`
+pub fn rk4<S: State>(state: &S, dt: f64, deriv: impl Fn(&S) -> S) -> S {
+    let k1 = deriv(state);
+    let k2 = deriv(&state.add_scaled(&k1, dt / 2.0));
+    let k3 = deriv(&state.add_scaled(&k2, dt / 2.0));
+    let k4 = deriv(&state.add_scaled(&k3, dt));
+    state
+        .add_scaled(&k1, dt / 6.0)
+        .add_scaled(&k2, dt / 3.0)
+        .add_scaled(&k3, dt / 3.0)
+        .add_scaled(&k4, dt / 6.0)
+}
+
+/// Integrators for second order systems where the acceleration only depends on the position.
+/// The symplectic ones (`SemiImplicitEuler`, `VelocityVerlet`, `Leapfrog`) keep the energy
+/// of orbits bounded, `Euler` gains energy every step and `Rk4` slowly loses it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Integrator {
+    Euler,
+    SemiImplicitEuler,
+    VelocityVerlet,
+    Leapfrog,
+    Rk4,
+}
+
+impl Integrator {
+    /// Advances `pos` and `vel` by `dt`
+    pub fn step<S: State>(&self, pos: &mut S, vel: &mut S, dt: f64, accel: impl Fn(&S) -> S) {
+        match self {
+            Integrator::Euler => {
+                let acc = accel(pos);
+                *pos = pos.add_scaled(vel, dt);
+                *vel = vel.add_scaled(&acc, dt);
+            }
+            Integrator::SemiImplicitEuler => {
+                *vel = vel.add_scaled(&accel(pos), dt);
+                *pos = pos.add_scaled(vel, dt);
+            }
+            Integrator::VelocityVerlet => {
+                // kick, drift, kick
+                let half_vel = vel.add_scaled(&accel(pos), dt / 2.0);
+                *pos = pos.add_scaled(&half_vel, dt);
+                *vel = half_vel.add_scaled(&accel(pos), dt / 2.0);
+            }
+            Integrator::Leapfrog => {
+                // drift, kick, drift
+                let half_pos = pos.add_scaled(vel, dt / 2.0);
+                *vel = vel.add_scaled(&accel(&half_pos), dt);
+                *pos = half_pos.add_scaled(vel, dt / 2.0);
+            }
+            Integrator::Rk4 => {
+                let state = (pos.clone(), vel.clone());
+                let (new_pos, new_vel) = rk4(&state, dt, |(p, v)| (v.clone(), accel(p)));
+                *pos = new_pos;
+                *vel = new_vel;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod integrate_tests {
+    use super::{euler, rk4, Integrator};
+    use crate::vec2d::Vec2D;
+
+    const ALL: [Integrator; 5] = [
+        Integrator::Euler,
+        Integrator::SemiImplicitEuler,
+        Integrator::VelocityVerlet,
+        Integrator::Leapfrog,
+        Integrator::Rk4,
+    ];
+
+    /// Unit mass orbiting a fixed unit mass at the origin with G = 1
+    fn gravity(pos: &Vec2D) -> Vec2D {
+        -*pos / pos.abs().powi(3)
+    }
+
+    fn energy(pos: Vec2D, vel: Vec2D) -> f64 {
+        vel.dot(vel) / 2.0 - 1.0 / pos.abs()
+    }
+
+    /// Largest relative energy error over `orbits` orbits of an ellipse with eccentricity 0.5
+    fn energy_drift(integrator: Integrator, orbits: usize) -> f64 {
+        let (mut pos, mut vel) = (Vec2D::new(1.0, 0.0), Vec2D::new(0.0, 1.5f64.sqrt()));
+        let start = energy(pos, vel);
+        // semi-major axis 2, period 2 pi a^(3/2)
+        let period = 2.0 * std::f64::consts::PI * 2.0f64.powf(1.5);
+        let steps = 1000;
+        let dt = period / steps as f64;
+
+        let mut max_drift: f64 = 0.0;
+        for _ in 0..orbits * steps {
+            integrator.step(&mut pos, &mut vel, dt, gravity);
+            max_drift = max_drift.max(((energy(pos, vel) - start) / start).abs());
+        }
+        max_drift
+    }
+
+    #[test]
+    fn first_order() {
+        // y' = y starting at 1 is e^t
+        let steps = 100;
+        let dt = 1.0 / steps as f64;
+        let (mut y_euler, mut y_rk4) = (1.0, 1.0);
+        for _ in 0..steps {
+            y_euler = euler(&y_euler, dt, |y| *y);
+            y_rk4 = rk4(&y_rk4, dt, |y| *y);
+        }
+        let e = std::f64::consts::E;
+        assert!((y_euler - e).abs() < 2e-2);
+        assert!((y_rk4 - e).abs() < 1e-9)
+    }
+
+    #[test]
+    fn constant_acceleration() {
+        // a quadratic trajectory is exact for all integrators except the Euler variants
+        let acc = Vec2D::new(0.0, -9.81);
+        for integrator in [
+            Integrator::VelocityVerlet,
+            Integrator::Leapfrog,
+            Integrator::Rk4,
+        ] {
+            let (mut pos, mut vel) = (Vec2D::default(), Vec2D::new(3.0, 10.0));
+            for _ in 0..10 {
+                integrator.step(&mut pos, &mut vel, 0.1, |_| acc);
+            }
+            let expected = Vec2D::new(3.0, 10.0 - 9.81 / 2.0);
+            assert!(pos.dist(&expected) < 1e-9, "{integrator:?} {pos}");
+            assert!(vel.dist(&Vec2D::new(3.0, 0.19)) < 1e-9)
+        }
+    }
+
+    #[test]
+    fn two_body_energy_drift() {
+        let drifts: Vec<f64> = ALL.iter().map(|int| energy_drift(*int, 10)).collect();
+        let [euler, semi_implicit, verlet, leapfrog, rk4] = drifts[..] else {
+            unreachable!()
+        };
+        assert!(euler > 0.5, "euler {euler}");
+        assert!(semi_implicit < 0.05, "semi implicit euler {semi_implicit}");
+        assert!(verlet < 1e-3, "velocity verlet {verlet}");
+        assert!(leapfrog < 1e-3, "leapfrog {leapfrog}");
+        assert!(rk4 < 1e-4, "rk4 {rk4}")
+    }
+
+    #[test]
+    fn symplectic_drift_is_bounded() {
+        // the error of symplectic integrators oscillates instead of accumulating
+        for integrator in [
+            Integrator::SemiImplicitEuler,
+            Integrator::VelocityVerlet,
+            Integrator::Leapfrog,
+        ] {
+            let short = energy_drift(integrator, 5);
+            let long = energy_drift(integrator, 50);
+            assert!(long < 1.5 * short, "{integrator:?} {short} {long}")
+        }
+    }
+}
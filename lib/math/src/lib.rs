@@ -1,6 +1,18 @@
+pub mod affine2;
+pub mod complex;
+pub mod convolution;
+pub mod delaunay;
+pub mod geometry;
 pub mod grid;
+pub mod integrate;
 pub mod mat22;
+pub mod mat33;
+pub mod mat44;
+pub mod noise;
+pub mod quaternion;
+pub mod spatial;
 pub mod vec2d;
+pub mod vec3d;
 
 pub fn rand_between(min: f64, max: f64) -> f64 {
     min + rand::random::<f64>() * (max - min)
@@ -1,6 +1,7 @@
 use super::vec2d::Vec2D;
-use std::ops::Mul;
+use std::ops::{Add, Index, Mul, Neg, Sub};
 
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Mat22 {
     a00: f64,
     a01: f64,
@@ -18,6 +19,24 @@ impl Mat22 {
         }
     }
 
+    pub fn identity() -> Mat22 {
+        Mat22::scale(Vec2D::new(1.0, 1.0))
+    }
+
+    pub fn scale(factors: Vec2D) -> Mat22 {
+        Mat22::new([[factors.x, 0.0], [0.0, factors.y]])
+    }
+
+    /// Counter-clockwise rotation in a y-up coordinate system
+    pub fn rotation(angle: f64) -> Mat22 {
+        let (sin, cos) = angle.sin_cos();
+        Mat22::new([[cos, -sin], [sin, cos]])
+    }
+
+    pub fn rows(&self) -> [[f64; 2]; 2] {
+        [[self.a00, self.a01], [self.a10, self.a11]]
+    }
+
     pub fn transpose(self) -> Mat22 {
         Mat22::new([[self.a00, self.a10], [self.a01, self.a11]])
     }
@@ -35,6 +54,12 @@ impl Mat22 {
     }
 }
 
+impl Default for Mat22 {
+    fn default() -> Mat22 {
+        Mat22::identity()
+    }
+}
+
 impl From<[[f64; 2]; 2]> for Mat22 {
     fn from(rows: [[f64; 2]; 2]) -> Mat22 {
         Mat22::new(rows)
@@ -50,6 +75,59 @@ impl From<[Vec2D; 2]> for Mat22 {
     }
 }
 
+impl Index<(usize, usize)> for Mat22 {
+    type Output = f64;
+    fn index(&self, index: (usize, usize)) -> &f64 {
+        match index {
+            (0, 0) => &self.a00,
+            (0, 1) => &self.a01,
+            (1, 0) => &self.a10,
+            (1, 1) => &self.a11,
+            _ => panic!("Index {index:?} out of bounds for 2x2 matrix"),
+        }
+    }
+}
+
+impl Add for Mat22 {
+    type Output = Mat22;
+    fn add(self, other: Mat22) -> Mat22 {
+        Mat22::new([
+            [self.a00 + other.a00, self.a01 + other.a01],
+            [self.a10 + other.a10, self.a11 + other.a11],
+        ])
+    }
+}
+
+impl Sub for Mat22 {
+    type Output = Mat22;
+    fn sub(self, other: Mat22) -> Mat22 {
+        self + -other
+    }
+}
+
+impl Neg for Mat22 {
+    type Output = Mat22;
+    fn neg(self) -> Mat22 {
+        self * -1.0
+    }
+}
+
+impl Mul<Mat22> for Mat22 {
+    type Output = Mat22;
+    fn mul(self, other: Mat22) -> Mat22 {
+        Mat22::new([
+            [
+                self.a00 * other.a00 + self.a01 * other.a10,
+                self.a00 * other.a01 + self.a01 * other.a11,
+            ],
+            [
+                self.a10 * other.a00 + self.a11 * other.a10,
+                self.a10 * other.a01 + self.a11 * other.a11,
+            ],
+        ])
+    }
+}
+
 impl Mul<Vec2D> for Mat22 {
     type Output = Vec2D;
     fn mul(self, other: Vec2D) -> Vec2D {
@@ -64,7 +142,7 @@ impl Mul<f64> for Mat22 {
     fn mul(self, other: f64) -> Mat22 {
         Mat22::new([
             [other * self.a00, other * self.a01],
-            [other * self.a10, other * self.a01],
+            [other * self.a10, other * self.a11],
         ])
     }
 }
@@ -72,10 +150,7 @@ impl Mul<f64> for Mat22 {
 impl Mul<Mat22> for f64 {
     type Output = Mat22;
     fn mul(self, other: Mat22) -> Mat22 {
-        Mat22::new([
-            [self * other.a00, self * other.a01],
-            [self * other.a10, self * other.a01],
-        ])
+        other * self
     }
 }
 
@@ -85,3 +160,69 @@ impl Mul<Mat22> for Vec2D {
         other.transpose() * self
     }
 }
+
+#[cfg(test)]
+mod mat22_tests {
+    use super::Mat22;
+    use crate::vec2d::Vec2D;
+    use std::f64::consts::PI;
+
+    #[test]
+    fn scalar_mul() {
+        let m = Mat22::new([[1.0, 2.0], [3.0, 4.0]]);
+        let expected = Mat22::new([[2.0, 4.0], [6.0, 8.0]]);
+        assert_eq!(m * 2.0, expected);
+        assert_eq!(2.0 * m, expected)
+    }
+
+    #[test]
+    fn add_sub() {
+        let m1 = Mat22::new([[1.0, 2.0], [3.0, 4.0]]);
+        let m2 = Mat22::new([[0.5, -1.0], [2.0, 0.0]]);
+        assert_eq!(m1 + m2, Mat22::new([[1.5, 1.0], [5.0, 4.0]]));
+        assert_eq!(m1 - m2, Mat22::new([[0.5, 3.0], [1.0, 4.0]]));
+        assert_eq!(-m1, Mat22::new([[-1.0, -2.0], [-3.0, -4.0]]))
+    }
+
+    #[test]
+    fn mul_mat() {
+        let m1 = Mat22::new([[1.0, 2.0], [3.0, 4.0]]);
+        let m2 = Mat22::new([[0.0, 1.0], [1.0, 0.0]]);
+        assert_eq!(m1 * m2, Mat22::new([[2.0, 1.0], [4.0, 3.0]]));
+        assert_eq!(m2 * m1, Mat22::new([[3.0, 4.0], [1.0, 2.0]]));
+        assert_eq!(m1 * Mat22::identity(), m1)
+    }
+
+    #[test]
+    fn mul_vec() {
+        let m = Mat22::new([[1.0, 2.0], [3.0, 4.0]]);
+        let v = Vec2D::new(1.0, -1.0);
+        assert_eq!(m * v, Vec2D::new(-1.0, -1.0));
+        assert_eq!(v * m, Vec2D::new(-2.0, -2.0))
+    }
+
+    #[test]
+    fn det_transpose() {
+        let m = Mat22::new([[1.0, 2.0], [3.0, 4.0]]);
+        assert_eq!(m.det(), -2.0);
+        assert_eq!(m.transpose(), Mat22::new([[1.0, 3.0], [2.0, 4.0]]));
+        assert_eq!(m.transpose()[(0, 1)], m[(1, 0)])
+    }
+
+    #[test]
+    fn inverse() {
+        let m = Mat22::new([[4.0, 7.0], [2.0, 6.0]]);
+        let result = m * m.inv().unwrap();
+        for (i, j) in [(0, 0), (0, 1), (1, 0), (1, 1)] {
+            assert!((result[(i, j)] - Mat22::identity()[(i, j)]).abs() < 1e-9)
+        }
+        assert_eq!(Mat22::new([[1.0, 2.0], [2.0, 4.0]]).inv(), None)
+    }
+
+    #[test]
+    fn rotation() {
+        let result = Mat22::rotation(PI / 2.0) * Vec2D::new(1.0, 0.0);
+        assert!(result.dist(&Vec2D::new(0.0, 1.0)) < 1e-9);
+        assert!((Mat22::rotation(0.7).det() - 1.0).abs() < 1e-9)
+    }
+}
@@ -0,0 +1,247 @@
+use super::vec3d::Vec3D;
+use std::ops::{Index, Mul};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Mat33 {
+    rows: [[f64; 3]; 3],
+}
+
+impl Mat33 {
+    pub fn new(rows: [[f64; 3]; 3]) -> Mat33 {
+        Mat33 { rows }
+    }
+
+    pub fn identity() -> Mat33 {
+        Mat33::scale(Vec3D::new(1.0, 1.0, 1.0))
+    }
+
+    pub fn scale(factors: Vec3D) -> Mat33 {
+        Mat33::new([
+            [factors.x, 0.0, 0.0],
+            [0.0, factors.y, 0.0],
+            [0.0, 0.0, factors.z],
+        ])
+    }
+
+    pub fn rotation_x(angle: f64) -> Mat33 {
+        let (sin, cos) = angle.sin_cos();
+        Mat33::new([[1.0, 0.0, 0.0], [0.0, cos, -sin], [0.0, sin, cos]])
+    }
+
+    pub fn rotation_y(angle: f64) -> Mat33 {
+        let (sin, cos) = angle.sin_cos();
+        Mat33::new([[cos, 0.0, sin], [0.0, 1.0, 0.0], [-sin, 0.0, cos]])
+    }
+
+    pub fn rotation_z(angle: f64) -> Mat33 {
+        let (sin, cos) = angle.sin_cos();
+        Mat33::new([[cos, -sin, 0.0], [sin, cos, 0.0], [0.0, 0.0, 1.0]])
+    }
+
+    /// Counter-clockwise rotation around `axis` (Rodrigues' formula)
+    pub fn rotation(axis: Vec3D, angle: f64) -> Mat33 {
+        let Vec3D { x, y, z } = axis.normalize();
+        let (sin, cos) = angle.sin_cos();
+        let t = 1.0 - cos;
+        Mat33::new([
+            [t * x * x + cos, t * x * y - sin * z, t * x * z + sin * y],
+            [t * x * y + sin * z, t * y * y + cos, t * y * z - sin * x],
+            [t * x * z - sin * y, t * y * z + sin * x, t * z * z + cos],
+        ])
+    }
+
+    pub fn rows(&self) -> [[f64; 3]; 3] {
+        self.rows
+    }
+
+    pub fn transpose(self) -> Mat33 {
+        let mut rows = [[0.0; 3]; 3];
+        for (i, row) in rows.iter_mut().enumerate() {
+            for (j, val) in row.iter_mut().enumerate() {
+                *val = self.rows[j][i];
+            }
+        }
+        Mat33::new(rows)
+    }
+
+    pub fn det(&self) -> f64 {
+        let m = &self.rows;
+        m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+            - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+            + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+    }
+
+    pub fn inv(self) -> Option<Mat33> {
+        let det = self.det();
+        if det == 0.0 {
+            return None;
+        }
+        let m = &self.rows;
+        let adjugate = Mat33::new([
+            [
+                m[1][1] * m[2][2] - m[1][2] * m[2][1],
+                m[0][2] * m[2][1] - m[0][1] * m[2][2],
+                m[0][1] * m[1][2] - m[0][2] * m[1][1],
+            ],
+            [
+                m[1][2] * m[2][0] - m[1][0] * m[2][2],
+                m[0][0] * m[2][2] - m[0][2] * m[2][0],
+                m[0][2] * m[1][0] - m[0][0] * m[1][2],
+            ],
+            [
+                m[1][0] * m[2][1] - m[1][1] * m[2][0],
+                m[0][1] * m[2][0] - m[0][0] * m[2][1],
+                m[0][0] * m[1][1] - m[0][1] * m[1][0],
+            ],
+        ]);
+        Some(adjugate * (1.0 / det))
+    }
+}
+
+impl Default for Mat33 {
+    fn default() -> Mat33 {
+        Mat33::identity()
+    }
+}
+
+impl From<[[f64; 3]; 3]> for Mat33 {
+    fn from(rows: [[f64; 3]; 3]) -> Mat33 {
+        Mat33::new(rows)
+    }
+}
+
+impl From<[Vec3D; 3]> for Mat33 {
+    fn from(col_vecs: [Vec3D; 3]) -> Mat33 {
+        Mat33::new([
+            [col_vecs[0].x, col_vecs[1].x, col_vecs[2].x],
+            [col_vecs[0].y, col_vecs[1].y, col_vecs[2].y],
+            [col_vecs[0].z, col_vecs[1].z, col_vecs[2].z],
+        ])
+    }
+}
+
+impl Index<(usize, usize)> for Mat33 {
+    type Output = f64;
+    fn index(&self, (row, col): (usize, usize)) -> &f64 {
+        &self.rows[row][col]
+    }
+}
+
+impl Mul<Vec3D> for Mat33 {
+    type Output = Vec3D;
+    fn mul(self, other: Vec3D) -> Vec3D {
+        let m = &self.rows;
+        Vec3D::new(
+            m[0][0] * other.x + m[0][1] * other.y + m[0][2] * other.z,
+            m[1][0] * other.x + m[1][1] * other.y + m[1][2] * other.z,
+            m[2][0] * other.x + m[2][1] * other.y + m[2][2] * other.z,
+        )
+    }
+}
+
+impl Mul<Mat33> for Mat33 {
+    type Output = Mat33;
+    fn mul(self, other: Mat33) -> Mat33 {
+        let mut rows = [[0.0; 3]; 3];
+        for (i, row) in rows.iter_mut().enumerate() {
+            for (j, val) in row.iter_mut().enumerate() {
+                *val = (0..3).map(|k| self.rows[i][k] * other.rows[k][j]).sum();
+            }
+        }
+        Mat33::new(rows)
+    }
+}
+
+impl Mul<f64> for Mat33 {
+    type Output = Mat33;
+    fn mul(self, other: f64) -> Mat33 {
+        Mat33::new(self.rows.map(|row| row.map(|val| val * other)))
+    }
+}
+
+impl Mul<Mat33> for f64 {
+    type Output = Mat33;
+    fn mul(self, other: Mat33) -> Mat33 {
+        other * self
+    }
+}
+
+#[cfg(test)]
+mod mat33_tests {
+    use super::Mat33;
+    use crate::vec3d::Vec3D;
+    use std::f64::consts::PI;
+
+    fn assert_close(result: Mat33, expected: Mat33) {
+        for i in 0..3 {
+            for j in 0..3 {
+                assert!(
+                    (result[(i, j)] - expected[(i, j)]).abs() < 1e-9,
+                    "{result:?} != {expected:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn mul_identity() {
+        let m = Mat33::new([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0], [7.0, 8.0, 10.0]]);
+        assert_eq!(m * Mat33::identity(), m);
+        assert_eq!(Mat33::identity() * m, m)
+    }
+
+    #[test]
+    fn mul_vec() {
+        let m = Mat33::new([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0], [7.0, 8.0, 9.0]]);
+        let result = m * Vec3D::new(1.0, 0.0, -1.0);
+        assert_eq!(result, Vec3D::new(-2.0, -2.0, -2.0))
+    }
+
+    #[test]
+    fn mul_mat() {
+        let m1 = Mat33::new([[1.0, 2.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 2.0]]);
+        let m2 = Mat33::new([[1.0, 0.0, 0.0], [3.0, 1.0, 0.0], [0.0, 1.0, 1.0]]);
+        let expected = Mat33::new([[7.0, 2.0, 0.0], [3.0, 1.0, 0.0], [0.0, 2.0, 2.0]]);
+        assert_eq!(m1 * m2, expected)
+    }
+
+    #[test]
+    fn det_transpose() {
+        let m = Mat33::new([[2.0, 0.0, 1.0], [1.0, 3.0, 2.0], [1.0, 1.0, 2.0]]);
+        assert_eq!(m.det(), 6.0);
+        assert_eq!(m.transpose().det(), 6.0);
+        assert_eq!(m.transpose()[(0, 1)], m[(1, 0)])
+    }
+
+    #[test]
+    fn inverse() {
+        let m = Mat33::new([[2.0, 0.0, 1.0], [1.0, 3.0, 2.0], [1.0, 1.0, 2.0]]);
+        assert_close(m * m.inv().unwrap(), Mat33::identity());
+        assert_close(m.inv().unwrap() * m, Mat33::identity())
+    }
+
+    #[test]
+    fn inverse_singular() {
+        let m = Mat33::new([[1.0, 2.0, 3.0], [2.0, 4.0, 6.0], [0.0, 0.0, 1.0]]);
+        assert_eq!(m.inv(), None)
+    }
+
+    #[test]
+    fn rotation_axes() {
+        let x = Vec3D::new(1.0, 0.0, 0.0);
+        let result = Mat33::rotation_z(PI / 2.0) * x;
+        assert!(result.dist(&Vec3D::new(0.0, 1.0, 0.0)) < 1e-9);
+        assert_close(Mat33::rotation(x, 0.3), Mat33::rotation_x(0.3));
+        assert_close(
+            Mat33::rotation(Vec3D::new(0.0, 2.0, 0.0), 0.3),
+            Mat33::rotation_y(0.3),
+        )
+    }
+
+    #[test]
+    fn rotation_orthogonal() {
+        let rot = Mat33::rotation(Vec3D::new(1.0, 2.0, 3.0), 1.2);
+        assert_close(rot * rot.transpose(), Mat33::identity());
+        assert!((rot.det() - 1.0).abs() < 1e-9)
+    }
+}
@@ -0,0 +1,327 @@
+use super::{mat33::Mat33, vec3d::Vec3D};
+use std::ops::{Index, Mul};
+
+/// Homogeneous transform for column vectors, translations live in the last column
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Mat44 {
+    rows: [[f64; 4]; 4],
+}
+
+impl Mat44 {
+    pub fn new(rows: [[f64; 4]; 4]) -> Mat44 {
+        Mat44 { rows }
+    }
+
+    pub fn identity() -> Mat44 {
+        Mat44::from(Mat33::identity())
+    }
+
+    pub fn translation(offset: Vec3D) -> Mat44 {
+        Mat44::new([
+            [1.0, 0.0, 0.0, offset.x],
+            [0.0, 1.0, 0.0, offset.y],
+            [0.0, 0.0, 1.0, offset.z],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+    }
+
+    pub fn scale(factors: Vec3D) -> Mat44 {
+        Mat44::from(Mat33::scale(factors))
+    }
+
+    pub fn rotation_x(angle: f64) -> Mat44 {
+        Mat44::from(Mat33::rotation_x(angle))
+    }
+
+    pub fn rotation_y(angle: f64) -> Mat44 {
+        Mat44::from(Mat33::rotation_y(angle))
+    }
+
+    pub fn rotation_z(angle: f64) -> Mat44 {
+        Mat44::from(Mat33::rotation_z(angle))
+    }
+
+    pub fn rotation(axis: Vec3D, angle: f64) -> Mat44 {
+        Mat44::from(Mat33::rotation(axis, angle))
+    }
+
+    /// View matrix of a camera at `eye` looking at `target`,
+    /// the camera looks down its negative z-axis
+    pub fn look_at(eye: Vec3D, target: Vec3D, up: Vec3D) -> Mat44 {
+        let forward = (target - eye).normalize();
+        let right = forward.cross(up).normalize();
+        let up = right.cross(forward);
+        Mat44::new([
+            [right.x, right.y, right.z, -right.dot(eye)],
+            [up.x, up.y, up.z, -up.dot(eye)],
+            [-forward.x, -forward.y, -forward.z, forward.dot(eye)],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+    }
+
+    /// Perspective projection with vertical field of view `fov_y` (radians),
+    /// maps the view frustum between `near` and `far` to the cube [-1,1]^3
+    pub fn perspective(fov_y: f64, aspect: f64, near: f64, far: f64) -> Mat44 {
+        let f = 1.0 / (fov_y / 2.0).tan();
+        Mat44::new([
+            [f / aspect, 0.0, 0.0, 0.0],
+            [0.0, f, 0.0, 0.0],
+            [
+                0.0,
+                0.0,
+                (far + near) / (near - far),
+                2.0 * far * near / (near - far),
+            ],
+            [0.0, 0.0, -1.0, 0.0],
+        ])
+    }
+
+    pub fn rows(&self) -> [[f64; 4]; 4] {
+        self.rows
+    }
+
+    pub fn transpose(self) -> Mat44 {
+        let mut rows = [[0.0; 4]; 4];
+        for (i, row) in rows.iter_mut().enumerate() {
+            for (j, val) in row.iter_mut().enumerate() {
+                *val = self.rows[j][i];
+            }
+        }
+        Mat44::new(rows)
+    }
+
+    /// Applies the transform to a point, including the perspective divide
+    pub fn transform_point(&self, pt: Vec3D) -> Vec3D {
+        let [x, y, z, w] = self.mul_homogeneous([pt.x, pt.y, pt.z, 1.0]);
+        if w == 0.0 {
+            return Vec3D::new(x, y, z);
+        }
+        Vec3D::new(x / w, y / w, z / w)
+    }
+
+    /// Applies the transform to a direction, translations are ignored
+    pub fn transform_vector(&self, vec: Vec3D) -> Vec3D {
+        let [x, y, z, _] = self.mul_homogeneous([vec.x, vec.y, vec.z, 0.0]);
+        Vec3D::new(x, y, z)
+    }
+
+    fn mul_homogeneous(&self, vec: [f64; 4]) -> [f64; 4] {
+        self.rows
+            .map(|row| row.iter().zip(vec.iter()).map(|(m, v)| m * v).sum())
+    }
+
+    pub fn det(&self) -> f64 {
+        match self.lu() {
+            None => 0.0,
+            Some((lu, sign, _)) => sign * (0..4).map(|i| lu[i][i]).product::<f64>(),
+        }
+    }
+
+    pub fn inv(self) -> Option<Mat44> {
+        let (lu, _, perm) = self.lu()?;
+        // solve L y = P e_col, then U x = y for every column of the identity
+        let cols = [0, 1, 2, 3].map(|col| {
+            let mut x = [0.0; 4];
+            for i in 0..4 {
+                let rhs = if perm[i] == col { 1.0 } else { 0.0 };
+                x[i] = rhs - (0..i).map(|k| lu[i][k] * x[k]).sum::<f64>();
+            }
+            for i in (0..4).rev() {
+                let sum = ((i + 1)..4).map(|k| lu[i][k] * x[k]).sum::<f64>();
+                x[i] = (x[i] - sum) / lu[i][i];
+            }
+            x
+        });
+        Some(Mat44::new(cols).transpose())
+    }
+
+    /// LU decomposition with partial pivoting, returns the combined LU matrix,
+    /// the sign of the row permutation and the permutation itself
+    fn lu(&self) -> Option<([[f64; 4]; 4], f64, [usize; 4])> {
+        let mut lu = self.rows;
+        let mut perm = [0, 1, 2, 3];
+        let mut sign = 1.0;
+        for col in 0..4 {
+            let pivot = (col..4)
+                .max_by(|i, j| lu[*i][col].abs().partial_cmp(&lu[*j][col].abs()).unwrap())
+                .unwrap();
+            if lu[pivot][col] == 0.0 {
+                return None;
+            }
+            if pivot != col {
+                lu.swap(pivot, col);
+                perm.swap(pivot, col);
+                sign = -sign;
+            }
+            for row in (col + 1)..4 {
+                let pivot_row = lu[col];
+                let fac = lu[row][col] / pivot_row[col];
+                lu[row][col] = fac;
+                for (val, pivot_val) in lu[row].iter_mut().zip(pivot_row).skip(col + 1) {
+                    *val -= fac * pivot_val;
+                }
+            }
+        }
+        Some((lu, sign, perm))
+    }
+}
+
+impl Default for Mat44 {
+    fn default() -> Mat44 {
+        Mat44::identity()
+    }
+}
+
+impl From<[[f64; 4]; 4]> for Mat44 {
+    fn from(rows: [[f64; 4]; 4]) -> Mat44 {
+        Mat44::new(rows)
+    }
+}
+
+impl From<Mat33> for Mat44 {
+    fn from(mat: Mat33) -> Mat44 {
+        let m = mat.rows();
+        Mat44::new([
+            [m[0][0], m[0][1], m[0][2], 0.0],
+            [m[1][0], m[1][1], m[1][2], 0.0],
+            [m[2][0], m[2][1], m[2][2], 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+    }
+}
+
+impl Index<(usize, usize)> for Mat44 {
+    type Output = f64;
+    fn index(&self, (row, col): (usize, usize)) -> &f64 {
+        &self.rows[row][col]
+    }
+}
+
+impl Mul<Mat44> for Mat44 {
+    type Output = Mat44;
+    fn mul(self, other: Mat44) -> Mat44 {
+        let mut rows = [[0.0; 4]; 4];
+        for (i, row) in rows.iter_mut().enumerate() {
+            for (j, val) in row.iter_mut().enumerate() {
+                *val = (0..4).map(|k| self.rows[i][k] * other.rows[k][j]).sum();
+            }
+        }
+        Mat44::new(rows)
+    }
+}
+
+impl Mul<Vec3D> for Mat44 {
+    type Output = Vec3D;
+    fn mul(self, other: Vec3D) -> Vec3D {
+        self.transform_point(other)
+    }
+}
+
+impl Mul<f64> for Mat44 {
+    type Output = Mat44;
+    fn mul(self, other: f64) -> Mat44 {
+        Mat44::new(self.rows.map(|row| row.map(|val| val * other)))
+    }
+}
+
+impl Mul<Mat44> for f64 {
+    type Output = Mat44;
+    fn mul(self, other: Mat44) -> Mat44 {
+        other * self
+    }
+}
+
+#[cfg(test)]
+mod mat44_tests {
+    use super::Mat44;
+    use crate::vec3d::Vec3D;
+    use std::f64::consts::PI;
+
+    fn assert_close(result: Mat44, expected: Mat44) {
+        for i in 0..4 {
+            for j in 0..4 {
+                assert!(
+                    (result[(i, j)] - expected[(i, j)]).abs() < 1e-9,
+                    "{result:?} != {expected:?}"
+                );
+            }
+        }
+    }
+
+    fn assert_close_vec(result: Vec3D, expected: Vec3D) {
+        assert!(result.dist(&expected) < 1e-9, "{result} != {expected}")
+    }
+
+    #[test]
+    fn translate_point() {
+        let m = Mat44::translation(Vec3D::new(1.0, 2.0, 3.0));
+        assert_eq!(m * Vec3D::new(1.0, 1.0, 1.0), Vec3D::new(2.0, 3.0, 4.0));
+        assert_eq!(
+            m.transform_vector(Vec3D::new(1.0, 1.0, 1.0)),
+            Vec3D::new(1.0, 1.0, 1.0)
+        )
+    }
+
+    #[test]
+    fn compose() {
+        let scale = Mat44::scale(Vec3D::new(2.0, 2.0, 2.0));
+        let trans = Mat44::translation(Vec3D::new(1.0, 0.0, 0.0));
+        let result = (trans * scale) * Vec3D::new(1.0, 1.0, 1.0);
+        assert_eq!(result, Vec3D::new(3.0, 2.0, 2.0))
+    }
+
+    #[test]
+    fn rotation() {
+        let result = Mat44::rotation_y(PI / 2.0) * Vec3D::new(0.0, 0.0, 1.0);
+        assert_close_vec(result, Vec3D::new(1.0, 0.0, 0.0))
+    }
+
+    #[test]
+    fn inverse() {
+        let m = Mat44::translation(Vec3D::new(1.0, -2.0, 3.0))
+            * Mat44::rotation(Vec3D::new(1.0, 1.0, 0.0), 0.7)
+            * Mat44::scale(Vec3D::new(2.0, 3.0, 0.5));
+        assert_close(m * m.inv().unwrap(), Mat44::identity());
+        assert!((m.det() - 3.0).abs() < 1e-9)
+    }
+
+    #[test]
+    fn inverse_singular() {
+        let m = Mat44::scale(Vec3D::new(1.0, 0.0, 1.0));
+        assert_eq!(m.inv(), None);
+        assert_eq!(m.det(), 0.0)
+    }
+
+    #[test]
+    fn transpose() {
+        let m = Mat44::translation(Vec3D::new(1.0, 2.0, 3.0));
+        assert_eq!(m.transpose()[(3, 0)], 1.0);
+        assert_eq!(m.transpose().transpose(), m)
+    }
+
+    #[test]
+    fn look_at() {
+        let eye = Vec3D::new(0.0, 0.0, 5.0);
+        let view = Mat44::look_at(eye, Vec3D::default(), Vec3D::new(0.0, 1.0, 0.0));
+        assert_close_vec(view * Vec3D::default(), Vec3D::new(0.0, 0.0, -5.0));
+        assert_close_vec(view * eye, Vec3D::default());
+        assert_close_vec(view * Vec3D::new(1.0, 0.0, 0.0), Vec3D::new(1.0, 0.0, -5.0))
+    }
+
+    #[test]
+    fn perspective() {
+        let proj = Mat44::perspective(PI / 2.0, 1.0, 1.0, 10.0);
+        assert_close_vec(
+            proj * Vec3D::new(0.0, 0.0, -1.0),
+            Vec3D::new(0.0, 0.0, -1.0),
+        );
+        assert_close_vec(
+            proj * Vec3D::new(0.0, 0.0, -10.0),
+            Vec3D::new(0.0, 0.0, 1.0),
+        );
+        assert_close_vec(
+            proj * Vec3D::new(2.0, 2.0, -2.0),
+            Vec3D::new(1.0, 1.0, 1.0 / 9.0),
+        )
+    }
+}
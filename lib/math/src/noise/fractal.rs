@@ -0,0 +1,155 @@
+use super::Noise;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FractalKind {
+    /// Plain sum of octaves, values lie in [-1,1]
+    Fbm,
+    /// Sharp ridges where the base noise crosses zero, values lie in [0,1]
+    Ridged,
+    /// Sum of absolute values, values lie in [0,1]
+    Turbulence,
+}
+
+/// Sums octaves of a base noise, each octave has `lacunarity` times the frequency
+/// and `gain` times the amplitude of the previous one
+#[derive(Debug, Clone)]
+pub struct Fractal<N: Noise> {
+    pub noise: N,
+    pub kind: FractalKind,
+    pub octaves: usize,
+    pub lacunarity: f64,
+    pub gain: f64,
+}
+
+impl<N: Noise> Fractal<N> {
+    pub fn new(noise: N, kind: FractalKind) -> Fractal<N> {
+        Fractal {
+            noise,
+            kind,
+            octaves: 4,
+            lacunarity: 2.0,
+            gain: 0.5,
+        }
+    }
+
+    pub fn fbm(noise: N) -> Fractal<N> {
+        Fractal::new(noise, FractalKind::Fbm)
+    }
+
+    pub fn ridged(noise: N) -> Fractal<N> {
+        Fractal::new(noise, FractalKind::Ridged)
+    }
+
+    pub fn turbulence(noise: N) -> Fractal<N> {
+        Fractal::new(noise, FractalKind::Turbulence)
+    }
+
+    pub fn with_octaves(mut self, octaves: usize) -> Fractal<N> {
+        self.octaves = octaves;
+        self
+    }
+
+    pub fn with_lacunarity(mut self, lacunarity: f64) -> Fractal<N> {
+        self.lacunarity = lacunarity;
+        self
+    }
+
+    pub fn with_gain(mut self, gain: f64) -> Fractal<N> {
+        self.gain = gain;
+        self
+    }
+
+    /// `sample` evaluates the base noise at the given frequency
+    fn combine<F: Fn(f64) -> f64>(&self, sample: F) -> f64 {
+        let mut sum = 0.0;
+        let mut total_amplitude = 0.0;
+        let mut frequency = 1.0;
+        let mut amplitude = 1.0;
+        for _ in 0..self.octaves {
+            let val = sample(frequency);
+            sum += amplitude
+                * match self.kind {
+                    FractalKind::Fbm => val,
+                    FractalKind::Ridged => (1.0 - val.abs()).powi(2),
+                    FractalKind::Turbulence => val.abs(),
+                };
+            total_amplitude += amplitude;
+            frequency *= self.lacunarity;
+            amplitude *= self.gain;
+        }
+        if total_amplitude == 0.0 {
+            return 0.0;
+        }
+        sum / total_amplitude
+    }
+}
+
+impl<N: Noise> Noise for Fractal<N> {
+    fn noise1(&self, x: f64) -> f64 {
+        self.combine(|f| self.noise.noise1(f * x))
+    }
+
+    fn noise2(&self, x: f64, y: f64) -> f64 {
+        self.combine(|f| self.noise.noise2(f * x, f * y))
+    }
+
+    fn noise3(&self, x: f64, y: f64, z: f64) -> f64 {
+        self.combine(|f| self.noise.noise3(f * x, f * y, f * z))
+    }
+
+    fn noise4(&self, x: f64, y: f64, z: f64, w: f64) -> f64 {
+        self.combine(|f| self.noise.noise4(f * x, f * y, f * z, f * w))
+    }
+}
+
+/// Domain warping, samples `noise` at points displaced by `strength` times `warp`,
+/// every coordinate is displaced by `warp` sampled at a different offset
+#[derive(Debug, Clone)]
+pub struct Warp<N: Noise, W: Noise> {
+    pub noise: N,
+    pub warp: W,
+    pub strength: f64,
+}
+
+/// Offsets decorrelating the displacements of the different coordinates
+const WARP_OFFSETS: [f64; 4] = [0.0, 5.2, 1.7, 9.2];
+
+impl<N: Noise, W: Noise> Warp<N, W> {
+    pub fn new(noise: N, warp: W, strength: f64) -> Warp<N, W> {
+        Warp {
+            noise,
+            warp,
+            strength,
+        }
+    }
+
+    fn displace<const D: usize, F: Fn([f64; D]) -> f64>(&self, pt: [f64; D], warp: F) -> [f64; D] {
+        let mut displaced = pt;
+        for (i, coord) in displaced.iter_mut().enumerate() {
+            *coord += self.strength * warp(pt.map(|c| c + WARP_OFFSETS[i]));
+        }
+        displaced
+    }
+}
+
+impl<N: Noise, W: Noise> Noise for Warp<N, W> {
+    fn noise1(&self, x: f64) -> f64 {
+        let [x] = self.displace([x], |[x]| self.warp.noise1(x));
+        self.noise.noise1(x)
+    }
+
+    fn noise2(&self, x: f64, y: f64) -> f64 {
+        let [x, y] = self.displace([x, y], |[x, y]| self.warp.noise2(x, y));
+        self.noise.noise2(x, y)
+    }
+
+    fn noise3(&self, x: f64, y: f64, z: f64) -> f64 {
+        let [x, y, z] = self.displace([x, y, z], |[x, y, z]| self.warp.noise3(x, y, z));
+        self.noise.noise3(x, y, z)
+    }
+
+    fn noise4(&self, x: f64, y: f64, z: f64, w: f64) -> f64 {
+        let [x, y, z, w] = self.displace([x, y, z, w], |[x, y, z, w]| self.warp.noise4(x, y, z, w));
+        self.noise.noise4(x, y, z, w)
+    }
+}
@@ -0,0 +1,230 @@
+pub mod fractal;
+pub mod perlin;
+pub mod simplex;
+pub mod value;
+pub use fractal::{Fractal, FractalKind, Warp};
+pub use perlin::Perlin;
+pub use simplex::Simplex;
+pub use value::ValueNoise;
+
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+
+/// Coherent noise sampled at points of up to four dimensions,
+/// the same noise returns the same value for the same point
+pub trait Noise {
+    fn noise1(&self, x: f64) -> f64;
+    fn noise2(&self, x: f64, y: f64) -> f64;
+    fn noise3(&self, x: f64, y: f64, z: f64) -> f64;
+    fn noise4(&self, x: f64, y: f64, z: f64, w: f64) -> f64;
+}
+
+/// Shuffled permutation of 0..256, doubled so lookups never need to wrap
+#[derive(Debug, Clone)]
+struct PermTable {
+    perm: Vec<u8>,
+}
+
+impl PermTable {
+    fn new(seed: u64) -> PermTable {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut perm: Vec<u8> = (0..=255).collect();
+        perm.shuffle(&mut rng);
+        perm.extend_from_within(..);
+        PermTable { perm }
+    }
+
+    /// Hashes integer lattice coordinates to a byte
+    fn hash<const N: usize>(&self, cell: [i64; N]) -> u8 {
+        cell.iter().fold(0, |hash, coord| {
+            self.perm[hash as usize + (coord & 255) as usize]
+        })
+    }
+}
+
+/// Dot product of the offset with a pseudo random gradient picked by `hash`
+fn grad<const N: usize>(hash: u8, offset: [f64; N]) -> f64 {
+    let sign = |bit: u8, val: f64| if hash & bit == 0 { val } else { -val };
+    match offset[..] {
+        [x] => sign(8, (1 + (hash & 7)) as f64 * x) / 8.0,
+        [x, y] => match hash & 7 {
+            0..=3 => sign(1, x) + sign(2, y),
+            4 | 5 => sign(1, x),
+            _ => sign(1, y),
+        },
+        [x, y, z] => {
+            // the 12 edge midpoints of the cube, see Perlin's "Improving Noise"
+            let h = hash & 15;
+            let u = if h < 8 { x } else { y };
+            let v = match h {
+                0..=3 => y,
+                12 | 14 => x,
+                _ => z,
+            };
+            sign(1, u) + sign(2, v)
+        }
+        [x, y, z, w] => {
+            // the 32 edge midpoints of the tesseract, one coordinate is dropped
+            let [a, b, c] = match (hash >> 3) & 3 {
+                0 => [y, z, w],
+                1 => [x, z, w],
+                2 => [x, y, w],
+                _ => [x, y, z],
+            };
+            sign(1, a) + sign(2, b) + sign(4, c)
+        }
+        _ => offset.iter().sum(),
+    }
+}
+
+fn fade(t: f64) -> f64 {
+    t * t * t * (t * (6.0 * t - 15.0) + 10.0)
+}
+
+/// Smoothly interpolates `corner` over the lattice cell containing `pt`,
+/// `corner` gets the hash of a cell corner and the offset from it to `pt`
+fn lattice<const N: usize, F>(perm: &PermTable, pt: [f64; N], corner: F) -> f64
+where
+    F: Fn(u8, [f64; N]) -> f64,
+{
+    let cell = pt.map(|coord| coord.floor());
+    let frac: Vec<f64> = pt.iter().zip(cell).map(|(p, c)| p - c).collect();
+    let weights: Vec<f64> = frac.iter().map(|t| fade(*t)).collect();
+
+    let mut sum = 0.0;
+    for bits in 0..(1 << N) {
+        let mut corner_cell = [0; N];
+        let mut offset = [0.0; N];
+        let mut weight = 1.0;
+        for dim in 0..N {
+            let bit = (bits >> dim) & 1;
+            corner_cell[dim] = cell[dim] as i64 + bit as i64;
+            offset[dim] = frac[dim] - bit as f64;
+            weight *= if bit == 1 {
+                weights[dim]
+            } else {
+                1.0 - weights[dim]
+            };
+        }
+        sum += weight * corner(perm.hash(corner_cell), offset);
+    }
+    sum
+}
+
+#[cfg(test)]
+mod noise_tests {
+    use super::{Fractal, Noise, Perlin, Simplex, ValueNoise, Warp};
+
+    /// Samples a noise in every dimension along a pseudo random walk
+    fn samples<N: Noise>(noise: &N) -> Vec<f64> {
+        let mut result = vec![];
+        for i in 0..2000 {
+            let t = i as f64 * 0.173;
+            let (x, y, z, w) = (t, 1.3 * t + 0.7, (0.7 * t).sin() * 5.0, t * 0.31 - 3.0);
+            result.push(noise.noise1(x));
+            result.push(noise.noise2(x, y));
+            result.push(noise.noise3(x, y, z));
+            result.push(noise.noise4(x, y, z, w));
+        }
+        result
+    }
+
+    fn assert_range<N: Noise>(noise: &N, min: f64, max: f64) {
+        for sample in samples(noise) {
+            assert!(
+                (min..=max).contains(&sample),
+                "{sample} not in [{min},{max}]"
+            )
+        }
+    }
+
+    fn assert_seeded<N: Noise>(make: fn(u64) -> N) {
+        let samples1 = samples(&make(3));
+        assert_eq!(samples1, samples(&make(3)));
+        assert_ne!(samples1, samples(&make(4)))
+    }
+
+    fn assert_continuous<N: Noise>(noise: &N) {
+        let eps = 1e-4;
+        for i in 0..200 {
+            let x = i as f64 * 0.37;
+            assert!((noise.noise1(x) - noise.noise1(x + eps)).abs() < 0.01);
+            assert!((noise.noise2(x, 0.5 * x) - noise.noise2(x + eps, 0.5 * x)).abs() < 0.01);
+            let diff = noise.noise3(x, 1.0, -x) - noise.noise3(x, 1.0 + eps, -x);
+            assert!(diff.abs() < 0.01);
+            let diff = noise.noise4(x, 2.0, x, 0.1) - noise.noise4(x, 2.0, x, 0.1 + eps);
+            assert!(diff.abs() < 0.01)
+        }
+    }
+
+    #[test]
+    fn perlin() {
+        let noise = Perlin::new(1);
+        assert_range(&noise, -1.0, 1.0);
+        assert_seeded(Perlin::new);
+        assert_continuous(&noise);
+        // gradient noise vanishes at lattice points
+        assert_eq!(noise.noise2(3.0, -2.0), 0.0);
+        assert_eq!(noise.noise4(1.0, 2.0, 3.0, 4.0), 0.0)
+    }
+
+    #[test]
+    fn simplex() {
+        let noise = Simplex::new(1);
+        assert_range(&noise, -1.0, 1.0);
+        assert_seeded(Simplex::new);
+        assert_continuous(&noise);
+        // sorting the corner offsets must not panic on NaN
+        noise.noise3(f64::NAN, 1.0, 2.0);
+    }
+
+    #[test]
+    fn value() {
+        let noise = ValueNoise::new(1);
+        assert_range(&noise, -1.0, 1.0);
+        assert_seeded(ValueNoise::new);
+        assert_continuous(&noise)
+    }
+
+    #[test]
+    fn not_constant() {
+        for samples in [
+            samples(&Perlin::new(5)),
+            samples(&Simplex::new(5)),
+            samples(&ValueNoise::new(5)),
+        ] {
+            let mean = samples.iter().sum::<f64>() / samples.len() as f64;
+            let var =
+                samples.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / samples.len() as f64;
+            assert!(mean.abs() < 0.1, "mean {mean}");
+            assert!(var > 0.01, "variance {var}")
+        }
+    }
+
+    #[test]
+    fn fractal_ranges() {
+        assert_range(&Fractal::fbm(Perlin::new(2)), -1.0, 1.0);
+        assert_range(&Fractal::ridged(Simplex::new(2)), 0.0, 1.0);
+        assert_range(&Fractal::turbulence(Perlin::new(2)), 0.0, 1.0)
+    }
+
+    #[test]
+    fn fractal_single_octave() {
+        let noise = Perlin::new(7);
+        let fbm = Fractal::fbm(noise.clone()).with_octaves(1);
+        assert_eq!(fbm.noise2(0.3, 0.4), noise.noise2(0.3, 0.4));
+        let turbulence = Fractal::turbulence(noise.clone()).with_octaves(1);
+        assert_eq!(
+            turbulence.noise3(0.3, 0.4, 0.5),
+            noise.noise3(0.3, 0.4, 0.5).abs()
+        )
+    }
+
+    #[test]
+    fn warp() {
+        let unwarped = Warp::new(Perlin::new(1), Simplex::new(2), 0.0);
+        assert_eq!(unwarped.noise2(0.3, 0.6), Perlin::new(1).noise2(0.3, 0.6));
+        let warped = Warp::new(Perlin::new(1), Simplex::new(2), 2.0);
+        assert_range(&warped, -1.0, 1.0);
+        assert_ne!(warped.noise2(0.3, 0.6), unwarped.noise2(0.3, 0.6))
+    }
+}
@@ -0,0 +1,39 @@
+use super::{grad, lattice, Noise, PermTable};
+
+/// Improved Perlin gradient noise, values lie in [-1,1] and vanish on the integer lattice
+#[derive(Debug, Clone)]
+pub struct Perlin {
+    perm: PermTable,
+}
+
+impl Perlin {
+    pub fn new(seed: u64) -> Perlin {
+        Perlin {
+            perm: PermTable::new(seed),
+        }
+    }
+}
+
+impl Default for Perlin {
+    fn default() -> Perlin {
+        Perlin::new(0)
+    }
+}
+
+impl Noise for Perlin {
+    fn noise1(&self, x: f64) -> f64 {
+        2.0 * lattice(&self.perm, [x], grad)
+    }
+
+    fn noise2(&self, x: f64, y: f64) -> f64 {
+        lattice(&self.perm, [x, y], grad)
+    }
+
+    fn noise3(&self, x: f64, y: f64, z: f64) -> f64 {
+        lattice(&self.perm, [x, y, z], grad)
+    }
+
+    fn noise4(&self, x: f64, y: f64, z: f64, w: f64) -> f64 {
+        lattice(&self.perm, [x, y, z, w], grad) / 1.5
+    }
+}
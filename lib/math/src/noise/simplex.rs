@@ -0,0 +1,79 @@
+use super::{grad, Noise, PermTable};
+
+/// Simplex noise, cheaper than Perlin noise in higher dimensions
+/// and without its axis aligned artifacts, values lie in [-1,1]
+#[derive(Debug, Clone)]
+pub struct Simplex {
+    perm: PermTable,
+}
+
+impl Simplex {
+    pub fn new(seed: u64) -> Simplex {
+        Simplex {
+            perm: PermTable::new(seed),
+        }
+    }
+
+    /// Sums the contributions of the corners of the simplex containing `pt`,
+    /// see Gustavson's "Simplex noise demystified"
+    fn sample<const N: usize>(&self, pt: [f64; N], radius_sq: f64, scale: f64) -> f64 {
+        let dim = N as f64;
+        let skew = ((dim + 1.0).sqrt() - 1.0) / dim;
+        let unskew = (1.0 - 1.0 / (dim + 1.0).sqrt()) / dim;
+
+        let skewed_sum = pt.iter().sum::<f64>() * skew;
+        let cell = pt.map(|coord| (coord + skewed_sum).floor());
+        let unskewed_sum = cell.iter().sum::<f64>() * unskew;
+        let mut origin = [0.0; N];
+        for (i, val) in origin.iter_mut().enumerate() {
+            *val = pt[i] - cell[i] + unskewed_sum;
+        }
+
+        // walking along the axes in order of decreasing offset visits the corners
+        let mut order: [usize; N] = std::array::from_fn(|i| i);
+        order.sort_by(|i, j| origin[*j].total_cmp(&origin[*i]));
+
+        let mut sum = 0.0;
+        let mut corner = [0; N];
+        for step in 0..=N {
+            if step > 0 {
+                corner[order[step - 1]] += 1;
+            }
+            let mut offset = [0.0; N];
+            let mut corner_cell = [0; N];
+            for i in 0..N {
+                offset[i] = origin[i] - corner[i] as f64 + step as f64 * unskew;
+                corner_cell[i] = cell[i] as i64 + corner[i];
+            }
+            let falloff = radius_sq - offset.iter().map(|d| d * d).sum::<f64>();
+            if falloff > 0.0 {
+                sum += falloff.powi(4) * grad(self.perm.hash(corner_cell), offset);
+            }
+        }
+        scale * sum
+    }
+}
+
+impl Default for Simplex {
+    fn default() -> Simplex {
+        Simplex::new(0)
+    }
+}
+
+impl Noise for Simplex {
+    fn noise1(&self, x: f64) -> f64 {
+        self.sample([x], 0.5, 70.0)
+    }
+
+    fn noise2(&self, x: f64, y: f64) -> f64 {
+        self.sample([x, y], 0.5, 70.0)
+    }
+
+    fn noise3(&self, x: f64, y: f64, z: f64) -> f64 {
+        self.sample([x, y, z], 0.6, 32.0)
+    }
+
+    fn noise4(&self, x: f64, y: f64, z: f64, w: f64) -> f64 {
+        self.sample([x, y, z, w], 0.6, 27.0)
+    }
+}
@@ -0,0 +1,43 @@
+use super::{lattice, Noise, PermTable};
+
+/// Interpolated random values on the integer lattice, values lie in [-1,1]
+#[derive(Debug, Clone)]
+pub struct ValueNoise {
+    perm: PermTable,
+}
+
+impl ValueNoise {
+    pub fn new(seed: u64) -> ValueNoise {
+        ValueNoise {
+            perm: PermTable::new(seed),
+        }
+    }
+}
+
+impl Default for ValueNoise {
+    fn default() -> ValueNoise {
+        ValueNoise::new(0)
+    }
+}
+
+fn value<const N: usize>(hash: u8, _: [f64; N]) -> f64 {
+    hash as f64 / 127.5 - 1.0
+}
+
+impl Noise for ValueNoise {
+    fn noise1(&self, x: f64) -> f64 {
+        lattice(&self.perm, [x], value)
+    }
+
+    fn noise2(&self, x: f64, y: f64) -> f64 {
+        lattice(&self.perm, [x, y], value)
+    }
+
+    fn noise3(&self, x: f64, y: f64, z: f64) -> f64 {
+        lattice(&self.perm, [x, y, z], value)
+    }
+
+    fn noise4(&self, x: f64, y: f64, z: f64, w: f64) -> f64 {
+        lattice(&self.perm, [x, y, z, w], value)
+    }
+}
@@ -0,0 +1,257 @@
+use super::{mat33::Mat33, mat44::Mat44, vec3d::Vec3D};
+use std::{
+    fmt,
+    ops::{Add, Mul, Neg},
+};
+
+/// Quaternion w + xi + yj + zk, unit quaternions represent rotations
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Quaternion {
+    pub w: f64,
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+impl Quaternion {
+    pub fn new(w: f64, x: f64, y: f64, z: f64) -> Quaternion {
+        Quaternion { w, x, y, z }
+    }
+
+    pub fn identity() -> Quaternion {
+        Quaternion::new(1.0, 0.0, 0.0, 0.0)
+    }
+
+    /// Counter-clockwise rotation by `angle` around `axis`
+    pub fn from_axis_angle(axis: Vec3D, angle: f64) -> Quaternion {
+        let (sin, cos) = (angle / 2.0).sin_cos();
+        let axis = axis.normalize() * sin;
+        Quaternion::new(cos, axis.x, axis.y, axis.z)
+    }
+
+    /// Returns the rotation axis and angle, the axis is arbitrary for the identity
+    pub fn axis_angle(&self) -> (Vec3D, f64) {
+        let q = self.normalize();
+        let angle = 2.0 * q.w.clamp(-1.0, 1.0).acos();
+        let axis = q.vector();
+        if axis.abs() == 0.0 {
+            return (Vec3D::new(1.0, 0.0, 0.0), 0.0);
+        }
+        (axis.normalize(), angle)
+    }
+
+    pub fn vector(&self) -> Vec3D {
+        Vec3D::new(self.x, self.y, self.z)
+    }
+
+    pub fn dot(self, other: Quaternion) -> f64 {
+        self.w * other.w + self.x * other.x + self.y * other.y + self.z * other.z
+    }
+
+    pub fn norm(&self) -> f64 {
+        self.dot(*self).sqrt()
+    }
+
+    pub fn normalize(self) -> Quaternion {
+        let norm = self.norm();
+        if norm == 0.0 {
+            return self;
+        }
+        self * (1.0 / norm)
+    }
+
+    pub fn conjugate(self) -> Quaternion {
+        Quaternion::new(self.w, -self.x, -self.y, -self.z)
+    }
+
+    pub fn inv(self) -> Option<Quaternion> {
+        let norm_sq = self.dot(self);
+        if norm_sq == 0.0 {
+            return None;
+        }
+        Some(self.conjugate() * (1.0 / norm_sq))
+    }
+
+    /// Rotates `vec`, assumes a unit quaternion
+    pub fn rotate(&self, vec: Vec3D) -> Vec3D {
+        let u = self.vector();
+        let t = 2.0 * u.cross(vec);
+        vec + self.w * t + u.cross(t)
+    }
+
+    /// Spherical linear interpolation along the shortest arc
+    pub fn slerp(self, other: Quaternion, t: f64) -> Quaternion {
+        let mut other = other;
+        let mut cos = self.dot(other);
+        if cos < 0.0 {
+            other = -other;
+            cos = -cos;
+        }
+
+        if cos > 1.0 - 1e-9 {
+            return (self * (1.0 - t) + other * t).normalize();
+        }
+
+        let angle = cos.acos();
+        let sin = angle.sin();
+        self * (((1.0 - t) * angle).sin() / sin) + other * ((t * angle).sin() / sin)
+    }
+
+    pub fn to_mat33(&self) -> Mat33 {
+        let Quaternion { w, x, y, z } = self.normalize();
+        Mat33::new([
+            [
+                1.0 - 2.0 * (y * y + z * z),
+                2.0 * (x * y - w * z),
+                2.0 * (x * z + w * y),
+            ],
+            [
+                2.0 * (x * y + w * z),
+                1.0 - 2.0 * (x * x + z * z),
+                2.0 * (y * z - w * x),
+            ],
+            [
+                2.0 * (x * z - w * y),
+                2.0 * (y * z + w * x),
+                1.0 - 2.0 * (x * x + y * y),
+            ],
+        ])
+    }
+
+    pub fn to_mat44(&self) -> Mat44 {
+        Mat44::from(self.to_mat33())
+    }
+}
+
+impl Default for Quaternion {
+    fn default() -> Quaternion {
+        Quaternion::identity()
+    }
+}
+
+impl Add for Quaternion {
+    type Output = Quaternion;
+    fn add(self, other: Quaternion) -> Quaternion {
+        Quaternion::new(
+            self.w + other.w,
+            self.x + other.x,
+            self.y + other.y,
+            self.z + other.z,
+        )
+    }
+}
+
+impl Mul for Quaternion {
+    type Output = Quaternion;
+    fn mul(self, other: Quaternion) -> Quaternion {
+        Quaternion::new(
+            self.w * other.w - self.x * other.x - self.y * other.y - self.z * other.z,
+            self.w * other.x + self.x * other.w + self.y * other.z - self.z * other.y,
+            self.w * other.y - self.x * other.z + self.y * other.w + self.z * other.x,
+            self.w * other.z + self.x * other.y - self.y * other.x + self.z * other.w,
+        )
+    }
+}
+
+impl Mul<f64> for Quaternion {
+    type Output = Quaternion;
+    fn mul(self, other: f64) -> Quaternion {
+        Quaternion::new(
+            self.w * other,
+            self.x * other,
+            self.y * other,
+            self.z * other,
+        )
+    }
+}
+
+impl Mul<Vec3D> for Quaternion {
+    type Output = Vec3D;
+    fn mul(self, other: Vec3D) -> Vec3D {
+        self.rotate(other)
+    }
+}
+
+impl Neg for Quaternion {
+    type Output = Quaternion;
+    fn neg(self) -> Quaternion {
+        self * -1.0
+    }
+}
+
+impl fmt::Display for Quaternion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({}+{}i+{}j+{}k)", self.w, self.x, self.y, self.z)
+    }
+}
+
+#[cfg(test)]
+mod quaternion_tests {
+    use super::Quaternion;
+    use crate::{mat33::Mat33, vec3d::Vec3D};
+    use std::f64::consts::PI;
+
+    fn assert_close_vec(result: Vec3D, expected: Vec3D) {
+        assert!(result.dist(&expected) < 1e-9, "{result} != {expected}")
+    }
+
+    #[test]
+    fn rotate_vec() {
+        let q = Quaternion::from_axis_angle(Vec3D::new(0.0, 0.0, 1.0), PI / 2.0);
+        let result = q * Vec3D::new(1.0, 0.0, 0.0);
+        assert_close_vec(result, Vec3D::new(0.0, 1.0, 0.0))
+    }
+
+    #[test]
+    fn matches_matrix() {
+        let axis = Vec3D::new(1.0, -2.0, 0.5);
+        let q = Quaternion::from_axis_angle(axis, 0.8);
+        let mat = Mat33::rotation(axis, 0.8);
+        let v = Vec3D::new(0.3, 1.0, -2.0);
+        assert_close_vec(q.rotate(v), mat * v);
+        assert_close_vec(q.to_mat33() * v, mat * v)
+    }
+
+    #[test]
+    fn compose() {
+        let q1 = Quaternion::from_axis_angle(Vec3D::new(1.0, 0.0, 0.0), 0.4);
+        let q2 = Quaternion::from_axis_angle(Vec3D::new(0.0, 1.0, 0.0), 1.1);
+        let v = Vec3D::new(1.0, 2.0, 3.0);
+        assert_close_vec((q2 * q1).rotate(v), q2.rotate(q1.rotate(v)))
+    }
+
+    #[test]
+    fn inverse() {
+        let q = Quaternion::new(1.0, 2.0, -1.0, 0.5);
+        let result = q * q.inv().unwrap();
+        assert!((result.w - 1.0).abs() < 1e-9);
+        assert!(result.vector().abs() < 1e-9);
+        assert_eq!(Quaternion::new(0.0, 0.0, 0.0, 0.0).inv(), None)
+    }
+
+    #[test]
+    fn axis_angle_roundtrip() {
+        let axis = Vec3D::new(0.0, 3.0, 4.0);
+        let (result_axis, result_angle) = Quaternion::from_axis_angle(axis, 1.3).axis_angle();
+        assert_close_vec(result_axis, axis.normalize());
+        assert!((result_angle - 1.3).abs() < 1e-9)
+    }
+
+    #[test]
+    fn slerp_halfway() {
+        let axis = Vec3D::new(0.0, 0.0, 1.0);
+        let q1 = Quaternion::identity();
+        let q2 = Quaternion::from_axis_angle(axis, PI / 2.0);
+        let result = q1.slerp(q2, 0.5);
+        let expected = Quaternion::from_axis_angle(axis, PI / 4.0);
+        assert!((result.dot(expected) - 1.0).abs() < 1e-9)
+    }
+
+    #[test]
+    fn slerp_endpoints() {
+        let q1 = Quaternion::from_axis_angle(Vec3D::new(1.0, 1.0, 0.0), 0.2);
+        let q2 = Quaternion::from_axis_angle(Vec3D::new(0.0, 1.0, 1.0), 2.0);
+        assert!((q1.slerp(q2, 0.0).dot(q1) - 1.0).abs() < 1e-9);
+        assert!((q1.slerp(q2, 1.0).dot(q2) - 1.0).abs() < 1e-9)
+    }
+}
@@ -0,0 +1,164 @@
+use super::{Nearest, SpatialIndex};
+use crate::vec2d::Vec2D;
+use std::collections::HashMap;
+
+/// Buckets points into square cells of `cell_size`, works best when most queries
+/// have a radius close to the cell size and points are spread evenly
+#[derive(Debug, Clone)]
+pub struct GridHash<T> {
+    cell_size: f64,
+    cells: HashMap<(i64, i64), Vec<(Vec2D, T)>>,
+    len: usize,
+    /// smallest and largest occupied cell coordinates, never shrinks on removal
+    cell_bounds: Option<[(i64, i64); 2]>,
+}
+
+impl<T> GridHash<T> {
+    pub fn new(cell_size: f64) -> GridHash<T> {
+        GridHash {
+            cell_size,
+            cells: HashMap::new(),
+            len: 0,
+            cell_bounds: None,
+        }
+    }
+
+    fn cell(&self, pos: Vec2D) -> (i64, i64) {
+        (
+            (pos.x / self.cell_size).floor() as i64,
+            (pos.y / self.cell_size).floor() as i64,
+        )
+    }
+
+    /// Points in ring `r` around the cell of `pos` are at least
+    /// `r * cell_size - offset_in_cell(pos)` away from `pos`
+    fn offset_in_cell(&self, pos: Vec2D) -> f64 {
+        let (cx, cy) = self.cell(pos);
+        let fx = pos.x / self.cell_size - cx as f64;
+        let fy = pos.y / self.cell_size - cy as f64;
+        let to_edge = fx.min(1.0 - fx).min(fy).min(1.0 - fy);
+        self.cell_size * (1.0 - to_edge)
+    }
+
+    /// Items in the cells at chebyshev distance `ring` from `center`
+    fn ring(&self, center: (i64, i64), ring: i64) -> impl Iterator<Item = &(Vec2D, T)> {
+        let (cx, cy) = center;
+        let cells: Vec<(i64, i64)> = if ring == 0 {
+            vec![center]
+        } else {
+            let horizontal =
+                (-ring..=ring).flat_map(move |dx| [(cx + dx, cy - ring), (cx + dx, cy + ring)]);
+            let vertical =
+                (1 - ring..ring).flat_map(move |dy| [(cx - ring, cy + dy), (cx + ring, cy + dy)]);
+            horizontal.chain(vertical).collect()
+        };
+        cells
+            .into_iter()
+            .filter_map(|cell| self.cells.get(&cell))
+            .flatten()
+    }
+}
+
+impl<T> SpatialIndex<T> for GridHash<T> {
+    fn insert(&mut self, pos: Vec2D, item: T) {
+        let cell = self.cell(pos);
+        self.cell_bounds = Some(match self.cell_bounds {
+            None => [cell, cell],
+            Some([min, max]) => [
+                (min.0.min(cell.0), min.1.min(cell.1)),
+                (max.0.max(cell.0), max.1.max(cell.1)),
+            ],
+        });
+        self.cells.entry(cell).or_default().push((pos, item));
+        self.len += 1;
+    }
+
+    fn remove(&mut self, pos: Vec2D, item: &T) -> Option<T>
+    where
+        T: PartialEq,
+    {
+        let cell = self.cell(pos);
+        let bucket = self.cells.get_mut(&cell)?;
+        let ind = bucket
+            .iter()
+            .position(|(other_pos, other)| *other_pos == pos && other == item)?;
+        let (_, removed) = bucket.swap_remove(ind);
+        if bucket.is_empty() {
+            self.cells.remove(&cell);
+        }
+        self.len -= 1;
+        Some(removed)
+    }
+
+    fn within(&self, center: Vec2D, radius: f64) -> Vec<(Vec2D, &T)> {
+        let (min_x, min_y) = self.cell(center - Vec2D::new(radius, radius));
+        let (max_x, max_y) = self.cell(center + Vec2D::new(radius, radius));
+        let span = |min: i64, max: i64| max.checked_sub(min)?.checked_add(1);
+        let num_cells = span(min_x, max_x)
+            .zip(span(min_y, max_y))
+            .and_then(|(width, height)| width.checked_mul(height));
+        let mut found = vec![];
+        // large queries visit the occupied cells instead of every cell in range,
+        // so do ranges with more cells than fit into an i64
+        if num_cells.is_none_or(|num| num as usize > self.cells.len()) {
+            for ((x, y), bucket) in self.cells.iter() {
+                if (min_x..=max_x).contains(x) && (min_y..=max_y).contains(y) {
+                    found.extend(bucket.iter());
+                }
+            }
+        } else {
+            for x in min_x..=max_x {
+                for y in min_y..=max_y {
+                    if let Some(bucket) = self.cells.get(&(x, y)) {
+                        found.extend(bucket.iter());
+                    }
+                }
+            }
+        }
+        found
+            .into_iter()
+            .filter(|(pos, _)| pos.dist(&center) <= radius)
+            .map(|(pos, item)| (*pos, item))
+            .collect()
+    }
+
+    fn nearest(&self, center: Vec2D, k: usize) -> Vec<(Vec2D, &T)> {
+        let mut nearest = Nearest::new(center, k);
+        let [min, max] = match self.cell_bounds {
+            None => return vec![],
+            Some(bounds) => bounds,
+        };
+        let center_cell = self.cell(center);
+        let max_ring = [
+            center_cell.0 - min.0,
+            max.0 - center_cell.0,
+            center_cell.1 - min.1,
+            max.1 - center_cell.1,
+        ]
+        .into_iter()
+        .max()
+        .unwrap_or(0)
+        .max(0);
+
+        for ring in 0..=max_ring {
+            // no point in this ring or further out can be closer than the ones found
+            if nearest.worst() <= ring as f64 * self.cell_size - self.offset_in_cell(center) {
+                break;
+            }
+            for (pos, item) in self.ring(center_cell, ring) {
+                nearest.push(*pos, item);
+            }
+        }
+        nearest.into_vec()
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn clear(&mut self) {
+        self.cells.clear();
+        self.len = 0;
+        self.cell_bounds = None;
+    }
+}
@@ -0,0 +1,219 @@
+use super::{Nearest, SpatialIndex};
+use crate::vec2d::Vec2D;
+
+/// 2D k-d tree alternating between splitting along x and y,
+/// `from_points` builds a balanced tree while `insert` appends leaves.
+/// Removed items leave a tombstone until more than half the nodes are dead,
+/// then the tree gets rebuilt.
+#[derive(Debug, Clone)]
+pub struct KdTree<T> {
+    nodes: Vec<Node<T>>,
+    root: Option<usize>,
+    len: usize,
+}
+
+#[derive(Debug, Clone)]
+struct Node<T> {
+    pos: Vec2D,
+    item: Option<T>,
+    /// split along x if true, along y otherwise
+    split_x: bool,
+    left: Option<usize>,
+    right: Option<usize>,
+}
+
+impl<T> Node<T> {
+    /// Signed distance from `pos` to the splitting line, negative on the left side
+    fn side(&self, pos: Vec2D) -> f64 {
+        if self.split_x {
+            pos.x - self.pos.x
+        } else {
+            pos.y - self.pos.y
+        }
+    }
+}
+
+impl<T> KdTree<T> {
+    pub fn new() -> KdTree<T> {
+        KdTree {
+            nodes: vec![],
+            root: None,
+            len: 0,
+        }
+    }
+
+    pub fn from_points<I: IntoIterator<Item = (Vec2D, T)>>(points: I) -> KdTree<T> {
+        let mut tree = KdTree::new();
+        let points: Vec<(Vec2D, T)> = points.into_iter().collect();
+        tree.len = points.len();
+        tree.root = tree.build(points, true);
+        tree
+    }
+
+    fn build(&mut self, mut points: Vec<(Vec2D, T)>, split_x: bool) -> Option<usize> {
+        if points.is_empty() {
+            return None;
+        }
+        let key = |pos: &Vec2D| if split_x { pos.x } else { pos.y };
+        points.sort_by(|(p1, _), (p2, _)| key(p1).partial_cmp(&key(p2)).unwrap());
+        // points equal to the median along the split axis must end up on the right
+        let mut median = points.len() / 2;
+        while median > 0 && key(&points[median - 1].0) == key(&points[median].0) {
+            median -= 1;
+        }
+        let right = points.split_off(median + 1);
+        let (pos, item) = points.pop().unwrap();
+
+        let ind = self.nodes.len();
+        self.nodes.push(Node {
+            pos,
+            item: Some(item),
+            split_x,
+            left: None,
+            right: None,
+        });
+        self.nodes[ind].left = self.build(points, !split_x);
+        self.nodes[ind].right = self.build(right, !split_x);
+        Some(ind)
+    }
+
+    fn rebuild(&mut self) {
+        let points = std::mem::take(&mut self.nodes)
+            .into_iter()
+            .filter_map(|node| node.item.map(|item| (node.pos, item)));
+        *self = KdTree::from_points(points);
+    }
+
+    fn find(&self, pos: Vec2D, item: &T) -> Option<usize>
+    where
+        T: PartialEq,
+    {
+        let mut current = self.root;
+        while let Some(ind) = current {
+            let node = &self.nodes[ind];
+            if node.pos == pos && node.item.as_ref() == Some(item) {
+                return Some(ind);
+            }
+            current = if node.side(pos) < 0.0 {
+                node.left
+            } else {
+                node.right
+            };
+        }
+        None
+    }
+
+    fn within_from<'a>(
+        &'a self,
+        ind: Option<usize>,
+        center: Vec2D,
+        radius: f64,
+        found: &mut Vec<(Vec2D, &'a T)>,
+    ) {
+        let Some(ind) = ind else {
+            return;
+        };
+        let node = &self.nodes[ind];
+        if let Some(item) = node.item.as_ref() {
+            if node.pos.dist(&center) <= radius {
+                found.push((node.pos, item));
+            }
+        }
+        let side = node.side(center);
+        if side < radius {
+            self.within_from(node.left, center, radius, found);
+        }
+        if side >= -radius {
+            self.within_from(node.right, center, radius, found);
+        }
+    }
+
+    fn nearest_from<'a>(&'a self, ind: Option<usize>, nearest: &mut Nearest<'a, T>) {
+        let Some(ind) = ind else {
+            return;
+        };
+        let node = &self.nodes[ind];
+        if let Some(item) = node.item.as_ref() {
+            nearest.push(node.pos, item);
+        }
+        let side = node.side(nearest.center);
+        let (near, far) = if side < 0.0 {
+            (node.left, node.right)
+        } else {
+            (node.right, node.left)
+        };
+        self.nearest_from(near, nearest);
+        if side.abs() < nearest.worst() {
+            self.nearest_from(far, nearest);
+        }
+    }
+}
+
+impl<T> Default for KdTree<T> {
+    fn default() -> KdTree<T> {
+        KdTree::new()
+    }
+}
+
+impl<T> SpatialIndex<T> for KdTree<T> {
+    fn insert(&mut self, pos: Vec2D, item: T) {
+        let ind = self.nodes.len();
+        let mut split_x = true;
+        let mut parent = None;
+        let mut current = self.root;
+        while let Some(current_ind) = current {
+            let node = &self.nodes[current_ind];
+            let left = node.side(pos) < 0.0;
+            parent = Some((current_ind, left));
+            split_x = !node.split_x;
+            current = if left { node.left } else { node.right };
+        }
+
+        self.nodes.push(Node {
+            pos,
+            item: Some(item),
+            split_x,
+            left: None,
+            right: None,
+        });
+        match parent {
+            None => self.root = Some(ind),
+            Some((parent_ind, true)) => self.nodes[parent_ind].left = Some(ind),
+            Some((parent_ind, false)) => self.nodes[parent_ind].right = Some(ind),
+        }
+        self.len += 1;
+    }
+
+    fn remove(&mut self, pos: Vec2D, item: &T) -> Option<T>
+    where
+        T: PartialEq,
+    {
+        let ind = self.find(pos, item)?;
+        let removed = self.nodes[ind].item.take();
+        self.len -= 1;
+        if 2 * self.len < self.nodes.len() {
+            self.rebuild();
+        }
+        removed
+    }
+
+    fn within(&self, center: Vec2D, radius: f64) -> Vec<(Vec2D, &T)> {
+        let mut found = vec![];
+        self.within_from(self.root, center, radius, &mut found);
+        found
+    }
+
+    fn nearest(&self, center: Vec2D, k: usize) -> Vec<(Vec2D, &T)> {
+        let mut nearest = Nearest::new(center, k);
+        self.nearest_from(self.root, &mut nearest);
+        nearest.into_vec()
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn clear(&mut self) {
+        *self = KdTree::new();
+    }
+}
@@ -0,0 +1,269 @@
+pub mod grid_hash;
+pub mod kdtree;
+pub mod quadtree;
+pub use grid_hash::GridHash;
+pub use kdtree::KdTree;
+pub use quadtree::QuadTree;
+
+use super::vec2d::Vec2D;
+
+/// Points in the plane carrying an item each, usually an index into a `Vec`
+/// owned by the caller
+pub trait SpatialIndex<T> {
+    fn insert(&mut self, pos: Vec2D, item: T);
+
+    /// Removes an item stored at exactly `pos`, returns it if it was found
+    fn remove(&mut self, pos: Vec2D, item: &T) -> Option<T>
+    where
+        T: PartialEq;
+
+    /// All items at most `radius` away from `center`, in no particular order
+    fn within(&self, center: Vec2D, radius: f64) -> Vec<(Vec2D, &T)>;
+
+    /// The `k` items closest to `center`, closest first
+    fn nearest(&self, center: Vec2D, k: usize) -> Vec<(Vec2D, &T)>;
+
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn clear(&mut self);
+}
+
+/// Collects the `k` closest candidates seen so far
+struct Nearest<'a, T> {
+    center: Vec2D,
+    k: usize,
+    found: Vec<(f64, Vec2D, &'a T)>,
+}
+
+impl<'a, T> Nearest<'a, T> {
+    fn new(center: Vec2D, k: usize) -> Nearest<'a, T> {
+        Nearest {
+            center,
+            k,
+            found: Vec::with_capacity(k + 1),
+        }
+    }
+
+    fn push(&mut self, pos: Vec2D, item: &'a T) {
+        let dist = pos.dist(&self.center);
+        if dist >= self.worst() {
+            return;
+        }
+        let ind = self.found.partition_point(|(d, _, _)| *d <= dist);
+        self.found.insert(ind, (dist, pos, item));
+        self.found.truncate(self.k);
+    }
+
+    /// Candidates further away than this can not improve the result
+    fn worst(&self) -> f64 {
+        if self.found.len() < self.k {
+            return f64::INFINITY;
+        }
+        self.found
+            .last()
+            .map(|(d, _, _)| *d)
+            .unwrap_or(f64::NEG_INFINITY)
+    }
+
+    fn into_vec(self) -> Vec<(Vec2D, &'a T)> {
+        self.found
+            .into_iter()
+            .map(|(_, pos, item)| (pos, item))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod spatial_tests {
+    use super::{GridHash, KdTree, QuadTree, SpatialIndex};
+    use crate::vec2d::Vec2D;
+
+    /// Deterministic points in [0,1000]^2 with a dense cluster and duplicates
+    fn points() -> Vec<Vec2D> {
+        let mut state: u64 = 12345;
+        let mut next = || {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (state >> 11) as f64 / (1u64 << 53) as f64
+        };
+        let mut pts = vec![];
+        for _ in 0..500 {
+            pts.push(Vec2D::new(1000.0 * next(), 1000.0 * next()));
+        }
+        for _ in 0..200 {
+            pts.push(Vec2D::new(500.0 + next(), 500.0 + next()));
+        }
+        pts.push(pts[3]);
+        pts.push(pts[3]);
+        pts
+    }
+
+    fn indices() -> Vec<Box<dyn SpatialIndex<usize>>> {
+        vec![
+            Box::new(GridHash::new(25.0)),
+            Box::new(QuadTree::new(Vec2D::default(), Vec2D::new(1000.0, 1000.0))),
+            Box::new(KdTree::new()),
+        ]
+    }
+
+    fn filled() -> Vec<Box<dyn SpatialIndex<usize>>> {
+        let mut result = indices();
+        for index in result.iter_mut() {
+            for (i, pt) in points().into_iter().enumerate() {
+                index.insert(pt, i);
+            }
+        }
+        result
+    }
+
+    fn brute_within(pts: &[Vec2D], center: Vec2D, radius: f64) -> Vec<usize> {
+        (0..pts.len())
+            .filter(|i| pts[*i].dist(&center) <= radius)
+            .collect()
+    }
+
+    fn sorted(found: Vec<(Vec2D, &usize)>) -> Vec<usize> {
+        let mut result: Vec<usize> = found.into_iter().map(|(_, i)| *i).collect();
+        result.sort();
+        result
+    }
+
+    #[test]
+    fn within_matches_brute_force() {
+        let pts = points();
+        let queries = [
+            (Vec2D::new(500.0, 500.0), 0.5),
+            (Vec2D::new(500.0, 500.0), 30.0),
+            (Vec2D::new(100.0, 900.0), 120.0),
+            (Vec2D::new(-50.0, -50.0), 100.0),
+            (pts[3], 0.0),
+            (Vec2D::new(500.0, 500.0), 2000.0),
+        ];
+        for index in filled() {
+            assert_eq!(index.len(), pts.len());
+            for (center, radius) in queries {
+                let expected = brute_within(&pts, center, radius);
+                assert_eq!(sorted(index.within(center, radius)), expected)
+            }
+        }
+    }
+
+    #[test]
+    fn within_huge_radius() {
+        let all: Vec<usize> = (0..points().len()).collect();
+        for index in filled() {
+            for radius in [f64::INFINITY, 1e300] {
+                let found = index.within(Vec2D::new(500.0, 500.0), radius);
+                assert_eq!(sorted(found), all);
+            }
+        }
+    }
+
+    #[test]
+    fn nearest_matches_brute_force() {
+        let pts = points();
+        let queries = [
+            Vec2D::new(500.5, 500.5),
+            Vec2D::new(10.0, 990.0),
+            Vec2D::new(-300.0, 400.0),
+            Vec2D::new(2000.0, 2000.0),
+        ];
+        for index in filled() {
+            for center in queries {
+                for k in [1, 5, 40] {
+                    let mut dists: Vec<f64> = pts.iter().map(|pt| pt.dist(&center)).collect();
+                    dists.sort_by(|d1, d2| d1.partial_cmp(d2).unwrap());
+                    let result: Vec<f64> = index
+                        .nearest(center, k)
+                        .iter()
+                        .map(|(pos, _)| pos.dist(&center))
+                        .collect();
+                    assert_eq!(result, dists[..k])
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn nearest_more_than_len() {
+        for mut index in indices() {
+            assert!(index.nearest(Vec2D::default(), 3).is_empty());
+            index.insert(Vec2D::new(1.0, 1.0), 0);
+            index.insert(Vec2D::new(5.0, 5.0), 1);
+            let result: Vec<usize> = index
+                .nearest(Vec2D::new(4.0, 4.0), 10)
+                .into_iter()
+                .map(|(_, i)| *i)
+                .collect();
+            assert_eq!(result, vec![1, 0])
+        }
+    }
+
+    #[test]
+    fn remove() {
+        let pts = points();
+        for mut index in filled() {
+            assert_eq!(index.remove(pts[10], &10), Some(10));
+            assert_eq!(index.remove(pts[10], &10), None);
+            assert_eq!(index.remove(pts[11], &12), None);
+            // duplicates are removed one at a time
+            assert_eq!(index.remove(pts[3], &700), Some(700));
+            assert_eq!(index.len(), pts.len() - 2);
+
+            let found = sorted(index.within(pts[3], 0.0));
+            assert_eq!(found, vec![3, 701]);
+            assert!(!sorted(index.within(pts[10], 0.0)).contains(&10));
+            let nearest = index.nearest(pts[10], 1);
+            assert_ne!(*nearest[0].1, 10)
+        }
+    }
+
+    #[test]
+    fn remove_all_and_clear() {
+        let pts = points();
+        for mut index in filled() {
+            for (i, pt) in pts.iter().enumerate().take(600) {
+                assert_eq!(index.remove(*pt, &i), Some(i));
+            }
+            assert_eq!(index.len(), pts.len() - 600);
+            let expected = brute_within(&pts, Vec2D::new(500.0, 500.0), 2000.0)
+                .into_iter()
+                .filter(|i| *i >= 600)
+                .collect::<Vec<usize>>();
+            assert_eq!(
+                sorted(index.within(Vec2D::new(500.0, 500.0), 2000.0)),
+                expected
+            );
+            index.clear();
+            assert!(index.is_empty());
+            assert!(index.within(Vec2D::new(500.0, 500.0), 2000.0).is_empty())
+        }
+    }
+
+    #[test]
+    fn points_outside_bounds() {
+        let mut index = QuadTree::new(Vec2D::default(), Vec2D::new(10.0, 10.0));
+        for i in 0..50 {
+            index.insert(Vec2D::new(-100.0 * i as f64, 20.0 * i as f64), i);
+        }
+        let found = sorted(index.within(Vec2D::new(-4900.0, 980.0), 1.0));
+        assert_eq!(found, vec![49]);
+        assert_eq!(*index.nearest(Vec2D::new(-1000.0, 0.0), 1)[0].1, 10)
+    }
+
+    #[test]
+    fn kdtree_from_points() {
+        let pts = points();
+        let index = KdTree::from_points(pts.iter().copied().zip(0..pts.len()));
+        let center = Vec2D::new(300.0, 600.0);
+        assert_eq!(
+            sorted(index.within(center, 150.0)),
+            brute_within(&pts, center, 150.0)
+        )
+    }
+}
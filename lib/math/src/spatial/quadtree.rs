@@ -0,0 +1,196 @@
+use super::{Nearest, SpatialIndex};
+use crate::vec2d::Vec2D;
+
+const NODE_CAPACITY: usize = 8;
+const MAX_DEPTH: usize = 16;
+
+/// Splits a rectangular region into quadrants once a node holds more than
+/// `NODE_CAPACITY` points, adapts to clustered points better than `GridHash`.
+/// Points outside the region are still stored correctly, they end up in the
+/// quadrants closest to them.
+#[derive(Debug, Clone)]
+pub struct QuadTree<T> {
+    root: Node<T>,
+    len: usize,
+}
+
+#[derive(Debug, Clone)]
+struct Node<T> {
+    min: Vec2D,
+    max: Vec2D,
+    depth: usize,
+    /// bounding box of all points ever inserted below this node
+    bounds: Option<[Vec2D; 2]>,
+    items: Vec<(Vec2D, T)>,
+    children: Vec<Node<T>>,
+}
+
+impl<T> Node<T> {
+    fn new(min: Vec2D, max: Vec2D, depth: usize) -> Node<T> {
+        Node {
+            min,
+            max,
+            depth,
+            bounds: None,
+            items: vec![],
+            children: vec![],
+        }
+    }
+
+    fn center(&self) -> Vec2D {
+        (self.min + self.max) / 2.0
+    }
+
+    fn quadrant(&self, pos: Vec2D) -> usize {
+        let center = self.center();
+        (pos.x >= center.x) as usize + 2 * (pos.y >= center.y) as usize
+    }
+
+    fn dist_to_bounds(&self, pos: Vec2D) -> f64 {
+        match self.bounds {
+            None => f64::INFINITY,
+            Some([min, max]) => {
+                let dx = (min.x - pos.x).max(pos.x - max.x).max(0.0);
+                let dy = (min.y - pos.y).max(pos.y - max.y).max(0.0);
+                dx.hypot(dy)
+            }
+        }
+    }
+
+    fn insert(&mut self, pos: Vec2D, item: T) {
+        self.bounds = Some(match self.bounds {
+            None => [pos, pos],
+            Some([min, max]) => [
+                Vec2D::new(min.x.min(pos.x), min.y.min(pos.y)),
+                Vec2D::new(max.x.max(pos.x), max.y.max(pos.y)),
+            ],
+        });
+
+        if !self.children.is_empty() {
+            let quadrant = self.quadrant(pos);
+            self.children[quadrant].insert(pos, item);
+            return;
+        }
+
+        self.items.push((pos, item));
+        if self.items.len() > NODE_CAPACITY && self.depth < MAX_DEPTH {
+            self.split();
+        }
+    }
+
+    fn split(&mut self) {
+        let center = self.center();
+        let (min, max) = (self.min, self.max);
+        self.children = vec![
+            Node::new(min, center, self.depth + 1),
+            Node::new(
+                Vec2D::new(center.x, min.y),
+                Vec2D::new(max.x, center.y),
+                self.depth + 1,
+            ),
+            Node::new(
+                Vec2D::new(min.x, center.y),
+                Vec2D::new(center.x, max.y),
+                self.depth + 1,
+            ),
+            Node::new(center, max, self.depth + 1),
+        ];
+        for (pos, item) in std::mem::take(&mut self.items) {
+            let quadrant = self.quadrant(pos);
+            self.children[quadrant].insert(pos, item);
+        }
+    }
+
+    fn remove(&mut self, pos: Vec2D, item: &T) -> Option<T>
+    where
+        T: PartialEq,
+    {
+        if !self.children.is_empty() {
+            let quadrant = self.quadrant(pos);
+            return self.children[quadrant].remove(pos, item);
+        }
+        let ind = self
+            .items
+            .iter()
+            .position(|(other_pos, other)| *other_pos == pos && other == item)?;
+        Some(self.items.swap_remove(ind).1)
+    }
+
+    fn within<'a>(&'a self, center: Vec2D, radius: f64, found: &mut Vec<(Vec2D, &'a T)>) {
+        if self.dist_to_bounds(center) > radius {
+            return;
+        }
+        for (pos, item) in self.items.iter() {
+            if pos.dist(&center) <= radius {
+                found.push((*pos, item));
+            }
+        }
+        for child in self.children.iter() {
+            child.within(center, radius, found);
+        }
+    }
+
+    fn nearest<'a>(&'a self, nearest: &mut Nearest<'a, T>) {
+        if self.dist_to_bounds(nearest.center) >= nearest.worst() {
+            return;
+        }
+        for (pos, item) in self.items.iter() {
+            nearest.push(*pos, item);
+        }
+        let mut children: Vec<&Node<T>> = self.children.iter().collect();
+        children.sort_by(|c1, c2| {
+            let d1 = c1.dist_to_bounds(nearest.center);
+            d1.partial_cmp(&c2.dist_to_bounds(nearest.center)).unwrap()
+        });
+        for child in children {
+            child.nearest(nearest);
+        }
+    }
+}
+
+impl<T> QuadTree<T> {
+    /// `min` and `max` are corners of the region that gets subdivided
+    pub fn new(min: Vec2D, max: Vec2D) -> QuadTree<T> {
+        QuadTree {
+            root: Node::new(min, max, 0),
+            len: 0,
+        }
+    }
+}
+
+impl<T> SpatialIndex<T> for QuadTree<T> {
+    fn insert(&mut self, pos: Vec2D, item: T) {
+        self.root.insert(pos, item);
+        self.len += 1;
+    }
+
+    fn remove(&mut self, pos: Vec2D, item: &T) -> Option<T>
+    where
+        T: PartialEq,
+    {
+        let removed = self.root.remove(pos, item)?;
+        self.len -= 1;
+        Some(removed)
+    }
+
+    fn within(&self, center: Vec2D, radius: f64) -> Vec<(Vec2D, &T)> {
+        let mut found = vec![];
+        self.root.within(center, radius, &mut found);
+        found
+    }
+
+    fn nearest(&self, center: Vec2D, k: usize) -> Vec<(Vec2D, &T)> {
+        let mut nearest = Nearest::new(center, k);
+        self.root.nearest(&mut nearest);
+        nearest.into_vec()
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn clear(&mut self) {
+        self.root = Node::new(self.root.min, self.root.max, 0);
+        self.len = 0;
+    }
+}
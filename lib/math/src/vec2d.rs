@@ -64,6 +64,11 @@ impl Vec2D {
     pub fn dot(self, other: Vec2D) -> f64 {
         self.x * other.x + self.y * other.y
     }
+
+    /// z component of the 3D cross product, positive if `other` is counter-clockwise from `self`
+    pub fn cross(self, other: Vec2D) -> f64 {
+        self.x * other.y - self.y * other.x
+    }
 }
 
 impl From<[f64; 2]> for Vec2D {
@@ -0,0 +1,282 @@
+use std::f64::consts::PI;
+use std::{
+    fmt,
+    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+};
+
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub struct Vec3D {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+impl Vec3D {
+    pub fn new(x: f64, y: f64, z: f64) -> Vec3D {
+        Vec3D { x, y, z }
+    }
+
+    /// theta is the angle to the z-axis, phi the angle in the xy-plane
+    pub fn from_spherical(r: f64, theta: f64, phi: f64) -> Vec3D {
+        Vec3D {
+            x: r * theta.sin() * phi.cos(),
+            y: r * theta.sin() * phi.sin(),
+            z: r * theta.cos(),
+        }
+    }
+
+    /// Returns (r, theta, phi), see `from_spherical`
+    pub fn spherical(&self) -> (f64, f64, f64) {
+        let r = self.abs();
+        if r == 0.0 {
+            return (0.0, 0.0, 0.0);
+        }
+        (r, (self.z / r).acos(), self.y.atan2(self.x))
+    }
+
+    pub fn abs(&self) -> f64 {
+        (self.x * self.x + self.y * self.y + self.z * self.z).sqrt()
+    }
+
+    pub fn set_abs(&mut self, r: f64) {
+        let abs = self.abs();
+        if abs == 0.0 {
+            return;
+        }
+        *self *= r / abs;
+    }
+
+    pub fn normalize(self) -> Vec3D {
+        let abs = self.abs();
+        if abs == 0.0 {
+            return self;
+        }
+        self / abs
+    }
+
+    pub fn dist(&self, other: &Vec3D) -> f64 {
+        (*self - *other).abs()
+    }
+
+    pub fn rand_unit() -> Vec3D {
+        let phi = rand::random::<f64>() * 2.0 * PI;
+        let z = 2.0 * rand::random::<f64>() - 1.0;
+        Vec3D::from_spherical(1.0, z.acos(), phi)
+    }
+
+    pub fn dot(self, other: Vec3D) -> f64 {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+
+    pub fn cross(self, other: Vec3D) -> Vec3D {
+        Vec3D {
+            x: self.y * other.z - self.z * other.y,
+            y: self.z * other.x - self.x * other.z,
+            z: self.x * other.y - self.y * other.x,
+        }
+    }
+}
+
+impl From<[f64; 3]> for Vec3D {
+    fn from(pos: [f64; 3]) -> Vec3D {
+        Vec3D {
+            x: pos[0],
+            y: pos[1],
+            z: pos[2],
+        }
+    }
+}
+
+impl Add for Vec3D {
+    type Output = Vec3D;
+    fn add(self, other: Vec3D) -> Self::Output {
+        Vec3D {
+            x: self.x + other.x,
+            y: self.y + other.y,
+            z: self.z + other.z,
+        }
+    }
+}
+
+impl AddAssign for Vec3D {
+    fn add_assign(&mut self, other: Vec3D) {
+        self.x += other.x;
+        self.y += other.y;
+        self.z += other.z;
+    }
+}
+
+impl Sub for Vec3D {
+    type Output = Vec3D;
+    fn sub(self, other: Vec3D) -> Self::Output {
+        Vec3D {
+            x: self.x - other.x,
+            y: self.y - other.y,
+            z: self.z - other.z,
+        }
+    }
+}
+
+impl SubAssign for Vec3D {
+    fn sub_assign(&mut self, other: Vec3D) {
+        self.x -= other.x;
+        self.y -= other.y;
+        self.z -= other.z;
+    }
+}
+
+impl Mul<f64> for Vec3D {
+    type Output = Vec3D;
+    fn mul(self, rhs: f64) -> Self::Output {
+        Vec3D {
+            x: self.x * rhs,
+            y: self.y * rhs,
+            z: self.z * rhs,
+        }
+    }
+}
+
+impl Mul<Vec3D> for f64 {
+    type Output = Vec3D;
+    fn mul(self, rhs: Vec3D) -> Self::Output {
+        Vec3D {
+            x: self * rhs.x,
+            y: self * rhs.y,
+            z: self * rhs.z,
+        }
+    }
+}
+
+impl MulAssign<f64> for Vec3D {
+    fn mul_assign(&mut self, rhs: f64) {
+        self.x *= rhs;
+        self.y *= rhs;
+        self.z *= rhs;
+    }
+}
+
+impl Div<f64> for Vec3D {
+    type Output = Vec3D;
+    fn div(self, rhs: f64) -> Self::Output {
+        Vec3D {
+            x: self.x / rhs,
+            y: self.y / rhs,
+            z: self.z / rhs,
+        }
+    }
+}
+
+impl DivAssign<f64> for Vec3D {
+    fn div_assign(&mut self, rhs: f64) {
+        self.x /= rhs;
+        self.y /= rhs;
+        self.z /= rhs;
+    }
+}
+
+impl Neg for Vec3D {
+    type Output = Vec3D;
+    fn neg(self) -> Self::Output {
+        Vec3D {
+            x: -self.x,
+            y: -self.y,
+            z: -self.z,
+        }
+    }
+}
+
+impl fmt::Display for Vec3D {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({},{},{})", self.x, self.y, self.z)
+    }
+}
+
+#[cfg(test)]
+mod vec3d_tests {
+    use super::Vec3D;
+    use std::f64::consts::PI;
+
+    fn assert_close(result: Vec3D, expected: Vec3D) {
+        assert!(result.dist(&expected) < 1e-9, "{result} != {expected}")
+    }
+
+    #[test]
+    fn add_sub() {
+        let v1 = Vec3D::new(1.0, 2.0, 3.0);
+        let v2 = Vec3D::new(4.0, 5.0, 6.0);
+        assert_eq!(v1 + v2, Vec3D::new(5.0, 7.0, 9.0));
+        assert_eq!(v2 - v1, Vec3D::new(3.0, 3.0, 3.0));
+        assert_eq!(-v1, Vec3D::new(-1.0, -2.0, -3.0))
+    }
+
+    #[test]
+    fn assign_ops() {
+        let mut result = Vec3D::new(1.0, 2.0, 3.0);
+        result += Vec3D::new(1.0, 1.0, 1.0);
+        result *= 2.0;
+        result -= Vec3D::new(0.0, 2.0, 4.0);
+        result /= 2.0;
+        assert_eq!(result, Vec3D::new(2.0, 2.0, 2.0))
+    }
+
+    #[test]
+    fn scalar_mul() {
+        let v = Vec3D::new(1.0, -2.0, 0.5);
+        assert_eq!(v * 2.0, 2.0 * v);
+        assert_eq!(v / 0.5, Vec3D::new(2.0, -4.0, 1.0))
+    }
+
+    #[test]
+    fn dot_cross() {
+        let x = Vec3D::new(1.0, 0.0, 0.0);
+        let y = Vec3D::new(0.0, 1.0, 0.0);
+        assert_eq!(x.dot(y), 0.0);
+        assert_eq!(x.cross(y), Vec3D::new(0.0, 0.0, 1.0));
+        assert_eq!(y.cross(x), Vec3D::new(0.0, 0.0, -1.0))
+    }
+
+    #[test]
+    fn normalize() {
+        let result = Vec3D::new(3.0, 0.0, 4.0).normalize();
+        assert_close(result, Vec3D::new(0.6, 0.0, 0.8));
+        assert_eq!(Vec3D::default().normalize(), Vec3D::default())
+    }
+
+    #[test]
+    fn set_abs() {
+        let mut result = Vec3D::new(0.0, 2.0, 0.0);
+        result.set_abs(5.0);
+        assert_close(result, Vec3D::new(0.0, 5.0, 0.0))
+    }
+
+    #[test]
+    fn spherical_roundtrip() {
+        let v = Vec3D::new(1.0, -2.0, 3.0);
+        let (r, theta, phi) = v.spherical();
+        assert_close(Vec3D::from_spherical(r, theta, phi), v)
+    }
+
+    #[test]
+    fn spherical_axes() {
+        assert_close(
+            Vec3D::from_spherical(2.0, 0.0, 1.0),
+            Vec3D::new(0.0, 0.0, 2.0),
+        );
+        assert_close(
+            Vec3D::from_spherical(1.0, PI / 2.0, PI / 2.0),
+            Vec3D::new(0.0, 1.0, 0.0),
+        )
+    }
+
+    #[test]
+    fn rand_unit() {
+        let result = Vec3D::rand_unit().abs();
+        assert!((result - 1.0).abs() < 1e-9)
+    }
+
+    #[test]
+    fn display_vec() {
+        let result = format!("{}", Vec3D::new(1.0, 2.0, 3.0));
+        assert_eq!(result, "(1,2,3)")
+    }
+}
@@ -42,8 +42,8 @@ fn run_app(args: &mut env::Args) {
         22 => App::new(juliaset::JuliaSet::new()).run(),
         23 => App::new(supershape::SuperShape::new()).run(),
         24 => App::new(perlinnoise::PerlinNoise::new()).run(),
-        25 => panic!("Spherical Geometry not implemented"),
-        26 => panic!("3D supershapes not implemented"),
+        25 => App::new(supershape3d::SuperShape3D::sphere()).run(),
+        26 => App::new(supershape3d::SuperShape3D::new()).run(),
         27 => App::new(fireworks::Fireworks::new()).run(),
         28 => App::new(metaballs::Metaballs::new()).run(),
         29 => App::new(smartrockets::SmartRockets::new()).run(),
@@ -58,15 +58,15 @@ fn run_app(args: &mut env::Args) {
         38 => App::new(wordinteractor::WordInteractor::new()).run(),
         39 => App::new(madlibs::MadLibs::new()).run(),
         40 => App::new(tfidf::TfIdf::new()).run(),
-        41 => panic!("Clappy Bird not implemented"),
+        41 => App::new(clappybird::ClappyBird::new()).run(),
         42 => App::new(markovchain::MarkovChain::new()).run(),
         43 => App::new(contextfreegrammar::ContextfreeGrammar::new()).run(),
         44 => App::new(afinn::Afinn::new()).run(),
-        45 => panic!("Firebase not implemented"),
+        45 => App::new(savingdrawings::SavingDrawings::new()).run(),
         46 => App::new(asteroids::Asteroids::new()).run(),
         47 => App::new(pixelsorting::PixelSorting::new()).run(),
-        48 => panic!("White House social media data not implemented"),
-        49 => panic!("White House social media mosaic not implemented"),
+        48 => App::new(socialmedia::SocialMedia::new()).run(),
+        49 => App::new(photomosaic::PhotoMosaic::new()).run(),
         50 => App::new(circlepacking::CirclePacking::new()).run(),
         51 => App::new(astar::AStar::new()).run(),
         _ => panic!("Challenge {num} does not exist"),